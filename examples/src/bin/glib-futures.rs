@@ -4,6 +4,7 @@ use gst::prelude::*;
 extern crate glib;
 
 extern crate futures;
+use futures::future;
 use futures::prelude::*;
 
 use std::env;
@@ -25,7 +26,7 @@ fn example_main() {
     assert_ne!(ret, gst::StateChangeReturn::Failure);
 
     let messages = gst::BusStream::new(&bus)
-        .for_each(|msg| {
+        .take_while(|msg| {
             use gst::MessageView;
 
             let quit = match msg.view() {
@@ -42,15 +43,11 @@ fn example_main() {
                 _ => false,
             };
 
-            if quit {
-                Err(())
-            } else {
-                Ok(())
-            }
+            future::ready(!quit)
         })
-        .and_then(|_| Ok(()));
+        .for_each(|_| future::ready(()));
 
-    let _ = ctx.block_on(messages);
+    ctx.block_on(messages);
 
     let ret = pipeline.set_state(gst::State::Null);
     assert_ne!(ret, gst::StateChangeReturn::Failure);
@@ -8,6 +8,7 @@
 
 extern crate glib_sys as glib_ffi;
 extern crate gobject_sys as gobject_ffi;
+#[macro_use]
 extern crate gstreamer as gst;
 extern crate gstreamer_base_sys as ffi;
 extern crate gstreamer_sys as gst_ffi;
@@ -65,3 +66,6 @@ pub mod prelude {
 }
 
 mod utils;
+
+#[cfg(any(feature = "subclassing", feature = "dox"))]
+pub mod subclass;
@@ -0,0 +1,960 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::ops;
+use std::os::raw::c_char;
+use std::ptr;
+
+use ffi;
+use glib_ffi;
+use gst_ffi;
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use gst;
+use gst::prelude::*;
+use gst::subclass::prelude::*;
+use gst::subclass::ElementInstanceStruct;
+
+use Aggregator;
+use AggregatorPad;
+
+pub trait AggregatorImpl: ElementImpl + Send + Sync + 'static {
+    fn flush(&self, aggregator: &Aggregator) -> gst::FlowReturn {
+        self.parent_flush(aggregator)
+    }
+
+    fn clip(
+        &self,
+        aggregator: &Aggregator,
+        aggregator_pad: &AggregatorPad,
+        buffer: gst::Buffer,
+    ) -> Option<gst::Buffer> {
+        self.parent_clip(aggregator, aggregator_pad, buffer)
+    }
+
+    fn finish_buffer(&self, aggregator: &Aggregator, buffer: gst::Buffer) -> gst::FlowReturn {
+        self.parent_finish_buffer(aggregator, buffer)
+    }
+
+    fn sink_event(
+        &self,
+        aggregator: &Aggregator,
+        aggregator_pad: &AggregatorPad,
+        event: gst::Event,
+    ) -> bool {
+        self.parent_sink_event(aggregator, aggregator_pad, event)
+    }
+
+    fn sink_query(
+        &self,
+        aggregator: &Aggregator,
+        aggregator_pad: &AggregatorPad,
+        query: &mut gst::QueryRef,
+    ) -> bool {
+        self.parent_sink_query(aggregator, aggregator_pad, query)
+    }
+
+    fn src_event(&self, aggregator: &Aggregator, event: gst::Event) -> bool {
+        self.parent_src_event(aggregator, event)
+    }
+
+    fn src_query(&self, aggregator: &Aggregator, query: &mut gst::QueryRef) -> bool {
+        self.parent_src_query(aggregator, query)
+    }
+
+    /// Produces output from the queued input buffers.
+    ///
+    /// `timeout` is `true` if this is called because the latency deadline was
+    /// reached in live mode, in which case not all pads necessarily have data.
+    fn aggregate(&self, aggregator: &Aggregator, timeout: bool) -> gst::FlowReturn;
+
+    fn start(&self, aggregator: &Aggregator) -> bool {
+        self.parent_start(aggregator)
+    }
+
+    fn stop(&self, aggregator: &Aggregator) -> bool {
+        self.parent_stop(aggregator)
+    }
+
+    fn get_next_time(&self, aggregator: &Aggregator) -> gst::ClockTime {
+        self.parent_get_next_time(aggregator)
+    }
+
+    fn create_new_pad(
+        &self,
+        aggregator: &Aggregator,
+        templ: &gst::PadTemplate,
+        req_name: Option<&str>,
+        caps: Option<&gst::CapsRef>,
+    ) -> Option<AggregatorPad> {
+        self.parent_create_new_pad(aggregator, templ, req_name, caps)
+    }
+
+    fn update_src_caps(
+        &self,
+        aggregator: &Aggregator,
+        caps: &gst::CapsRef,
+    ) -> Result<gst::Caps, gst::FlowError> {
+        self.parent_update_src_caps(aggregator, caps)
+    }
+
+    fn fixate_src_caps(&self, aggregator: &Aggregator, caps: gst::Caps) -> gst::Caps {
+        self.parent_fixate_src_caps(aggregator, caps)
+    }
+
+    fn negotiated_src_caps(&self, aggregator: &Aggregator, caps: &gst::CapsRef) -> bool {
+        self.parent_negotiated_src_caps(aggregator, caps)
+    }
+}
+
+pub trait AggregatorImplExt {
+    fn parent_flush(&self, aggregator: &Aggregator) -> gst::FlowReturn;
+
+    fn parent_clip(
+        &self,
+        aggregator: &Aggregator,
+        aggregator_pad: &AggregatorPad,
+        buffer: gst::Buffer,
+    ) -> Option<gst::Buffer>;
+
+    fn parent_finish_buffer(&self, aggregator: &Aggregator, buffer: gst::Buffer)
+        -> gst::FlowReturn;
+
+    fn parent_sink_event(
+        &self,
+        aggregator: &Aggregator,
+        aggregator_pad: &AggregatorPad,
+        event: gst::Event,
+    ) -> bool;
+
+    fn parent_sink_query(
+        &self,
+        aggregator: &Aggregator,
+        aggregator_pad: &AggregatorPad,
+        query: &mut gst::QueryRef,
+    ) -> bool;
+
+    fn parent_src_event(&self, aggregator: &Aggregator, event: gst::Event) -> bool;
+
+    fn parent_src_query(&self, aggregator: &Aggregator, query: &mut gst::QueryRef) -> bool;
+
+    fn parent_start(&self, aggregator: &Aggregator) -> bool;
+
+    fn parent_stop(&self, aggregator: &Aggregator) -> bool;
+
+    fn parent_get_next_time(&self, aggregator: &Aggregator) -> gst::ClockTime;
+
+    fn parent_create_new_pad(
+        &self,
+        aggregator: &Aggregator,
+        templ: &gst::PadTemplate,
+        req_name: Option<&str>,
+        caps: Option<&gst::CapsRef>,
+    ) -> Option<AggregatorPad>;
+
+    fn parent_update_src_caps(
+        &self,
+        aggregator: &Aggregator,
+        caps: &gst::CapsRef,
+    ) -> Result<gst::Caps, gst::FlowError>;
+
+    fn parent_fixate_src_caps(&self, aggregator: &Aggregator, caps: gst::Caps) -> gst::Caps;
+
+    fn parent_negotiated_src_caps(&self, aggregator: &Aggregator, caps: &gst::CapsRef) -> bool;
+}
+
+impl<T: AggregatorImpl + ObjectSubclass> AggregatorImplExt for T
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn parent_flush(&self, aggregator: &Aggregator) -> gst::FlowReturn {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .flush
+                .map(|f| from_glib(f(aggregator.to_glib_none().0)))
+                .unwrap_or(gst::FlowReturn::Ok)
+        }
+    }
+
+    fn parent_clip(
+        &self,
+        aggregator: &Aggregator,
+        aggregator_pad: &AggregatorPad,
+        buffer: gst::Buffer,
+    ) -> Option<gst::Buffer> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            match (*parent_class).clip {
+                None => Some(buffer),
+                Some(ref func) => from_glib_full(func(
+                    aggregator.to_glib_none().0,
+                    aggregator_pad.to_glib_none().0,
+                    buffer.into_ptr(),
+                )),
+            }
+        }
+    }
+
+    fn parent_finish_buffer(
+        &self,
+        aggregator: &Aggregator,
+        buffer: gst::Buffer,
+    ) -> gst::FlowReturn {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .finish_buffer
+                .map(|f| from_glib(f(aggregator.to_glib_none().0, buffer.into_ptr())))
+                .unwrap_or(gst::FlowReturn::Ok)
+        }
+    }
+
+    fn parent_sink_event(
+        &self,
+        aggregator: &Aggregator,
+        aggregator_pad: &AggregatorPad,
+        event: gst::Event,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .sink_event
+                .map(|f| {
+                    from_glib(f(
+                        aggregator.to_glib_none().0,
+                        aggregator_pad.to_glib_none().0,
+                        event.into_ptr(),
+                    ))
+                })
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_sink_query(
+        &self,
+        aggregator: &Aggregator,
+        aggregator_pad: &AggregatorPad,
+        query: &mut gst::QueryRef,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .sink_query
+                .map(|f| {
+                    from_glib(f(
+                        aggregator.to_glib_none().0,
+                        aggregator_pad.to_glib_none().0,
+                        query.as_mut_ptr(),
+                    ))
+                })
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_src_event(&self, aggregator: &Aggregator, event: gst::Event) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .src_event
+                .map(|f| from_glib(f(aggregator.to_glib_none().0, event.into_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_src_query(&self, aggregator: &Aggregator, query: &mut gst::QueryRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .src_query
+                .map(|f| from_glib(f(aggregator.to_glib_none().0, query.as_mut_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_start(&self, aggregator: &Aggregator) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .start
+                .map(|f| from_glib(f(aggregator.to_glib_none().0)))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_stop(&self, aggregator: &Aggregator) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .stop
+                .map(|f| from_glib(f(aggregator.to_glib_none().0)))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_get_next_time(&self, aggregator: &Aggregator) -> gst::ClockTime {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .get_next_time
+                .map(|f| from_glib(f(aggregator.to_glib_none().0)))
+                .unwrap_or(gst::CLOCK_TIME_NONE)
+        }
+    }
+
+    fn parent_create_new_pad(
+        &self,
+        aggregator: &Aggregator,
+        templ: &gst::PadTemplate,
+        req_name: Option<&str>,
+        caps: Option<&gst::CapsRef>,
+    ) -> Option<AggregatorPad> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            let caps_ptr = caps.map(|caps| caps.as_ptr()).unwrap_or(ptr::null());
+
+            (*parent_class)
+                .create_new_pad
+                .map(|f| {
+                    from_glib_none(f(
+                        aggregator.to_glib_none().0,
+                        templ.to_glib_none().0,
+                        req_name.to_glib_none().0,
+                        caps_ptr,
+                    ))
+                })
+                .unwrap_or(None)
+        }
+    }
+
+    fn parent_update_src_caps(
+        &self,
+        aggregator: &Aggregator,
+        caps: &gst::CapsRef,
+    ) -> Result<gst::Caps, gst::FlowError> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .update_src_caps
+                .map(|f| {
+                    let mut out_caps = ptr::null_mut();
+                    let flow_ret: gst::FlowReturn = from_glib(f(
+                        aggregator.to_glib_none().0,
+                        caps.as_mut_ptr(),
+                        &mut out_caps,
+                    ));
+                    flow_ret.into_result().map(|_| from_glib_full(out_caps))
+                })
+                .unwrap_or(Err(gst::FlowError::Error))
+        }
+    }
+
+    fn parent_fixate_src_caps(&self, aggregator: &Aggregator, caps: gst::Caps) -> gst::Caps {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+
+            match (*parent_class).fixate_src_caps {
+                Some(ref f) => from_glib_full(f(aggregator.to_glib_none().0, caps.into_ptr())),
+                None => caps,
+            }
+        }
+    }
+
+    fn parent_negotiated_src_caps(&self, aggregator: &Aggregator, caps: &gst::CapsRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorClass;
+            (*parent_class)
+                .negotiated_src_caps
+                .map(|f| from_glib(f(aggregator.to_glib_none().0, caps.as_mut_ptr())))
+                .unwrap_or(false)
+        }
+    }
+}
+
+/// Rust class struct for `GstAggregator` and all its subclasses.
+#[repr(C)]
+pub struct AggregatorClass(ffi::GstAggregatorClass);
+
+unsafe impl IsClassFor for AggregatorClass {
+    type Instance = Aggregator;
+}
+
+unsafe impl Send for AggregatorClass {}
+unsafe impl Sync for AggregatorClass {}
+
+impl ops::Deref for AggregatorClass {
+    type Target = gst::subclass::ElementClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for AggregatorClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl ElementClassSubclassExt for AggregatorClass {}
+
+unsafe impl<T: ObjectSubclass + AggregatorImpl> IsSubclassable<T> for AggregatorClass
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn override_vfuncs(&mut self) {
+        <gst::subclass::ElementClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstAggregatorClass);
+            klass.flush = Some(aggregator_flush::<T>);
+            klass.clip = Some(aggregator_clip::<T>);
+            klass.finish_buffer = Some(aggregator_finish_buffer::<T>);
+            klass.sink_event = Some(aggregator_sink_event::<T>);
+            klass.sink_query = Some(aggregator_sink_query::<T>);
+            klass.src_event = Some(aggregator_src_event::<T>);
+            klass.src_query = Some(aggregator_src_query::<T>);
+            klass.aggregate = Some(aggregator_aggregate::<T>);
+            klass.start = Some(aggregator_start::<T>);
+            klass.stop = Some(aggregator_stop::<T>);
+            klass.get_next_time = Some(aggregator_get_next_time::<T>);
+            klass.create_new_pad = Some(aggregator_create_new_pad::<T>);
+            klass.update_src_caps = Some(aggregator_update_src_caps::<T>);
+            klass.fixate_src_caps = Some(aggregator_fixate_src_caps::<T>);
+            klass.negotiated_src_caps = Some(aggregator_negotiated_src_caps::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn aggregator_flush<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+) -> gst_ffi::GstFlowReturn
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(
+        &wrap,
+        &instance.as_ref().panicked(),
+        gst::FlowReturn::Error,
+        { imp.flush(&wrap) }
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_clip<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    aggregator_pad: *mut ffi::GstAggregatorPad,
+    buffer: *mut gst_ffi::GstBuffer,
+) -> *mut gst_ffi::GstBuffer
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    let ret = gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), None, {
+        imp.clip(
+            &wrap,
+            &from_glib_borrow(aggregator_pad),
+            from_glib_full(buffer),
+        )
+    });
+
+    ret.map(|r| r.into_ptr()).unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "C" fn aggregator_finish_buffer<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    buffer: *mut gst_ffi::GstBuffer,
+) -> gst_ffi::GstFlowReturn
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(
+        &wrap,
+        &instance.as_ref().panicked(),
+        gst::FlowReturn::Error,
+        { imp.finish_buffer(&wrap, from_glib_full(buffer)) }
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_sink_event<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    aggregator_pad: *mut ffi::GstAggregatorPad,
+    event: *mut gst_ffi::GstEvent,
+) -> glib_ffi::gboolean
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.sink_event(
+            &wrap,
+            &from_glib_borrow(aggregator_pad),
+            from_glib_full(event),
+        )
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_sink_query<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    aggregator_pad: *mut ffi::GstAggregatorPad,
+    query: *mut gst_ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.sink_query(
+            &wrap,
+            &from_glib_borrow(aggregator_pad),
+            gst::QueryRef::from_mut_ptr(query),
+        )
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_src_event<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    event: *mut gst_ffi::GstEvent,
+) -> glib_ffi::gboolean
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.src_event(&wrap, from_glib_full(event))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_src_query<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    query: *mut gst_ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.src_query(&wrap, gst::QueryRef::from_mut_ptr(query))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_aggregate<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    timeout: glib_ffi::gboolean,
+) -> gst_ffi::GstFlowReturn
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(
+        &wrap,
+        &instance.as_ref().panicked(),
+        gst::FlowReturn::Error,
+        { imp.aggregate(&wrap, from_glib(timeout)) }
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_start<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+) -> glib_ffi::gboolean
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.start(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_stop<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+) -> glib_ffi::gboolean
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.stop(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_get_next_time<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+) -> gst_ffi::GstClockTime
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(
+        &wrap,
+        &instance.as_ref().panicked(),
+        gst::CLOCK_TIME_NONE,
+        { imp.get_next_time(&wrap) }
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_create_new_pad<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    templ: *mut gst_ffi::GstPadTemplate,
+    req_name: *const c_char,
+    caps: *const gst_ffi::GstCaps,
+) -> *mut ffi::GstAggregatorPad
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), None, {
+        let req_name: Option<String> = from_glib_none(req_name);
+
+        imp.create_new_pad(
+            &wrap,
+            &from_glib_borrow(templ),
+            req_name.as_ref().map(|s| s.as_str()),
+            if caps.is_null() {
+                None
+            } else {
+                Some(gst::CapsRef::from_ptr(caps))
+            },
+        )
+    })
+    .to_glib_full()
+}
+
+unsafe extern "C" fn aggregator_update_src_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    caps: *mut gst_ffi::GstCaps,
+    res: *mut *mut gst_ffi::GstCaps,
+) -> gst_ffi::GstFlowReturn
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    *res = ptr::null_mut();
+
+    gst_panic_to_error!(
+        &wrap,
+        &instance.as_ref().panicked(),
+        gst::FlowReturn::Error,
+        {
+            match imp.update_src_caps(&wrap, gst::CapsRef::from_ptr(caps)) {
+                Ok(res_caps) => {
+                    *res = res_caps.into_ptr();
+                    gst::FlowReturn::Ok
+                }
+                Err(err) => gst::FlowReturn::from_error(err),
+            }
+        }
+    )
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_fixate_src_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    caps: *mut gst_ffi::GstCaps,
+) -> *mut gst_ffi::GstCaps
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(
+        &wrap,
+        &instance.as_ref().panicked(),
+        gst::Caps::new_empty(),
+        { imp.fixate_src_caps(&wrap, from_glib_full(caps)) }
+    )
+    .into_ptr()
+}
+
+unsafe extern "C" fn aggregator_negotiated_src_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregator,
+    caps: *mut gst_ffi::GstCaps,
+) -> glib_ffi::gboolean
+where
+    T: AggregatorImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Aggregator = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.negotiated_src_caps(&wrap, gst::CapsRef::from_ptr(caps))
+    })
+    .to_glib()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib;
+    use glib::subclass;
+    use std::sync::atomic;
+
+    use subclass::prelude::*;
+    use AggregatorExtManual;
+    use AggregatorPadExt;
+
+    pub mod imp {
+        use super::*;
+
+        pub struct TestAggregatorPad;
+
+        impl ObjectSubclass for TestAggregatorPad {
+            const NAME: &'static str = "TestAggregatorPad";
+            type ParentType = AggregatorPad;
+            type Instance = subclass::simple::InstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                TestAggregatorPad
+            }
+        }
+
+        impl ObjectImpl for TestAggregatorPad {
+            glib_object_impl!();
+        }
+
+        impl PadImpl for TestAggregatorPad {}
+
+        impl AggregatorPadImpl for TestAggregatorPad {}
+
+        pub struct TestAggregator {
+            pub(super) n_buffers: atomic::AtomicU32,
+        }
+
+        impl ObjectSubclass for TestAggregator {
+            const NAME: &'static str = "TestAggregator";
+            type ParentType = Aggregator;
+            type Instance = ElementInstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                Self {
+                    n_buffers: atomic::AtomicU32::new(0),
+                }
+            }
+
+            fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+                klass.set_metadata(
+                    "Test Aggregator",
+                    "Generic",
+                    "Forwards buffers of all sink pads",
+                    "Sebastian Dröge <sebastian@centricular.com>",
+                );
+
+                let caps = gst::Caps::new_simple("foo/bar", &[]);
+                let src_pad_template = gst::PadTemplate::new_with_gtype(
+                    "src",
+                    gst::PadDirection::Src,
+                    gst::PadPresence::Always,
+                    &caps,
+                    AggregatorPad::static_type(),
+                );
+                klass.add_pad_template(src_pad_template);
+
+                let caps = gst::Caps::new_any();
+                let sink_pad_template = gst::PadTemplate::new_with_gtype(
+                    "sink_%u",
+                    gst::PadDirection::Sink,
+                    gst::PadPresence::Request,
+                    &caps,
+                    TestAggregatorPad::get_type(),
+                );
+                klass.add_pad_template(sink_pad_template);
+            }
+        }
+
+        impl ObjectImpl for TestAggregator {
+            glib_object_impl!();
+        }
+
+        impl ElementImpl for TestAggregator {}
+
+        impl AggregatorImpl for TestAggregator {
+            fn aggregate(&self, aggregator: &Aggregator, _timeout: bool) -> gst::FlowReturn {
+                let mut eos = true;
+
+                for pad in aggregator.get_sink_pads() {
+                    let pad = pad.downcast::<AggregatorPad>().unwrap();
+
+                    if let Some(buffer) = pad.pop_buffer() {
+                        self.n_buffers.fetch_add(1, atomic::Ordering::SeqCst);
+                        let ret = aggregator.finish_buffer(buffer);
+                        if ret != gst::FlowReturn::Ok {
+                            return ret;
+                        }
+                        eos = false;
+                    } else if !pad.is_eos() {
+                        eos = false;
+                    }
+                }
+
+                if eos {
+                    gst::FlowReturn::Eos
+                } else {
+                    gst::FlowReturn::Ok
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_aggregator_subclass() {
+        gst::init().unwrap();
+
+        let type_ = imp::TestAggregator::get_type();
+        assert!(gst::Element::register(None, "testaggregator", 0, type_));
+
+        let pipeline = gst::Pipeline::new(None);
+        let src1 = gst::ElementFactory::make("fakesrc", None).unwrap();
+        let src2 = gst::ElementFactory::make("fakesrc", None).unwrap();
+        let aggregator = gst::ElementFactory::make("testaggregator", None).unwrap();
+        let sink = gst::ElementFactory::make("fakesink", None).unwrap();
+
+        src1.set_property("num-buffers", &10i32).unwrap();
+        src2.set_property("num-buffers", &20i32).unwrap();
+
+        pipeline
+            .add_many(&[&src1, &src2, &aggregator, &sink])
+            .unwrap();
+        src1.link(&aggregator).unwrap();
+        src2.link(&aggregator).unwrap();
+        aggregator.link(&sink).unwrap();
+
+        let sink_pads = aggregator.get_sink_pads();
+        assert_eq!(sink_pads.len(), 2);
+        for pad in &sink_pads {
+            assert!(pad.is::<AggregatorPad>());
+            assert_eq!(pad.get_type(), imp::TestAggregatorPad::get_type());
+        }
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Playing),
+            gst::StateChangeReturn::Failure
+        );
+        let bus = pipeline.get_bus().unwrap();
+
+        while let Some(msg) = bus.timed_pop(gst::CLOCK_TIME_NONE) {
+            match msg.view() {
+                gst::MessageView::Eos(..) => break,
+                gst::MessageView::Error(err) => panic!("{:?}", err.get_error()),
+                _ => (),
+            }
+        }
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Null),
+            gst::StateChangeReturn::Failure
+        );
+
+        let aggregator = aggregator.downcast_ref::<Aggregator>().unwrap();
+        let imp = imp::TestAggregator::from_instance(aggregator);
+        assert_eq!(imp.n_buffers.load(atomic::Ordering::SeqCst), 30);
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::any::Any;
+use std::ops;
+use std::panic::{self, AssertUnwindSafe};
+
+use ffi;
+use glib_ffi;
+use gst_ffi;
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use gst;
+use gst::prelude::*;
+use gst::subclass::prelude::*;
+use gst::subclass::PadClass;
+
+use Aggregator;
+use AggregatorPad;
+
+pub trait AggregatorPadImpl: PadImpl + Send + Sync + 'static {
+    fn flush(&self, aggregator_pad: &AggregatorPad, aggregator: &Aggregator) -> gst::FlowReturn {
+        self.parent_flush(aggregator_pad, aggregator)
+    }
+
+    fn skip_buffer(
+        &self,
+        aggregator_pad: &AggregatorPad,
+        aggregator: &Aggregator,
+        buffer: &gst::BufferRef,
+    ) -> bool {
+        self.parent_skip_buffer(aggregator_pad, aggregator, buffer)
+    }
+}
+
+pub trait AggregatorPadImplExt {
+    fn parent_flush(
+        &self,
+        aggregator_pad: &AggregatorPad,
+        aggregator: &Aggregator,
+    ) -> gst::FlowReturn;
+
+    fn parent_skip_buffer(
+        &self,
+        aggregator_pad: &AggregatorPad,
+        aggregator: &Aggregator,
+        buffer: &gst::BufferRef,
+    ) -> bool;
+}
+
+impl<T: AggregatorPadImpl + ObjectSubclass> AggregatorPadImplExt for T {
+    fn parent_flush(
+        &self,
+        aggregator_pad: &AggregatorPad,
+        aggregator: &Aggregator,
+    ) -> gst::FlowReturn {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorPadClass;
+            (*parent_class)
+                .flush
+                .map(|f| {
+                    from_glib(f(
+                        aggregator_pad.to_glib_none().0,
+                        aggregator.to_glib_none().0,
+                    ))
+                })
+                .unwrap_or(gst::FlowReturn::Ok)
+        }
+    }
+
+    fn parent_skip_buffer(
+        &self,
+        aggregator_pad: &AggregatorPad,
+        aggregator: &Aggregator,
+        buffer: &gst::BufferRef,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstAggregatorPadClass;
+            (*parent_class)
+                .skip_buffer
+                .map(|f| {
+                    from_glib(f(
+                        aggregator_pad.to_glib_none().0,
+                        aggregator.to_glib_none().0,
+                        buffer.as_mut_ptr(),
+                    ))
+                })
+                .unwrap_or(false)
+        }
+    }
+}
+
+/// Rust class struct for `GstAggregatorPad` and all its subclasses.
+#[repr(C)]
+pub struct AggregatorPadClass(ffi::GstAggregatorPadClass);
+
+unsafe impl IsClassFor for AggregatorPadClass {
+    type Instance = AggregatorPad;
+}
+
+unsafe impl Send for AggregatorPadClass {}
+unsafe impl Sync for AggregatorPadClass {}
+
+impl ops::Deref for AggregatorPadClass {
+    type Target = PadClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for AggregatorPadClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl<T: ObjectSubclass + AggregatorPadImpl> IsSubclassable<T> for AggregatorPadClass {
+    fn override_vfuncs(&mut self) {
+        <PadClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstAggregatorPadClass);
+            klass.flush = Some(aggregator_pad_flush::<T>);
+            klass.skip_buffer = Some(aggregator_pad_skip_buffer::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn aggregator_pad_flush<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregatorPad,
+    aggregator: *mut ffi::GstAggregator,
+) -> gst_ffi::GstFlowReturn
+where
+    T: AggregatorPadImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: AggregatorPad = from_glib_borrow(ptr);
+    let aggregator: Aggregator = from_glib_borrow(aggregator);
+
+    match panic::catch_unwind(AssertUnwindSafe(|| imp.flush(&wrap, &aggregator))) {
+        Ok(ret) => ret,
+        Err(err) => {
+            post_panic_error(&wrap, &aggregator, &*err);
+            gst::FlowReturn::Error
+        }
+    }
+    .to_glib()
+}
+
+unsafe extern "C" fn aggregator_pad_skip_buffer<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAggregatorPad,
+    aggregator: *mut ffi::GstAggregator,
+    buffer: *mut gst_ffi::GstBuffer,
+) -> glib_ffi::gboolean
+where
+    T: AggregatorPadImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: AggregatorPad = from_glib_borrow(ptr);
+    let aggregator: Aggregator = from_glib_borrow(aggregator);
+    let buffer = gst::BufferRef::from_ptr(buffer);
+
+    match panic::catch_unwind(AssertUnwindSafe(|| {
+        imp.skip_buffer(&wrap, &aggregator, buffer)
+    })) {
+        Ok(ret) => ret,
+        Err(err) => {
+            post_panic_error(&wrap, &aggregator, &*err);
+            false
+        }
+    }
+    .to_glib()
+}
+
+// Pads have no panicked flag of their own, so report panics on the
+// aggregator the pad belongs to
+fn post_panic_error(
+    aggregator_pad: &AggregatorPad,
+    aggregator: &Aggregator,
+    err: &(Any + Send + 'static),
+) {
+    let cause = err
+        .downcast_ref::<&str>()
+        .map(|cause| *cause)
+        .or_else(|| err.downcast_ref::<String>().map(|cause| cause.as_str()));
+
+    match cause {
+        Some(cause) => aggregator.post_error_message(&gst_error_msg!(
+            gst::LibraryError::Failed,
+            ["Pad {} panicked: {}", aggregator_pad.get_name(), cause]
+        )),
+        None => aggregator.post_error_message(&gst_error_msg!(
+            gst::LibraryError::Failed,
+            ["Pad {} panicked", aggregator_pad.get_name()]
+        )),
+    }
+}
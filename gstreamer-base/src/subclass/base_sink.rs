@@ -0,0 +1,690 @@
+// Copyright (C) 2017-2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::ops;
+use std::ptr;
+
+use ffi;
+use glib_ffi;
+use gst_ffi;
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use gst;
+use gst::prelude::*;
+use gst::subclass::prelude::*;
+use gst::subclass::ElementInstanceStruct;
+
+use BaseSink;
+
+pub trait BaseSinkImpl: ElementImpl + Send + Sync + 'static {
+    fn start(&self, element: &BaseSink) -> bool {
+        self.parent_start(element)
+    }
+
+    fn stop(&self, element: &BaseSink) -> bool {
+        self.parent_stop(element)
+    }
+
+    fn render(&self, element: &BaseSink, buffer: &gst::BufferRef) -> gst::FlowReturn;
+
+    fn prepare(&self, element: &BaseSink, buffer: &gst::BufferRef) -> gst::FlowReturn {
+        self.parent_prepare(element, buffer)
+    }
+
+    fn render_list(&self, element: &BaseSink, list: &gst::BufferListRef) -> gst::FlowReturn {
+        for buffer in list.iter() {
+            let ret = self.render(element, buffer);
+            if ret != gst::FlowReturn::Ok {
+                return ret;
+            }
+        }
+
+        gst::FlowReturn::Ok
+    }
+
+    fn prepare_list(&self, element: &BaseSink, list: &gst::BufferListRef) -> gst::FlowReturn {
+        for buffer in list.iter() {
+            let ret = self.prepare(element, buffer);
+            if ret != gst::FlowReturn::Ok {
+                return ret;
+            }
+        }
+
+        gst::FlowReturn::Ok
+    }
+
+    fn query(&self, element: &BaseSink, query: &mut gst::QueryRef) -> bool {
+        BaseSinkImplExt::parent_query(self, element, query)
+    }
+
+    fn event(&self, element: &BaseSink, event: gst::Event) -> bool {
+        self.parent_event(element, event)
+    }
+
+    fn get_caps(&self, element: &BaseSink, filter: Option<&gst::CapsRef>) -> Option<gst::Caps> {
+        self.parent_get_caps(element, filter)
+    }
+
+    fn set_caps(&self, element: &BaseSink, caps: &gst::CapsRef) -> bool {
+        self.parent_set_caps(element, caps)
+    }
+
+    fn fixate(&self, element: &BaseSink, caps: gst::Caps) -> gst::Caps {
+        self.parent_fixate(element, caps)
+    }
+
+    fn propose_allocation(&self, element: &BaseSink, query: &mut gst::QueryRef) -> bool {
+        self.parent_propose_allocation(element, query)
+    }
+
+    fn unlock(&self, element: &BaseSink) -> bool {
+        self.parent_unlock(element)
+    }
+
+    fn unlock_stop(&self, element: &BaseSink) -> bool {
+        self.parent_unlock_stop(element)
+    }
+}
+
+pub trait BaseSinkImplExt {
+    fn parent_start(&self, element: &BaseSink) -> bool;
+
+    fn parent_stop(&self, element: &BaseSink) -> bool;
+
+    fn parent_prepare(&self, element: &BaseSink, buffer: &gst::BufferRef) -> gst::FlowReturn;
+
+    fn parent_query(&self, element: &BaseSink, query: &mut gst::QueryRef) -> bool;
+
+    fn parent_event(&self, element: &BaseSink, event: gst::Event) -> bool;
+
+    fn parent_get_caps(
+        &self,
+        element: &BaseSink,
+        filter: Option<&gst::CapsRef>,
+    ) -> Option<gst::Caps>;
+
+    fn parent_set_caps(&self, element: &BaseSink, caps: &gst::CapsRef) -> bool;
+
+    fn parent_fixate(&self, element: &BaseSink, caps: gst::Caps) -> gst::Caps;
+
+    fn parent_propose_allocation(&self, element: &BaseSink, query: &mut gst::QueryRef) -> bool;
+
+    fn parent_unlock(&self, element: &BaseSink) -> bool;
+
+    fn parent_unlock_stop(&self, element: &BaseSink) -> bool;
+}
+
+impl<T: BaseSinkImpl + ObjectSubclass> BaseSinkImplExt for T
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn parent_start(&self, element: &BaseSink) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            (*parent_class)
+                .start
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_stop(&self, element: &BaseSink) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            (*parent_class)
+                .stop
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_prepare(&self, element: &BaseSink, buffer: &gst::BufferRef) -> gst::FlowReturn {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            (*parent_class)
+                .prepare
+                .map(|f| from_glib(f(element.to_glib_none().0, buffer.as_mut_ptr())))
+                .unwrap_or(gst::FlowReturn::Ok)
+        }
+    }
+
+    fn parent_query(&self, element: &BaseSink, query: &mut gst::QueryRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            (*parent_class)
+                .query
+                .map(|f| from_glib(f(element.to_glib_none().0, query.as_mut_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_event(&self, element: &BaseSink, event: gst::Event) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            (*parent_class)
+                .event
+                .map(|f| from_glib(f(element.to_glib_none().0, event.into_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_get_caps(
+        &self,
+        element: &BaseSink,
+        filter: Option<&gst::CapsRef>,
+    ) -> Option<gst::Caps> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            let filter_ptr = if let Some(filter) = filter {
+                filter.as_mut_ptr()
+            } else {
+                ptr::null_mut()
+            };
+
+            (*parent_class)
+                .get_caps
+                .map(|f| from_glib_full(f(element.to_glib_none().0, filter_ptr)))
+                .unwrap_or(None)
+        }
+    }
+
+    fn parent_set_caps(&self, element: &BaseSink, caps: &gst::CapsRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            (*parent_class)
+                .set_caps
+                .map(|f| from_glib(f(element.to_glib_none().0, caps.as_mut_ptr())))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_fixate(&self, element: &BaseSink, caps: gst::Caps) -> gst::Caps {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+
+            match (*parent_class).fixate {
+                Some(fixate) => from_glib_full(fixate(element.to_glib_none().0, caps.into_ptr())),
+                None => caps,
+            }
+        }
+    }
+
+    fn parent_propose_allocation(&self, element: &BaseSink, query: &mut gst::QueryRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            (*parent_class)
+                .propose_allocation
+                .map(|f| from_glib(f(element.to_glib_none().0, query.as_mut_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_unlock(&self, element: &BaseSink) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            (*parent_class)
+                .unlock
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_unlock_stop(&self, element: &BaseSink) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSinkClass;
+            (*parent_class)
+                .unlock_stop
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+}
+
+/// Rust class struct for `GstBaseSink` and all its subclasses.
+#[repr(C)]
+pub struct BaseSinkClass(ffi::GstBaseSinkClass);
+
+unsafe impl IsClassFor for BaseSinkClass {
+    type Instance = BaseSink;
+}
+
+unsafe impl Send for BaseSinkClass {}
+unsafe impl Sync for BaseSinkClass {}
+
+impl ops::Deref for BaseSinkClass {
+    type Target = gst::subclass::ElementClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for BaseSinkClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl ElementClassSubclassExt for BaseSinkClass {}
+
+unsafe impl<T: ObjectSubclass + BaseSinkImpl> IsSubclassable<T> for BaseSinkClass
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn override_vfuncs(&mut self) {
+        <gst::subclass::ElementClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstBaseSinkClass);
+            klass.start = Some(base_sink_start::<T>);
+            klass.stop = Some(base_sink_stop::<T>);
+            klass.render = Some(base_sink_render::<T>);
+            klass.render_list = Some(base_sink_render_list::<T>);
+            klass.prepare = Some(base_sink_prepare::<T>);
+            klass.prepare_list = Some(base_sink_prepare_list::<T>);
+            klass.query = Some(base_sink_query::<T>);
+            klass.event = Some(base_sink_event::<T>);
+            klass.get_caps = Some(base_sink_get_caps::<T>);
+            klass.set_caps = Some(base_sink_set_caps::<T>);
+            klass.fixate = Some(base_sink_fixate::<T>);
+            klass.propose_allocation = Some(base_sink_propose_allocation::<T>);
+            klass.unlock = Some(base_sink_unlock::<T>);
+            klass.unlock_stop = Some(base_sink_unlock_stop::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn base_sink_start<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+) -> glib_ffi::gboolean
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.start(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_stop<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+) -> glib_ffi::gboolean
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.stop(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_render<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    buffer: *mut gst_ffi::GstBuffer,
+) -> gst_ffi::GstFlowReturn
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+    let buffer = gst::BufferRef::from_ptr(buffer);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        imp.render(&wrap, buffer)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_prepare<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    buffer: *mut gst_ffi::GstBuffer,
+) -> gst_ffi::GstFlowReturn
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+    let buffer = gst::BufferRef::from_ptr(buffer);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        imp.prepare(&wrap, buffer)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_render_list<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    list: *mut gst_ffi::GstBufferList,
+) -> gst_ffi::GstFlowReturn
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+    let list = gst::BufferListRef::from_ptr(list);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        imp.render_list(&wrap, list)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_prepare_list<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    list: *mut gst_ffi::GstBufferList,
+) -> gst_ffi::GstFlowReturn
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+    let list = gst::BufferListRef::from_ptr(list);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        imp.prepare_list(&wrap, list)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_query<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    query_ptr: *mut gst_ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+    let query = gst::QueryRef::from_mut_ptr(query_ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        BaseSinkImpl::query(imp, &wrap, query)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_event<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    event_ptr: *mut gst_ffi::GstEvent,
+) -> glib_ffi::gboolean
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.event(&wrap, from_glib_full(event_ptr))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_get_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    filter: *mut gst_ffi::GstCaps,
+) -> *mut gst_ffi::GstCaps
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+    let filter = if filter.is_null() {
+        None
+    } else {
+        Some(gst::CapsRef::from_ptr(filter))
+    };
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), None, {
+        imp.get_caps(&wrap, filter)
+    })
+    .map(|caps| caps.into_ptr())
+    .unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "C" fn base_sink_set_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    caps: *mut gst_ffi::GstCaps,
+) -> glib_ffi::gboolean
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+    let caps = gst::CapsRef::from_ptr(caps);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.set_caps(&wrap, caps)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_fixate<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    caps: *mut gst_ffi::GstCaps,
+) -> *mut gst_ffi::GstCaps
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+    let caps = from_glib_full(caps);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::Caps::new_empty(), {
+        imp.fixate(&wrap, caps)
+    })
+    .into_ptr()
+}
+
+unsafe extern "C" fn base_sink_propose_allocation<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+    query: *mut gst_ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+    let query = gst::QueryRef::from_mut_ptr(query);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.propose_allocation(&wrap, query)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_unlock<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+) -> glib_ffi::gboolean
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.unlock(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_sink_unlock_stop<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSink,
+) -> glib_ffi::gboolean
+where
+    T: BaseSinkImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSink = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.unlock_stop(&wrap)
+    })
+    .to_glib()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib;
+    use glib::subclass;
+    use std::sync::atomic;
+
+    pub mod imp {
+        use super::*;
+
+        pub struct TestSink {
+            pub(super) n_buffers: atomic::AtomicU32,
+        }
+
+        impl ObjectSubclass for TestSink {
+            const NAME: &'static str = "TestSink";
+            type ParentType = BaseSink;
+            type Instance = ElementInstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                Self {
+                    n_buffers: atomic::AtomicU32::new(0),
+                }
+            }
+
+            fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+                klass.set_metadata(
+                    "Test Sink",
+                    "Sink/Generic",
+                    "Counts buffers",
+                    "Sebastian Dröge <sebastian@centricular.com>",
+                );
+
+                let caps = gst::Caps::new_any();
+                let sink_pad_template = gst::PadTemplate::new(
+                    "sink",
+                    gst::PadDirection::Sink,
+                    gst::PadPresence::Always,
+                    &caps,
+                );
+                klass.add_pad_template(sink_pad_template);
+            }
+        }
+
+        impl ObjectImpl for TestSink {
+            glib_object_impl!();
+        }
+
+        impl ElementImpl for TestSink {}
+
+        impl BaseSinkImpl for TestSink {
+            fn render(&self, _element: &BaseSink, _buffer: &gst::BufferRef) -> gst::FlowReturn {
+                self.n_buffers.fetch_add(1, atomic::Ordering::SeqCst);
+                gst::FlowReturn::Ok
+            }
+        }
+    }
+
+    #[test]
+    fn test_base_sink_subclass() {
+        gst::init().unwrap();
+
+        let type_ = imp::TestSink::get_type();
+        assert!(gst::Element::register(None, "testsink", 0, type_));
+
+        let pipeline = gst::Pipeline::new(None);
+        let src = gst::ElementFactory::make("fakesrc", None).unwrap();
+        let sink = gst::ElementFactory::make("testsink", None).unwrap();
+
+        src.set_property("num-buffers", &100i32).unwrap();
+
+        pipeline.add_many(&[&src, &sink]).unwrap();
+        src.link(&sink).unwrap();
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Playing),
+            gst::StateChangeReturn::Failure
+        );
+        let bus = pipeline.get_bus().unwrap();
+
+        while let Some(msg) = bus.timed_pop(gst::CLOCK_TIME_NONE) {
+            match msg.view() {
+                gst::MessageView::Eos(..) => break,
+                gst::MessageView::Error(err) => panic!("{:?}", err.get_error()),
+                _ => (),
+            }
+        }
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Null),
+            gst::StateChangeReturn::Failure
+        );
+
+        let sink = sink.downcast_ref::<BaseSink>().unwrap();
+        let imp = imp::TestSink::from_instance(sink);
+        assert_eq!(imp.n_buffers.load(atomic::Ordering::SeqCst), 100);
+    }
+}
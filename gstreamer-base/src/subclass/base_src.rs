@@ -0,0 +1,861 @@
+// Copyright (C) 2017-2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::ops;
+use std::ptr;
+
+use ffi;
+use glib_ffi;
+use gst_ffi;
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use gst;
+use gst::prelude::*;
+use gst::subclass::prelude::*;
+use gst::subclass::ElementInstanceStruct;
+
+use BaseSrc;
+
+pub trait BaseSrcImpl: ElementImpl + Send + Sync + 'static {
+    fn start(&self, element: &BaseSrc) -> bool {
+        self.parent_start(element)
+    }
+
+    fn stop(&self, element: &BaseSrc) -> bool {
+        self.parent_stop(element)
+    }
+
+    fn is_seekable(&self, element: &BaseSrc) -> bool {
+        self.parent_is_seekable(element)
+    }
+
+    fn get_size(&self, element: &BaseSrc) -> Option<u64> {
+        self.parent_get_size(element)
+    }
+
+    fn fill(
+        &self,
+        element: &BaseSrc,
+        offset: u64,
+        length: u32,
+        buffer: &mut gst::BufferRef,
+    ) -> gst::FlowReturn {
+        self.parent_fill(element, offset, length, buffer)
+    }
+
+    fn create(
+        &self,
+        element: &BaseSrc,
+        offset: u64,
+        length: u32,
+    ) -> Result<gst::Buffer, gst::FlowError> {
+        self.parent_create(element, offset, length)
+    }
+
+    fn do_seek(&self, element: &BaseSrc, segment: &mut gst::Segment) -> bool {
+        self.parent_do_seek(element, segment)
+    }
+
+    fn query(&self, element: &BaseSrc, query: &mut gst::QueryRef) -> bool {
+        BaseSrcImplExt::parent_query(self, element, query)
+    }
+
+    fn event(&self, element: &BaseSrc, event: &gst::Event) -> bool {
+        self.parent_event(element, event)
+    }
+
+    fn get_caps(&self, element: &BaseSrc, filter: Option<&gst::CapsRef>) -> Option<gst::Caps> {
+        self.parent_get_caps(element, filter)
+    }
+
+    fn negotiate(&self, element: &BaseSrc) -> bool {
+        self.parent_negotiate(element)
+    }
+
+    fn set_caps(&self, element: &BaseSrc, caps: &gst::CapsRef) -> bool {
+        self.parent_set_caps(element, caps)
+    }
+
+    fn fixate(&self, element: &BaseSrc, caps: gst::Caps) -> gst::Caps {
+        self.parent_fixate(element, caps)
+    }
+
+    fn decide_allocation(&self, element: &BaseSrc, query: &mut gst::QueryRef) -> bool {
+        self.parent_decide_allocation(element, query)
+    }
+
+    fn unlock(&self, element: &BaseSrc) -> bool {
+        self.parent_unlock(element)
+    }
+
+    fn unlock_stop(&self, element: &BaseSrc) -> bool {
+        self.parent_unlock_stop(element)
+    }
+}
+
+pub trait BaseSrcImplExt {
+    fn parent_start(&self, element: &BaseSrc) -> bool;
+
+    fn parent_stop(&self, element: &BaseSrc) -> bool;
+
+    fn parent_is_seekable(&self, element: &BaseSrc) -> bool;
+
+    fn parent_get_size(&self, element: &BaseSrc) -> Option<u64>;
+
+    fn parent_fill(
+        &self,
+        element: &BaseSrc,
+        offset: u64,
+        length: u32,
+        buffer: &mut gst::BufferRef,
+    ) -> gst::FlowReturn;
+
+    fn parent_create(
+        &self,
+        element: &BaseSrc,
+        offset: u64,
+        length: u32,
+    ) -> Result<gst::Buffer, gst::FlowError>;
+
+    fn parent_do_seek(&self, element: &BaseSrc, segment: &mut gst::Segment) -> bool;
+
+    fn parent_query(&self, element: &BaseSrc, query: &mut gst::QueryRef) -> bool;
+
+    fn parent_event(&self, element: &BaseSrc, event: &gst::Event) -> bool;
+
+    fn parent_get_caps(
+        &self,
+        element: &BaseSrc,
+        filter: Option<&gst::CapsRef>,
+    ) -> Option<gst::Caps>;
+
+    fn parent_negotiate(&self, element: &BaseSrc) -> bool;
+
+    fn parent_set_caps(&self, element: &BaseSrc, caps: &gst::CapsRef) -> bool;
+
+    fn parent_fixate(&self, element: &BaseSrc, caps: gst::Caps) -> gst::Caps;
+
+    fn parent_decide_allocation(&self, element: &BaseSrc, query: &mut gst::QueryRef) -> bool;
+
+    fn parent_unlock(&self, element: &BaseSrc) -> bool;
+
+    fn parent_unlock_stop(&self, element: &BaseSrc) -> bool;
+}
+
+impl<T: BaseSrcImpl + ObjectSubclass> BaseSrcImplExt for T
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn parent_start(&self, element: &BaseSrc) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .start
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_stop(&self, element: &BaseSrc) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .stop
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_is_seekable(&self, element: &BaseSrc) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .is_seekable
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_get_size(&self, element: &BaseSrc) -> Option<u64> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .get_size
+                .map(|f| {
+                    let mut size = 0;
+                    if from_glib(f(element.to_glib_none().0, &mut size)) {
+                        Some(size)
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(None)
+        }
+    }
+
+    fn parent_fill(
+        &self,
+        element: &BaseSrc,
+        offset: u64,
+        length: u32,
+        buffer: &mut gst::BufferRef,
+    ) -> gst::FlowReturn {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .fill
+                .map(|f| {
+                    from_glib(f(
+                        element.to_glib_none().0,
+                        offset,
+                        length,
+                        buffer.as_mut_ptr(),
+                    ))
+                })
+                .unwrap_or(gst::FlowReturn::NotSupported)
+        }
+    }
+
+    fn parent_create(
+        &self,
+        element: &BaseSrc,
+        offset: u64,
+        length: u32,
+    ) -> Result<gst::Buffer, gst::FlowError> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .create
+                .map(|f| {
+                    let mut buffer: *mut gst_ffi::GstBuffer = ptr::null_mut();
+                    let ret: gst::FlowReturn =
+                        from_glib(f(element.to_glib_none().0, offset, length, &mut buffer));
+
+                    ret.into_result().map(|_| from_glib_full(buffer))
+                })
+                .unwrap_or(Err(gst::FlowError::NotSupported))
+        }
+    }
+
+    fn parent_do_seek(&self, element: &BaseSrc, segment: &mut gst::Segment) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .do_seek
+                .map(|f| from_glib(f(element.to_glib_none().0, segment.to_glib_none_mut().0)))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_query(&self, element: &BaseSrc, query: &mut gst::QueryRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .query
+                .map(|f| from_glib(f(element.to_glib_none().0, query.as_mut_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_event(&self, element: &BaseSrc, event: &gst::Event) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .event
+                .map(|f| from_glib(f(element.to_glib_none().0, event.to_glib_none().0)))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_get_caps(
+        &self,
+        element: &BaseSrc,
+        filter: Option<&gst::CapsRef>,
+    ) -> Option<gst::Caps> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            let filter_ptr = if let Some(filter) = filter {
+                filter.as_mut_ptr()
+            } else {
+                ptr::null_mut()
+            };
+
+            (*parent_class)
+                .get_caps
+                .map(|f| from_glib_full(f(element.to_glib_none().0, filter_ptr)))
+                .unwrap_or(None)
+        }
+    }
+
+    fn parent_negotiate(&self, element: &BaseSrc) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .negotiate
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_set_caps(&self, element: &BaseSrc, caps: &gst::CapsRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .set_caps
+                .map(|f| from_glib(f(element.to_glib_none().0, caps.as_mut_ptr())))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_fixate(&self, element: &BaseSrc, caps: gst::Caps) -> gst::Caps {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+
+            match (*parent_class).fixate {
+                Some(fixate) => from_glib_full(fixate(element.to_glib_none().0, caps.into_ptr())),
+                None => caps,
+            }
+        }
+    }
+
+    fn parent_decide_allocation(&self, element: &BaseSrc, query: &mut gst::QueryRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .decide_allocation
+                .map(|f| from_glib(f(element.to_glib_none().0, query.as_mut_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_unlock(&self, element: &BaseSrc) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .unlock
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_unlock_stop(&self, element: &BaseSrc) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBaseSrcClass;
+            (*parent_class)
+                .unlock_stop
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+}
+
+/// Rust class struct for `GstBaseSrc` and all its subclasses.
+#[repr(C)]
+pub struct BaseSrcClass(ffi::GstBaseSrcClass);
+
+unsafe impl IsClassFor for BaseSrcClass {
+    type Instance = BaseSrc;
+}
+
+unsafe impl Send for BaseSrcClass {}
+unsafe impl Sync for BaseSrcClass {}
+
+impl ops::Deref for BaseSrcClass {
+    type Target = gst::subclass::ElementClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for BaseSrcClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl ElementClassSubclassExt for BaseSrcClass {}
+
+unsafe impl<T: ObjectSubclass + BaseSrcImpl> IsSubclassable<T> for BaseSrcClass
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn override_vfuncs(&mut self) {
+        <gst::subclass::ElementClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstBaseSrcClass);
+            klass.start = Some(base_src_start::<T>);
+            klass.stop = Some(base_src_stop::<T>);
+            klass.is_seekable = Some(base_src_is_seekable::<T>);
+            klass.get_size = Some(base_src_get_size::<T>);
+            klass.fill = Some(base_src_fill::<T>);
+            klass.create = Some(base_src_create::<T>);
+            klass.do_seek = Some(base_src_do_seek::<T>);
+            klass.query = Some(base_src_query::<T>);
+            klass.event = Some(base_src_event::<T>);
+            klass.get_caps = Some(base_src_get_caps::<T>);
+            klass.negotiate = Some(base_src_negotiate::<T>);
+            klass.set_caps = Some(base_src_set_caps::<T>);
+            klass.fixate = Some(base_src_fixate::<T>);
+            klass.decide_allocation = Some(base_src_decide_allocation::<T>);
+            klass.unlock = Some(base_src_unlock::<T>);
+            klass.unlock_stop = Some(base_src_unlock_stop::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn base_src_start<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.start(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_stop<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.stop(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_is_seekable<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.is_seekable(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_get_size<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    size: *mut u64,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        match imp.get_size(&wrap) {
+            Some(s) => {
+                *size = s;
+                true
+            }
+            None => false,
+        }
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_fill<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    offset: u64,
+    length: u32,
+    buffer: *mut gst_ffi::GstBuffer,
+) -> gst_ffi::GstFlowReturn
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+    let buffer = gst::BufferRef::from_mut_ptr(buffer);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        imp.fill(&wrap, offset, length, buffer)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_create<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    offset: u64,
+    length: u32,
+    buffer_ptr: *mut *mut gst_ffi::GstBuffer,
+) -> gst_ffi::GstFlowReturn
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        match imp.create(&wrap, offset, length) {
+            Ok(buffer) => {
+                *buffer_ptr = buffer.into_ptr();
+                gst::FlowReturn::Ok
+            }
+            Err(err) => gst::FlowReturn::from_error(err),
+        }
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_do_seek<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    segment: *mut gst_ffi::GstSegment,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.do_seek(&wrap, &mut *(segment as *mut gst::Segment))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_query<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    query_ptr: *mut gst_ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+    let query = gst::QueryRef::from_mut_ptr(query_ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        BaseSrcImpl::query(imp, &wrap, query)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_event<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    event_ptr: *mut gst_ffi::GstEvent,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.event(&wrap, &from_glib_borrow(event_ptr))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_get_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    filter: *mut gst_ffi::GstCaps,
+) -> *mut gst_ffi::GstCaps
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+    let filter = if filter.is_null() {
+        None
+    } else {
+        Some(gst::CapsRef::from_ptr(filter))
+    };
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), None, {
+        imp.get_caps(&wrap, filter)
+    })
+    .map(|caps| caps.into_ptr())
+    .unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "C" fn base_src_negotiate<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.negotiate(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_set_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    caps: *mut gst_ffi::GstCaps,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+    let caps = gst::CapsRef::from_ptr(caps);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.set_caps(&wrap, caps)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_fixate<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    caps: *mut gst_ffi::GstCaps,
+) -> *mut gst_ffi::GstCaps
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+    let caps = from_glib_full(caps);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::Caps::new_empty(), {
+        imp.fixate(&wrap, caps)
+    })
+    .into_ptr()
+}
+
+unsafe extern "C" fn base_src_decide_allocation<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+    query: *mut gst_ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+    let query = gst::QueryRef::from_mut_ptr(query);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.decide_allocation(&wrap, query)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_unlock<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.unlock(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_src_unlock_stop<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseSrc,
+) -> glib_ffi::gboolean
+where
+    T: BaseSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.unlock_stop(&wrap)
+    })
+    .to_glib()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib;
+    use glib::subclass;
+    use std::sync::atomic;
+
+    pub mod imp {
+        use super::*;
+
+        pub struct TestSrc {
+            pub(super) n_buffers: atomic::AtomicU32,
+        }
+
+        impl ObjectSubclass for TestSrc {
+            const NAME: &'static str = "TestSrc";
+            type ParentType = BaseSrc;
+            type Instance = ElementInstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                Self {
+                    n_buffers: atomic::AtomicU32::new(0),
+                }
+            }
+
+            fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+                klass.set_metadata(
+                    "Test Source",
+                    "Source/Generic",
+                    "Produces empty buffers",
+                    "Sebastian Dröge <sebastian@centricular.com>",
+                );
+
+                let caps = gst::Caps::new_any();
+                let src_pad_template = gst::PadTemplate::new(
+                    "src",
+                    gst::PadDirection::Src,
+                    gst::PadPresence::Always,
+                    &caps,
+                );
+                klass.add_pad_template(src_pad_template);
+            }
+        }
+
+        impl ObjectImpl for TestSrc {
+            glib_object_impl!();
+        }
+
+        impl ElementImpl for TestSrc {}
+
+        impl BaseSrcImpl for TestSrc {
+            fn fill(
+                &self,
+                _element: &BaseSrc,
+                _offset: u64,
+                _length: u32,
+                _buffer: &mut gst::BufferRef,
+            ) -> gst::FlowReturn {
+                self.n_buffers.fetch_add(1, atomic::Ordering::SeqCst);
+                gst::FlowReturn::Ok
+            }
+        }
+    }
+
+    #[test]
+    fn test_base_src_subclass() {
+        gst::init().unwrap();
+
+        let type_ = imp::TestSrc::get_type();
+        assert!(gst::Element::register(None, "testsrc", 0, type_));
+
+        let pipeline = gst::Pipeline::new(None);
+        let src = gst::ElementFactory::make("testsrc", None).unwrap();
+        let sink = gst::ElementFactory::make("fakesink", None).unwrap();
+
+        src.set_property("num-buffers", &100i32).unwrap();
+
+        pipeline.add_many(&[&src, &sink]).unwrap();
+        src.link(&sink).unwrap();
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Playing),
+            gst::StateChangeReturn::Failure
+        );
+        let bus = pipeline.get_bus().unwrap();
+
+        while let Some(msg) = bus.timed_pop(gst::CLOCK_TIME_NONE) {
+            match msg.view() {
+                gst::MessageView::Eos(..) => break,
+                gst::MessageView::Error(err) => panic!("{:?}", err.get_error()),
+                _ => (),
+            }
+        }
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Null),
+            gst::StateChangeReturn::Failure
+        );
+
+        let src = src.downcast_ref::<BaseSrc>().unwrap();
+        let imp = imp::TestSrc::from_instance(src);
+        assert_eq!(imp.n_buffers.load(atomic::Ordering::SeqCst), 100);
+    }
+}
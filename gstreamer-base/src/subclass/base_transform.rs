@@ -0,0 +1,1124 @@
+// Copyright (C) 2017-2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::ops;
+use std::ptr;
+
+use ffi;
+use glib_ffi;
+use gst_ffi;
+
+use glib;
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use gst;
+use gst::prelude::*;
+use gst::subclass::prelude::*;
+use gst::subclass::ElementInstanceStruct;
+
+use BaseTransform;
+
+pub trait BaseTransformImpl: ElementImpl + Send + Sync + 'static {
+    fn start(&self, element: &BaseTransform) -> bool {
+        self.parent_start(element)
+    }
+
+    fn stop(&self, element: &BaseTransform) -> bool {
+        self.parent_stop(element)
+    }
+
+    fn transform_caps(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+        filter: Option<&gst::CapsRef>,
+    ) -> gst::Caps {
+        self.parent_transform_caps(element, direction, caps, filter)
+    }
+
+    fn fixate_caps(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+        othercaps: gst::Caps,
+    ) -> gst::Caps {
+        self.parent_fixate_caps(element, direction, caps, othercaps)
+    }
+
+    fn set_caps(
+        &self,
+        element: &BaseTransform,
+        incaps: &gst::CapsRef,
+        outcaps: &gst::CapsRef,
+    ) -> bool {
+        self.parent_set_caps(element, incaps, outcaps)
+    }
+
+    fn accept_caps(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+    ) -> bool {
+        self.parent_accept_caps(element, direction, caps)
+    }
+
+    fn query(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        query: &mut gst::QueryRef,
+    ) -> bool {
+        BaseTransformImplExt::parent_query(self, element, direction, query)
+    }
+
+    fn transform_size(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+        size: usize,
+        othercaps: &gst::CapsRef,
+    ) -> Option<usize> {
+        self.parent_transform_size(element, direction, caps, size, othercaps)
+    }
+
+    fn get_unit_size(&self, element: &BaseTransform, caps: &gst::CapsRef) -> Option<usize> {
+        self.parent_get_unit_size(element, caps)
+    }
+
+    fn sink_event(&self, element: &BaseTransform, event: gst::Event) -> bool {
+        self.parent_sink_event(element, event)
+    }
+
+    fn src_event(&self, element: &BaseTransform, event: gst::Event) -> bool {
+        self.parent_src_event(element, event)
+    }
+
+    fn transform(
+        &self,
+        element: &BaseTransform,
+        _inbuf: &gst::Buffer,
+        _outbuf: &mut gst::BufferRef,
+    ) -> gst::FlowReturn {
+        gst_element_error!(
+            element,
+            gst::CoreError::NotImplemented,
+            ["Transform not implemented"]
+        );
+        gst::FlowReturn::NotSupported
+    }
+
+    fn transform_ip(&self, element: &BaseTransform, _buf: &mut gst::BufferRef) -> gst::FlowReturn {
+        gst_element_error!(
+            element,
+            gst::CoreError::NotImplemented,
+            ["In-place transform not implemented"]
+        );
+        gst::FlowReturn::NotSupported
+    }
+
+    fn decide_allocation(&self, element: &BaseTransform, query: &mut gst::QueryRef) -> bool {
+        self.parent_decide_allocation(element, query)
+    }
+
+    fn propose_allocation(
+        &self,
+        element: &BaseTransform,
+        decide_query: Option<&gst::QueryRef>,
+        query: &mut gst::QueryRef,
+    ) -> bool {
+        self.parent_propose_allocation(element, decide_query, query)
+    }
+}
+
+pub trait BaseTransformImplExt {
+    fn parent_start(&self, element: &BaseTransform) -> bool;
+
+    fn parent_stop(&self, element: &BaseTransform) -> bool;
+
+    fn parent_transform_caps(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+        filter: Option<&gst::CapsRef>,
+    ) -> gst::Caps;
+
+    fn parent_fixate_caps(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+        othercaps: gst::Caps,
+    ) -> gst::Caps;
+
+    fn parent_set_caps(
+        &self,
+        element: &BaseTransform,
+        incaps: &gst::CapsRef,
+        outcaps: &gst::CapsRef,
+    ) -> bool;
+
+    fn parent_accept_caps(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+    ) -> bool;
+
+    fn parent_query(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        query: &mut gst::QueryRef,
+    ) -> bool;
+
+    fn parent_transform_size(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+        size: usize,
+        othercaps: &gst::CapsRef,
+    ) -> Option<usize>;
+
+    fn parent_get_unit_size(&self, element: &BaseTransform, caps: &gst::CapsRef)
+        -> Option<usize>;
+
+    fn parent_sink_event(&self, element: &BaseTransform, event: gst::Event) -> bool;
+
+    fn parent_src_event(&self, element: &BaseTransform, event: gst::Event) -> bool;
+
+    fn parent_decide_allocation(&self, element: &BaseTransform, query: &mut gst::QueryRef)
+        -> bool;
+
+    fn parent_propose_allocation(
+        &self,
+        element: &BaseTransform,
+        decide_query: Option<&gst::QueryRef>,
+        query: &mut gst::QueryRef,
+    ) -> bool;
+}
+
+impl<T: BaseTransformImpl + ObjectSubclass> BaseTransformImplExt for T
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn parent_start(&self, element: &BaseTransform) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .start
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_stop(&self, element: &BaseTransform) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .stop
+                .map(|f| from_glib(f(element.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_transform_caps(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+        filter: Option<&gst::CapsRef>,
+    ) -> gst::Caps {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            let filter_ptr = if let Some(filter) = filter {
+                filter.as_mut_ptr()
+            } else {
+                ptr::null_mut()
+            };
+
+            match (*parent_class).transform_caps {
+                Some(f) => from_glib_full(f(
+                    element.to_glib_none().0,
+                    direction.to_glib(),
+                    caps.as_mut_ptr(),
+                    filter_ptr,
+                )),
+                None => caps.to_owned(),
+            }
+        }
+    }
+
+    fn parent_fixate_caps(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+        othercaps: gst::Caps,
+    ) -> gst::Caps {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+
+            match (*parent_class).fixate_caps {
+                Some(f) => from_glib_full(f(
+                    element.to_glib_none().0,
+                    direction.to_glib(),
+                    caps.as_mut_ptr(),
+                    othercaps.into_ptr(),
+                )),
+                None => othercaps,
+            }
+        }
+    }
+
+    fn parent_set_caps(
+        &self,
+        element: &BaseTransform,
+        incaps: &gst::CapsRef,
+        outcaps: &gst::CapsRef,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .set_caps
+                .map(|f| {
+                    from_glib(f(
+                        element.to_glib_none().0,
+                        incaps.as_mut_ptr(),
+                        outcaps.as_mut_ptr(),
+                    ))
+                })
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_accept_caps(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .accept_caps
+                .map(|f| {
+                    from_glib(f(
+                        element.to_glib_none().0,
+                        direction.to_glib(),
+                        caps.as_mut_ptr(),
+                    ))
+                })
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_query(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        query: &mut gst::QueryRef,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .query
+                .map(|f| {
+                    from_glib(f(
+                        element.to_glib_none().0,
+                        direction.to_glib(),
+                        query.as_mut_ptr(),
+                    ))
+                })
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_transform_size(
+        &self,
+        element: &BaseTransform,
+        direction: gst::PadDirection,
+        caps: &gst::CapsRef,
+        size: usize,
+        othercaps: &gst::CapsRef,
+    ) -> Option<usize> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .transform_size
+                .map(|f| {
+                    let mut othersize = 0;
+                    let res: bool = from_glib(f(
+                        element.to_glib_none().0,
+                        direction.to_glib(),
+                        caps.as_mut_ptr(),
+                        size,
+                        othercaps.as_mut_ptr(),
+                        &mut othersize,
+                    ));
+                    if res {
+                        Some(othersize)
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(None)
+        }
+    }
+
+    fn parent_get_unit_size(
+        &self,
+        element: &BaseTransform,
+        caps: &gst::CapsRef,
+    ) -> Option<usize> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .get_unit_size
+                .map(|f| {
+                    let mut size = 0;
+                    if from_glib(f(element.to_glib_none().0, caps.as_mut_ptr(), &mut size)) {
+                        Some(size)
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(None)
+        }
+    }
+
+    fn parent_sink_event(&self, element: &BaseTransform, event: gst::Event) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .sink_event
+                .map(|f| from_glib(f(element.to_glib_none().0, event.into_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_src_event(&self, element: &BaseTransform, event: gst::Event) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .src_event
+                .map(|f| from_glib(f(element.to_glib_none().0, event.into_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_decide_allocation(
+        &self,
+        element: &BaseTransform,
+        query: &mut gst::QueryRef,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            (*parent_class)
+                .decide_allocation
+                .map(|f| from_glib(f(element.to_glib_none().0, query.as_mut_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_propose_allocation(
+        &self,
+        element: &BaseTransform,
+        decide_query: Option<&gst::QueryRef>,
+        query: &mut gst::QueryRef,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().get_parent_class() as *mut ffi::GstBaseTransformClass;
+            let decide_query_ptr = if let Some(decide_query) = decide_query {
+                decide_query.as_mut_ptr()
+            } else {
+                ptr::null_mut()
+            };
+
+            (*parent_class)
+                .propose_allocation
+                .map(|f| {
+                    from_glib(f(
+                        element.to_glib_none().0,
+                        decide_query_ptr,
+                        query.as_mut_ptr(),
+                    ))
+                })
+                .unwrap_or(false)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BaseTransformMode {
+    AlwaysInPlace,
+    NeverInPlace,
+    Both,
+}
+
+/// Rust class struct for `GstBaseTransform` and all its subclasses.
+#[repr(C)]
+pub struct BaseTransformClass(ffi::GstBaseTransformClass);
+
+unsafe impl IsClassFor for BaseTransformClass {
+    type Instance = BaseTransform;
+}
+
+unsafe impl Send for BaseTransformClass {}
+unsafe impl Sync for BaseTransformClass {}
+
+impl ops::Deref for BaseTransformClass {
+    type Target = gst::subclass::ElementClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for BaseTransformClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl ElementClassSubclassExt for BaseTransformClass {}
+
+/// Class-init helpers specific to `GstBaseTransform` subclasses.
+pub unsafe trait BaseTransformClassSubclassExt: Sized + 'static {
+    /// Selects which of `transform` and `transform_ip` the subclass implements
+    /// and how passthrough is handled.
+    fn configure(
+        &mut self,
+        mode: BaseTransformMode,
+        passthrough_on_same_caps: bool,
+        transform_ip_on_passthrough: bool,
+    ) {
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstBaseTransformClass);
+
+            klass.passthrough_on_same_caps = passthrough_on_same_caps.to_glib();
+            klass.transform_ip_on_passthrough = transform_ip_on_passthrough.to_glib();
+
+            match mode {
+                BaseTransformMode::AlwaysInPlace => {
+                    klass.transform = None;
+                }
+                BaseTransformMode::NeverInPlace => {
+                    klass.transform_ip = None;
+                }
+                BaseTransformMode::Both => (),
+            }
+        }
+    }
+}
+
+unsafe impl BaseTransformClassSubclassExt for BaseTransformClass {}
+unsafe impl<T: ObjectSubclass + BaseTransformImpl> BaseTransformClassSubclassExt
+    for glib::subclass::simple::ClassStruct<T>
+{
+}
+
+unsafe impl<T: ObjectSubclass + BaseTransformImpl> IsSubclassable<T> for BaseTransformClass
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn override_vfuncs(&mut self) {
+        <gst::subclass::ElementClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstBaseTransformClass);
+            klass.start = Some(base_transform_start::<T>);
+            klass.stop = Some(base_transform_stop::<T>);
+            klass.transform_caps = Some(base_transform_transform_caps::<T>);
+            klass.fixate_caps = Some(base_transform_fixate_caps::<T>);
+            klass.set_caps = Some(base_transform_set_caps::<T>);
+            klass.accept_caps = Some(base_transform_accept_caps::<T>);
+            klass.query = Some(base_transform_query::<T>);
+            klass.transform_size = Some(base_transform_transform_size::<T>);
+            klass.get_unit_size = Some(base_transform_get_unit_size::<T>);
+            klass.sink_event = Some(base_transform_sink_event::<T>);
+            klass.src_event = Some(base_transform_src_event::<T>);
+            klass.transform = Some(base_transform_transform::<T>);
+            klass.transform_ip = Some(base_transform_transform_ip::<T>);
+            klass.decide_allocation = Some(base_transform_decide_allocation::<T>);
+            klass.propose_allocation = Some(base_transform_propose_allocation::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn base_transform_start<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.start(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_stop<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.stop(&wrap)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_transform_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    direction: gst_ffi::GstPadDirection,
+    caps: *mut gst_ffi::GstCaps,
+    filter: *mut gst_ffi::GstCaps,
+) -> *mut gst_ffi::GstCaps
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+    let filter = if filter.is_null() {
+        None
+    } else {
+        Some(gst::CapsRef::from_ptr(filter))
+    };
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::Caps::new_empty(), {
+        imp.transform_caps(
+            &wrap,
+            from_glib(direction),
+            gst::CapsRef::from_ptr(caps),
+            filter,
+        )
+    })
+    .into_ptr()
+}
+
+unsafe extern "C" fn base_transform_fixate_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    direction: gst_ffi::GstPadDirection,
+    caps: *mut gst_ffi::GstCaps,
+    othercaps: *mut gst_ffi::GstCaps,
+) -> *mut gst_ffi::GstCaps
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::Caps::new_empty(), {
+        imp.fixate_caps(
+            &wrap,
+            from_glib(direction),
+            gst::CapsRef::from_ptr(caps),
+            from_glib_full(othercaps),
+        )
+    })
+    .into_ptr()
+}
+
+unsafe extern "C" fn base_transform_set_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    incaps: *mut gst_ffi::GstCaps,
+    outcaps: *mut gst_ffi::GstCaps,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.set_caps(
+            &wrap,
+            gst::CapsRef::from_ptr(incaps),
+            gst::CapsRef::from_ptr(outcaps),
+        )
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_accept_caps<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    direction: gst_ffi::GstPadDirection,
+    caps: *mut gst_ffi::GstCaps,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.accept_caps(&wrap, from_glib(direction), gst::CapsRef::from_ptr(caps))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_query<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    direction: gst_ffi::GstPadDirection,
+    query: *mut gst_ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        BaseTransformImpl::query(
+            imp,
+            &wrap,
+            from_glib(direction),
+            gst::QueryRef::from_mut_ptr(query),
+        )
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_transform_size<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    direction: gst_ffi::GstPadDirection,
+    caps: *mut gst_ffi::GstCaps,
+    size: usize,
+    othercaps: *mut gst_ffi::GstCaps,
+    othersize: *mut usize,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        match imp.transform_size(
+            &wrap,
+            from_glib(direction),
+            gst::CapsRef::from_ptr(caps),
+            size,
+            gst::CapsRef::from_ptr(othercaps),
+        ) {
+            Some(s) => {
+                *othersize = s;
+                true
+            }
+            None => false,
+        }
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_get_unit_size<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    caps: *mut gst_ffi::GstCaps,
+    size: *mut usize,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        match imp.get_unit_size(&wrap, gst::CapsRef::from_ptr(caps)) {
+            Some(s) => {
+                *size = s;
+                true
+            }
+            None => false,
+        }
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_sink_event<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    event: *mut gst_ffi::GstEvent,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.sink_event(&wrap, from_glib_full(event))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_src_event<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    event: *mut gst_ffi::GstEvent,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.src_event(&wrap, from_glib_full(event))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_transform<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    inbuf: *mut gst_ffi::GstBuffer,
+    outbuf: *mut gst_ffi::GstBuffer,
+) -> gst_ffi::GstFlowReturn
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        imp.transform(
+            &wrap,
+            &from_glib_borrow(inbuf),
+            gst::BufferRef::from_mut_ptr(outbuf),
+        )
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_transform_ip<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    buf: *mut gst_ffi::GstBuffer,
+) -> gst_ffi::GstFlowReturn
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        imp.transform_ip(&wrap, gst::BufferRef::from_mut_ptr(buf))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_decide_allocation<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    query: *mut gst_ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+    let query = gst::QueryRef::from_mut_ptr(query);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.decide_allocation(&wrap, query)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn base_transform_propose_allocation<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBaseTransform,
+    decide_query: *mut gst_ffi::GstQuery,
+    query: *mut gst_ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: BaseTransformImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BaseTransform = from_glib_borrow(ptr);
+    let decide_query = if decide_query.is_null() {
+        None
+    } else {
+        Some(gst::QueryRef::from_ptr(decide_query))
+    };
+    let query = gst::QueryRef::from_mut_ptr(query);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.propose_allocation(&wrap, decide_query, query)
+    })
+    .to_glib()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib;
+    use glib::subclass;
+    use std::sync::atomic;
+
+    pub mod imp {
+        use super::*;
+
+        fn class_init_common(klass: &mut BaseTransformClass) {
+            let caps = gst::Caps::new_any();
+            let src_pad_template = gst::PadTemplate::new(
+                "src",
+                gst::PadDirection::Src,
+                gst::PadPresence::Always,
+                &caps,
+            );
+            klass.add_pad_template(src_pad_template);
+
+            let sink_pad_template = gst::PadTemplate::new(
+                "sink",
+                gst::PadDirection::Sink,
+                gst::PadPresence::Always,
+                &caps,
+            );
+            klass.add_pad_template(sink_pad_template);
+
+            klass.configure(BaseTransformMode::AlwaysInPlace, false, false);
+        }
+
+        pub struct TestTransform {
+            pub(super) n_buffers: atomic::AtomicU32,
+        }
+
+        impl ObjectSubclass for TestTransform {
+            const NAME: &'static str = "TestTransform";
+            type ParentType = BaseTransform;
+            type Instance = ElementInstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                Self {
+                    n_buffers: atomic::AtomicU32::new(0),
+                }
+            }
+
+            fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+                klass.set_metadata(
+                    "Test Transform",
+                    "Filter/Generic",
+                    "Counts buffers",
+                    "Sebastian Dröge <sebastian@centricular.com>",
+                );
+
+                class_init_common(klass);
+            }
+        }
+
+        impl ObjectImpl for TestTransform {
+            glib_object_impl!();
+        }
+
+        impl ElementImpl for TestTransform {}
+
+        impl BaseTransformImpl for TestTransform {
+            fn transform_ip(
+                &self,
+                _element: &BaseTransform,
+                _buf: &mut gst::BufferRef,
+            ) -> gst::FlowReturn {
+                self.n_buffers.fetch_add(1, atomic::Ordering::SeqCst);
+                gst::FlowReturn::Ok
+            }
+        }
+
+        pub struct TestUnimplementedTransform;
+
+        impl ObjectSubclass for TestUnimplementedTransform {
+            const NAME: &'static str = "TestUnimplementedTransform";
+            type ParentType = BaseTransform;
+            type Instance = ElementInstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                TestUnimplementedTransform
+            }
+
+            fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+                klass.set_metadata(
+                    "Test Unimplemented Transform",
+                    "Filter/Generic",
+                    "Does not implement transform_ip",
+                    "Sebastian Dröge <sebastian@centricular.com>",
+                );
+
+                class_init_common(klass);
+            }
+        }
+
+        impl ObjectImpl for TestUnimplementedTransform {
+            glib_object_impl!();
+        }
+
+        impl ElementImpl for TestUnimplementedTransform {}
+
+        impl BaseTransformImpl for TestUnimplementedTransform {}
+    }
+
+    fn run_pipeline(transform: &gst::Element) -> Result<(), glib::Error> {
+        let pipeline = gst::Pipeline::new(None);
+        let src = gst::ElementFactory::make("fakesrc", None).unwrap();
+        let capsfilter = gst::ElementFactory::make("capsfilter", None).unwrap();
+        let sink = gst::ElementFactory::make("fakesink", None).unwrap();
+
+        src.set_property("num-buffers", &100i32).unwrap();
+        capsfilter
+            .set_property("caps", &gst::Caps::new_simple("foo/bar", &[]))
+            .unwrap();
+
+        pipeline
+            .add_many(&[&src, &capsfilter, transform, &sink])
+            .unwrap();
+        gst::Element::link_many(&[&src, &capsfilter, transform, &sink]).unwrap();
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Playing),
+            gst::StateChangeReturn::Failure
+        );
+        let bus = pipeline.get_bus().unwrap();
+
+        let mut res = Ok(());
+        while let Some(msg) = bus.timed_pop(gst::CLOCK_TIME_NONE) {
+            match msg.view() {
+                gst::MessageView::Eos(..) => break,
+                gst::MessageView::Error(err) => {
+                    res = Err(err.get_error());
+                    break;
+                }
+                _ => (),
+            }
+        }
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Null),
+            gst::StateChangeReturn::Failure
+        );
+
+        res
+    }
+
+    #[test]
+    fn test_base_transform_subclass() {
+        gst::init().unwrap();
+
+        let type_ = imp::TestTransform::get_type();
+        assert!(gst::Element::register(None, "testtransform", 0, type_));
+
+        let transform = gst::ElementFactory::make("testtransform", None).unwrap();
+        run_pipeline(&transform).unwrap();
+
+        let transform = transform.downcast_ref::<BaseTransform>().unwrap();
+        let imp = imp::TestTransform::from_instance(transform);
+        assert_eq!(imp.n_buffers.load(atomic::Ordering::SeqCst), 100);
+    }
+
+    #[test]
+    fn test_base_transform_unimplemented() {
+        gst::init().unwrap();
+
+        let type_ = imp::TestUnimplementedTransform::get_type();
+        assert!(gst::Element::register(
+            None,
+            "testunimplementedtransform",
+            0,
+            type_
+        ));
+
+        let transform = gst::ElementFactory::make("testunimplementedtransform", None).unwrap();
+        let err = run_pipeline(&transform).unwrap_err();
+        assert_eq!(
+            err.kind::<gst::CoreError>(),
+            Some(gst::CoreError::NotImplemented)
+        );
+    }
+}
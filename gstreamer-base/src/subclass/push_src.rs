@@ -0,0 +1,287 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::ops;
+use std::ptr;
+
+use ffi;
+use gobject_ffi;
+use gst_ffi;
+
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use gst;
+use gst::subclass::prelude::*;
+use gst::subclass::ElementInstanceStruct;
+
+use super::base_src::{BaseSrcClass, BaseSrcImpl};
+use PushSrc;
+
+pub trait PushSrcImpl: BaseSrcImpl + Send + Sync + 'static {
+    fn fill(&self, element: &PushSrc, buffer: &mut gst::BufferRef) -> gst::FlowReturn {
+        PushSrcImplExt::parent_fill(self, element, buffer)
+    }
+
+    fn create(&self, element: &PushSrc) -> Result<gst::Buffer, gst::FlowError> {
+        PushSrcImplExt::parent_create(self, element)
+    }
+}
+
+pub trait PushSrcImplExt {
+    fn parent_fill(&self, element: &PushSrc, buffer: &mut gst::BufferRef) -> gst::FlowReturn;
+
+    fn parent_create(&self, element: &PushSrc) -> Result<gst::Buffer, gst::FlowError>;
+}
+
+impl<T: PushSrcImpl + ObjectSubclass> PushSrcImplExt for T
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn parent_fill(&self, element: &PushSrc, buffer: &mut gst::BufferRef) -> gst::FlowReturn {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstPushSrcClass;
+            (*parent_class)
+                .fill
+                .map(|f| from_glib(f(element.to_glib_none().0, buffer.as_mut_ptr())))
+                .unwrap_or(gst::FlowReturn::NotSupported)
+        }
+    }
+
+    fn parent_create(&self, element: &PushSrc) -> Result<gst::Buffer, gst::FlowError> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstPushSrcClass;
+            (*parent_class)
+                .create
+                .map(|f| {
+                    let mut buffer: *mut gst_ffi::GstBuffer = ptr::null_mut();
+                    let ret: gst::FlowReturn = from_glib(f(element.to_glib_none().0, &mut buffer));
+
+                    ret.into_result().map(|_| from_glib_full(buffer))
+                })
+                .unwrap_or_else(|| {
+                    // GstPushSrc has no create implementation of its own and
+                    // falls back to the GstBaseSrc one, which allocates a
+                    // buffer and calls fill
+                    let base_src_class =
+                        gobject_ffi::g_type_class_peek(ffi::gst_base_src_get_type())
+                            as *mut ffi::GstBaseSrcClass;
+                    let base_src = element.to_glib_none().0 as *mut ffi::GstBaseSrc;
+
+                    (*base_src_class)
+                        .create
+                        .map(|f| {
+                            let mut buffer: *mut gst_ffi::GstBuffer = ptr::null_mut();
+                            let ret: gst::FlowReturn = from_glib(f(
+                                base_src,
+                                // Push sources don't support random access,
+                                // so no offset is requested
+                                gst_ffi::GST_BUFFER_OFFSET_NONE,
+                                ffi::gst_base_src_get_blocksize(base_src),
+                                &mut buffer,
+                            ));
+
+                            ret.into_result().map(|_| from_glib_full(buffer))
+                        })
+                        .unwrap_or(Err(gst::FlowError::NotSupported))
+                })
+        }
+    }
+}
+
+/// Rust class struct for `GstPushSrc` and all its subclasses.
+#[repr(C)]
+pub struct PushSrcClass(ffi::GstPushSrcClass);
+
+unsafe impl IsClassFor for PushSrcClass {
+    type Instance = PushSrc;
+}
+
+unsafe impl Send for PushSrcClass {}
+unsafe impl Sync for PushSrcClass {}
+
+impl ops::Deref for PushSrcClass {
+    type Target = BaseSrcClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for PushSrcClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl ElementClassSubclassExt for PushSrcClass {}
+
+unsafe impl<T: ObjectSubclass + PushSrcImpl> IsSubclassable<T> for PushSrcClass
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn override_vfuncs(&mut self) {
+        <BaseSrcClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstPushSrcClass);
+            klass.fill = Some(push_src_fill::<T>);
+            klass.create = Some(push_src_create::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn push_src_fill<T: ObjectSubclass>(
+    ptr: *mut ffi::GstPushSrc,
+    buffer: *mut gst_ffi::GstBuffer,
+) -> gst_ffi::GstFlowReturn
+where
+    T: PushSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: PushSrc = from_glib_borrow(ptr);
+    let buffer = gst::BufferRef::from_mut_ptr(buffer);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        PushSrcImpl::fill(imp, &wrap, buffer)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn push_src_create<T: ObjectSubclass>(
+    ptr: *mut ffi::GstPushSrc,
+    buffer_ptr: *mut *mut gst_ffi::GstBuffer,
+) -> gst_ffi::GstFlowReturn
+where
+    T: PushSrcImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: PushSrc = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), gst::FlowReturn::Error, {
+        match PushSrcImpl::create(imp, &wrap) {
+            Ok(buffer) => {
+                *buffer_ptr = buffer.into_ptr();
+                gst::FlowReturn::Ok
+            }
+            Err(err) => gst::FlowReturn::from_error(err),
+        }
+    })
+    .to_glib()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib;
+    use glib::subclass;
+    use std::sync::atomic;
+
+    pub mod imp {
+        use super::*;
+
+        pub struct TestPushSrc {
+            pub(super) n_buffers: atomic::AtomicU32,
+        }
+
+        impl ObjectSubclass for TestPushSrc {
+            const NAME: &'static str = "TestPushSrc";
+            type ParentType = PushSrc;
+            type Instance = ElementInstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                Self {
+                    n_buffers: atomic::AtomicU32::new(0),
+                }
+            }
+
+            fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+                klass.set_metadata(
+                    "Test Push Source",
+                    "Source/Generic",
+                    "Produces empty buffers",
+                    "Sebastian Dröge <sebastian@centricular.com>",
+                );
+
+                let caps = gst::Caps::new_any();
+                let src_pad_template = gst::PadTemplate::new(
+                    "src",
+                    gst::PadDirection::Src,
+                    gst::PadPresence::Always,
+                    &caps,
+                );
+                klass.add_pad_template(src_pad_template);
+            }
+        }
+
+        impl ObjectImpl for TestPushSrc {
+            glib_object_impl!();
+        }
+
+        impl ElementImpl for TestPushSrc {}
+
+        impl BaseSrcImpl for TestPushSrc {}
+
+        impl PushSrcImpl for TestPushSrc {
+            fn fill(&self, _element: &PushSrc, _buffer: &mut gst::BufferRef) -> gst::FlowReturn {
+                self.n_buffers.fetch_add(1, atomic::Ordering::SeqCst);
+                gst::FlowReturn::Ok
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_src_subclass() {
+        gst::init().unwrap();
+
+        let type_ = imp::TestPushSrc::get_type();
+        assert!(gst::Element::register(None, "testpushsrc", 0, type_));
+
+        let pipeline = gst::Pipeline::new(None);
+        let src = gst::ElementFactory::make("testpushsrc", None).unwrap();
+        let sink = gst::ElementFactory::make("fakesink", None).unwrap();
+
+        src.set_property("num-buffers", &100i32).unwrap();
+
+        pipeline.add_many(&[&src, &sink]).unwrap();
+        src.link(&sink).unwrap();
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Playing),
+            gst::StateChangeReturn::Failure
+        );
+        let bus = pipeline.get_bus().unwrap();
+
+        while let Some(msg) = bus.timed_pop(gst::CLOCK_TIME_NONE) {
+            match msg.view() {
+                gst::MessageView::Eos(..) => break,
+                gst::MessageView::Error(err) => panic!("{:?}", err.get_error()),
+                _ => (),
+            }
+        }
+
+        assert_ne!(
+            pipeline.set_state(gst::State::Null),
+            gst::StateChangeReturn::Failure
+        );
+
+        let src = src.downcast_ref::<PushSrc>().unwrap();
+        let imp = imp::TestPushSrc::from_instance(src);
+        assert_eq!(imp.n_buffers.load(atomic::Ordering::SeqCst), 100);
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ffi;
+use ARGBControlBinding;
+
+use glib::object::{Downcast, IsA};
+use glib::translate::*;
+use gst;
+
+impl ARGBControlBinding {
+    pub fn new<
+        P: IsA<gst::Object>,
+        Q: IsA<gst::ControlSource>,
+        R: IsA<gst::ControlSource>,
+        S: IsA<gst::ControlSource>,
+        T: IsA<gst::ControlSource>,
+    >(
+        object: &P,
+        property_name: &str,
+        cs_a: &Q,
+        cs_r: &R,
+        cs_g: &S,
+        cs_b: &T,
+    ) -> ARGBControlBinding {
+        assert_initialized_main_thread!();
+        unsafe {
+            gst::ControlBinding::from_glib_none(ffi::gst_argb_control_binding_new(
+                object.to_glib_none().0,
+                property_name.to_glib_none().0,
+                cs_a.to_glib_none().0,
+                cs_r.to_glib_none().0,
+                cs_g.to_glib_none().0,
+                cs_b.to_glib_none().0,
+            ))
+            .downcast_unchecked()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prelude::*;
+    use InterpolationControlSource;
+    use InterpolationMode;
+
+    #[test]
+    fn test_argb_control_binding() {
+        gst::init().unwrap();
+
+        let src = gst::ElementFactory::make("videotestsrc", None).unwrap();
+
+        let control_source = |value| {
+            let cs = InterpolationControlSource::new();
+            cs.set_property_mode(InterpolationMode::None);
+            cs.set(gst::ClockTime::from_seconds(0), value).unwrap();
+            cs
+        };
+        let cs_a = control_source(1.0);
+        let cs_r = control_source(1.0);
+        let cs_g = control_source(0.0);
+        let cs_b = control_source(0.0);
+
+        let binding = ARGBControlBinding::new(&src, "foreground-color", &cs_a, &cs_r, &cs_g, &cs_b);
+        src.add_control_binding(&binding).unwrap();
+        assert_eq!(
+            src.get_control_binding("foreground-color"),
+            Some(binding.clone().upcast())
+        );
+
+        assert_eq!(
+            src.get_value("foreground-color", gst::ClockTime::from_seconds(0))
+                .and_then(|v| v.get::<u32>()),
+            Some(0xffff_0000)
+        );
+
+        src.sync_values(gst::ClockTime::from_seconds(0)).unwrap();
+        assert_eq!(
+            src.get_property("foreground-color").unwrap().get::<u32>(),
+            Some(0xffff_0000)
+        );
+    }
+}
@@ -0,0 +1,142 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use ffi;
+use glib::StaticType;
+use glib::Value;
+use glib::signal::SignalHandlerId;
+use glib::signal::connect;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+use gst;
+use gst_ffi;
+use std::boxed::Box as Box_;
+use std::mem;
+use std::mem::transmute;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct ARGBControlBinding(Object<ffi::GstARGBControlBinding, ffi::GstARGBControlBindingClass>): [
+        gst::ControlBinding => gst_ffi::GstControlBinding,
+        gst::Object => gst_ffi::GstObject,
+    ];
+
+    match fn {
+        get_type => || ffi::gst_argb_control_binding_get_type(),
+    }
+}
+
+impl ARGBControlBinding {
+    pub fn get_property_control_source_a(&self) -> Option<gst::ControlSource> {
+        unsafe {
+            let mut value = Value::from_type(<gst::ControlSource as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "control-source-a".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get()
+        }
+    }
+
+    pub fn set_property_control_source_a(&self, control_source_a: Option<&gst::ControlSource>) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "control-source-a".to_glib_none().0, Value::from(control_source_a).to_glib_none().0);
+        }
+    }
+
+    pub fn get_property_control_source_b(&self) -> Option<gst::ControlSource> {
+        unsafe {
+            let mut value = Value::from_type(<gst::ControlSource as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "control-source-b".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get()
+        }
+    }
+
+    pub fn set_property_control_source_b(&self, control_source_b: Option<&gst::ControlSource>) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "control-source-b".to_glib_none().0, Value::from(control_source_b).to_glib_none().0);
+        }
+    }
+
+    pub fn get_property_control_source_g(&self) -> Option<gst::ControlSource> {
+        unsafe {
+            let mut value = Value::from_type(<gst::ControlSource as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "control-source-g".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get()
+        }
+    }
+
+    pub fn set_property_control_source_g(&self, control_source_g: Option<&gst::ControlSource>) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "control-source-g".to_glib_none().0, Value::from(control_source_g).to_glib_none().0);
+        }
+    }
+
+    pub fn get_property_control_source_r(&self) -> Option<gst::ControlSource> {
+        unsafe {
+            let mut value = Value::from_type(<gst::ControlSource as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "control-source-r".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get()
+        }
+    }
+
+    pub fn set_property_control_source_r(&self, control_source_r: Option<&gst::ControlSource>) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "control-source-r".to_glib_none().0, Value::from(control_source_r).to_glib_none().0);
+        }
+    }
+
+    pub fn connect_property_control_source_a_notify<F: Fn(&ARGBControlBinding) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&ARGBControlBinding) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::control-source-a",
+                transmute(notify_control_source_a_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+
+    pub fn connect_property_control_source_b_notify<F: Fn(&ARGBControlBinding) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&ARGBControlBinding) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::control-source-b",
+                transmute(notify_control_source_b_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+
+    pub fn connect_property_control_source_g_notify<F: Fn(&ARGBControlBinding) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&ARGBControlBinding) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::control-source-g",
+                transmute(notify_control_source_g_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+
+    pub fn connect_property_control_source_r_notify<F: Fn(&ARGBControlBinding) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&ARGBControlBinding) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::control-source-r",
+                transmute(notify_control_source_r_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+}
+
+unsafe impl Send for ARGBControlBinding {}
+unsafe impl Sync for ARGBControlBinding {}
+
+unsafe extern "C" fn notify_control_source_a_trampoline(this: *mut ffi::GstARGBControlBinding, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&ARGBControlBinding) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
+
+unsafe extern "C" fn notify_control_source_b_trampoline(this: *mut ffi::GstARGBControlBinding, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&ARGBControlBinding) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
+
+unsafe extern "C" fn notify_control_source_g_trampoline(this: *mut ffi::GstARGBControlBinding, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&ARGBControlBinding) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
+
+unsafe extern "C" fn notify_control_source_r_trampoline(this: *mut ffi::GstARGBControlBinding, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&ARGBControlBinding) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
@@ -0,0 +1,82 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use ffi;
+use glib::StaticType;
+use glib::Value;
+use glib::signal::SignalHandlerId;
+use glib::signal::connect;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+use gst;
+use gst_ffi;
+use std::boxed::Box as Box_;
+use std::mem;
+use std::mem::transmute;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct DirectControlBinding(Object<ffi::GstDirectControlBinding, ffi::GstDirectControlBindingClass>): [
+        gst::ControlBinding => gst_ffi::GstControlBinding,
+        gst::Object => gst_ffi::GstObject,
+    ];
+
+    match fn {
+        get_type => || ffi::gst_direct_control_binding_get_type(),
+    }
+}
+
+impl DirectControlBinding {
+    pub fn get_property_absolute(&self) -> bool {
+        unsafe {
+            let mut value = Value::from_type(<bool as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "absolute".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get().unwrap()
+        }
+    }
+
+    pub fn get_property_control_source(&self) -> Option<gst::ControlSource> {
+        unsafe {
+            let mut value = Value::from_type(<gst::ControlSource as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "control-source".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get()
+        }
+    }
+
+    pub fn set_property_control_source(&self, control_source: Option<&gst::ControlSource>) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "control-source".to_glib_none().0, Value::from(control_source).to_glib_none().0);
+        }
+    }
+
+    pub fn connect_property_absolute_notify<F: Fn(&DirectControlBinding) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&DirectControlBinding) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::absolute",
+                transmute(notify_absolute_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+
+    pub fn connect_property_control_source_notify<F: Fn(&DirectControlBinding) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&DirectControlBinding) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::control-source",
+                transmute(notify_control_source_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+}
+
+unsafe impl Send for DirectControlBinding {}
+unsafe impl Sync for DirectControlBinding {}
+
+unsafe extern "C" fn notify_absolute_trampoline(this: *mut ffi::GstDirectControlBinding, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&DirectControlBinding) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
+
+unsafe extern "C" fn notify_control_source_trampoline(this: *mut ffi::GstDirectControlBinding, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&DirectControlBinding) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
@@ -0,0 +1,170 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use LFOWaveform;
+use ffi;
+use glib::StaticType;
+use glib::Value;
+use glib::signal::SignalHandlerId;
+use glib::signal::connect;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+use gst;
+use gst_ffi;
+use std::boxed::Box as Box_;
+use std::mem;
+use std::mem::transmute;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct LFOControlSource(Object<ffi::GstLFOControlSource, ffi::GstLFOControlSourceClass>): [
+        gst::ControlSource => gst_ffi::GstControlSource,
+        gst::Object => gst_ffi::GstObject,
+    ];
+
+    match fn {
+        get_type => || ffi::gst_lfo_control_source_get_type(),
+    }
+}
+
+impl LFOControlSource {
+    pub fn get_property_amplitude(&self) -> f64 {
+        unsafe {
+            let mut value = Value::from_type(<f64 as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "amplitude".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get().unwrap()
+        }
+    }
+
+    pub fn set_property_amplitude(&self, amplitude: f64) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "amplitude".to_glib_none().0, Value::from(&amplitude).to_glib_none().0);
+        }
+    }
+
+    pub fn get_property_frequency(&self) -> f64 {
+        unsafe {
+            let mut value = Value::from_type(<f64 as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "frequency".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get().unwrap()
+        }
+    }
+
+    pub fn set_property_frequency(&self, frequency: f64) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "frequency".to_glib_none().0, Value::from(&frequency).to_glib_none().0);
+        }
+    }
+
+    pub fn get_property_offset(&self) -> f64 {
+        unsafe {
+            let mut value = Value::from_type(<f64 as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "offset".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get().unwrap()
+        }
+    }
+
+    pub fn set_property_offset(&self, offset: f64) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "offset".to_glib_none().0, Value::from(&offset).to_glib_none().0);
+        }
+    }
+
+    pub fn get_property_timeshift(&self) -> u64 {
+        unsafe {
+            let mut value = Value::from_type(<u64 as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "timeshift".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get().unwrap()
+        }
+    }
+
+    pub fn set_property_timeshift(&self, timeshift: u64) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "timeshift".to_glib_none().0, Value::from(&timeshift).to_glib_none().0);
+        }
+    }
+
+    pub fn get_property_waveform(&self) -> LFOWaveform {
+        unsafe {
+            let mut value = Value::from_type(<LFOWaveform as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "waveform".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get().unwrap()
+        }
+    }
+
+    pub fn set_property_waveform(&self, waveform: LFOWaveform) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "waveform".to_glib_none().0, Value::from(&waveform).to_glib_none().0);
+        }
+    }
+
+    pub fn connect_property_amplitude_notify<F: Fn(&LFOControlSource) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&LFOControlSource) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::amplitude",
+                transmute(notify_amplitude_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+
+    pub fn connect_property_frequency_notify<F: Fn(&LFOControlSource) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&LFOControlSource) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::frequency",
+                transmute(notify_frequency_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+
+    pub fn connect_property_offset_notify<F: Fn(&LFOControlSource) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&LFOControlSource) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::offset",
+                transmute(notify_offset_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+
+    pub fn connect_property_timeshift_notify<F: Fn(&LFOControlSource) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&LFOControlSource) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::timeshift",
+                transmute(notify_timeshift_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+
+    pub fn connect_property_waveform_notify<F: Fn(&LFOControlSource) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&LFOControlSource) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::waveform",
+                transmute(notify_waveform_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+}
+
+unsafe impl Send for LFOControlSource {}
+unsafe impl Sync for LFOControlSource {}
+
+unsafe extern "C" fn notify_amplitude_trampoline(this: *mut ffi::GstLFOControlSource, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&LFOControlSource) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
+
+unsafe extern "C" fn notify_frequency_trampoline(this: *mut ffi::GstLFOControlSource, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&LFOControlSource) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
+
+unsafe extern "C" fn notify_offset_trampoline(this: *mut ffi::GstLFOControlSource, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&LFOControlSource) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
+
+unsafe extern "C" fn notify_timeshift_trampoline(this: *mut ffi::GstLFOControlSource, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&LFOControlSource) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
+
+unsafe extern "C" fn notify_waveform_trampoline(this: *mut ffi::GstLFOControlSource, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&LFOControlSource) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
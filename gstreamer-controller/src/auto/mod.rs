@@ -0,0 +1,36 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+mod argb_control_binding;
+pub use self::argb_control_binding::ARGBControlBinding;
+
+mod direct_control_binding;
+pub use self::direct_control_binding::DirectControlBinding;
+
+mod interpolation_control_source;
+pub use self::interpolation_control_source::InterpolationControlSource;
+
+mod lfo_control_source;
+pub use self::lfo_control_source::LFOControlSource;
+
+#[cfg(any(feature = "v1_12", feature = "dox"))]
+mod proxy_control_binding;
+#[cfg(any(feature = "v1_12", feature = "dox"))]
+pub use self::proxy_control_binding::ProxyControlBinding;
+
+mod timed_value_control_source;
+pub use self::timed_value_control_source::TimedValueControlSource;
+pub use self::timed_value_control_source::TimedValueControlSourceExt;
+
+mod trigger_control_source;
+pub use self::trigger_control_source::TriggerControlSource;
+
+mod enums;
+pub use self::enums::InterpolationMode;
+pub use self::enums::LFOWaveform;
+
+#[doc(hidden)]
+pub mod traits {
+    pub use super::TimedValueControlSourceExt;
+}
@@ -0,0 +1,28 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use ffi;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+use gst;
+use gst_ffi;
+use std::mem;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct ProxyControlBinding(Object<ffi::GstProxyControlBinding, ffi::GstProxyControlBindingClass>): [
+        gst::ControlBinding => gst_ffi::GstControlBinding,
+        gst::Object => gst_ffi::GstObject,
+    ];
+
+    match fn {
+        get_type => || ffi::gst_proxy_control_binding_get_type(),
+    }
+}
+
+impl ProxyControlBinding {}
+
+unsafe impl Send for ProxyControlBinding {}
+unsafe impl Sync for ProxyControlBinding {}
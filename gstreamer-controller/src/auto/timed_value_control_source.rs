@@ -0,0 +1,88 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use ffi;
+use glib;
+use glib::object::IsA;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+use gst;
+use gst_ffi;
+use std::mem;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct TimedValueControlSource(Object<ffi::GstTimedValueControlSource, ffi::GstTimedValueControlSourceClass>): [
+        gst::ControlSource => gst_ffi::GstControlSource,
+        gst::Object => gst_ffi::GstObject,
+    ];
+
+    match fn {
+        get_type => || ffi::gst_timed_value_control_source_get_type(),
+    }
+}
+
+unsafe impl Send for TimedValueControlSource {}
+unsafe impl Sync for TimedValueControlSource {}
+
+pub trait TimedValueControlSourceExt {
+    //fn find_control_point_iter(&self, timestamp: gst::ClockTime) -> /*Ignored*/Option<glib::SequenceIter>;
+
+    fn get_count(&self) -> i32;
+
+    fn set(&self, timestamp: gst::ClockTime, value: f64) -> Result<(), glib::error::BoolError>;
+
+    fn unset(&self, timestamp: gst::ClockTime) -> bool;
+
+    fn unset_all(&self);
+
+    //fn connect_value_added<Unsupported or ignored types>(&self, f: F) -> SignalHandlerId;
+
+    //fn connect_value_changed<Unsupported or ignored types>(&self, f: F) -> SignalHandlerId;
+
+    //fn connect_value_removed<Unsupported or ignored types>(&self, f: F) -> SignalHandlerId;
+}
+
+impl<O: IsA<TimedValueControlSource>> TimedValueControlSourceExt for O {
+    //fn find_control_point_iter(&self, timestamp: gst::ClockTime) -> /*Ignored*/Option<glib::SequenceIter> {
+    //    unsafe { TODO: call ffi::gst_timed_value_control_source_find_control_point_iter() }
+    //}
+
+    fn get_count(&self) -> i32 {
+        unsafe {
+            ffi::gst_timed_value_control_source_get_count(self.to_glib_none().0)
+        }
+    }
+
+    fn set(&self, timestamp: gst::ClockTime, value: f64) -> Result<(), glib::error::BoolError> {
+        unsafe {
+            glib::error::BoolError::from_glib(ffi::gst_timed_value_control_source_set(self.to_glib_none().0, timestamp.to_glib(), value), "Failed to set control point")
+        }
+    }
+
+    fn unset(&self, timestamp: gst::ClockTime) -> bool {
+        unsafe {
+            from_glib(ffi::gst_timed_value_control_source_unset(self.to_glib_none().0, timestamp.to_glib()))
+        }
+    }
+
+    fn unset_all(&self) {
+        unsafe {
+            ffi::gst_timed_value_control_source_unset_all(self.to_glib_none().0);
+        }
+    }
+
+    //fn connect_value_added<Unsupported or ignored types>(&self, f: F) -> SignalHandlerId {
+    //    Ignored timed_value: GstController.ControlPoint
+    //}
+
+    //fn connect_value_changed<Unsupported or ignored types>(&self, f: F) -> SignalHandlerId {
+    //    Ignored timed_value: GstController.ControlPoint
+    //}
+
+    //fn connect_value_removed<Unsupported or ignored types>(&self, f: F) -> SignalHandlerId {
+    //    Ignored timed_value: GstController.ControlPoint
+    //}
+}
@@ -0,0 +1,63 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use TimedValueControlSource;
+use ffi;
+use glib::StaticType;
+use glib::Value;
+use glib::signal::SignalHandlerId;
+use glib::signal::connect;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+use gst;
+use gst_ffi;
+use std::boxed::Box as Box_;
+use std::mem;
+use std::mem::transmute;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct TriggerControlSource(Object<ffi::GstTriggerControlSource, ffi::GstTriggerControlSourceClass>): [
+        TimedValueControlSource,
+        gst::ControlSource => gst_ffi::GstControlSource,
+        gst::Object => gst_ffi::GstObject,
+    ];
+
+    match fn {
+        get_type => || ffi::gst_trigger_control_source_get_type(),
+    }
+}
+
+impl TriggerControlSource {
+    pub fn get_property_tolerance(&self) -> i64 {
+        unsafe {
+            let mut value = Value::from_type(<i64 as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "tolerance".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get().unwrap()
+        }
+    }
+
+    pub fn set_property_tolerance(&self, tolerance: i64) {
+        unsafe {
+            gobject_ffi::g_object_set_property(self.to_glib_none().0, "tolerance".to_glib_none().0, Value::from(&tolerance).to_glib_none().0);
+        }
+    }
+
+    pub fn connect_property_tolerance_notify<F: Fn(&TriggerControlSource) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&TriggerControlSource) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::tolerance",
+                transmute(notify_tolerance_trampoline as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+}
+
+unsafe impl Send for TriggerControlSource {}
+unsafe impl Sync for TriggerControlSource {}
+
+unsafe extern "C" fn notify_tolerance_trampoline(this: *mut ffi::GstTriggerControlSource, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer) {
+    let f: &&(Fn(&TriggerControlSource) + Send + Sync + 'static) = transmute(f);
+    f(&from_glib_borrow(this))
+}
@@ -0,0 +1,97 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ffi;
+use DirectControlBinding;
+
+use glib::object::{Downcast, IsA};
+use glib::translate::*;
+use gst;
+
+impl DirectControlBinding {
+    pub fn new<P: IsA<gst::Object>, Q: IsA<gst::ControlSource>>(
+        object: &P,
+        property_name: &str,
+        cs: &Q,
+    ) -> DirectControlBinding {
+        assert_initialized_main_thread!();
+        unsafe {
+            gst::ControlBinding::from_glib_none(ffi::gst_direct_control_binding_new(
+                object.to_glib_none().0,
+                property_name.to_glib_none().0,
+                cs.to_glib_none().0,
+            ))
+            .downcast_unchecked()
+        }
+    }
+
+    pub fn new_absolute<P: IsA<gst::Object>, Q: IsA<gst::ControlSource>>(
+        object: &P,
+        property_name: &str,
+        cs: &Q,
+    ) -> DirectControlBinding {
+        assert_initialized_main_thread!();
+        unsafe {
+            gst::ControlBinding::from_glib_none(ffi::gst_direct_control_binding_new_absolute(
+                object.to_glib_none().0,
+                property_name.to_glib_none().0,
+                cs.to_glib_none().0,
+            ))
+            .downcast_unchecked()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prelude::*;
+    use InterpolationControlSource;
+    use InterpolationMode;
+
+    #[test]
+    fn test_direct_control_binding() {
+        gst::init().unwrap();
+
+        let volume = gst::ElementFactory::make("volume", None).unwrap();
+
+        let cs = InterpolationControlSource::new();
+        cs.set_property_mode(InterpolationMode::Linear);
+        cs.set_from_list(&[
+            (gst::ClockTime::from_seconds(0), 0.0),
+            (gst::ClockTime::from_seconds(1), 2.0),
+        ])
+        .unwrap();
+
+        let binding = DirectControlBinding::new_absolute(&volume, "volume", &cs);
+        assert!(binding.get_property_absolute());
+        volume.add_control_binding(&binding).unwrap();
+        assert_eq!(
+            volume.get_control_binding("volume"),
+            Some(binding.clone().upcast())
+        );
+        assert!(volume.get_control_binding("mute").is_none());
+
+        assert_eq!(
+            volume
+                .get_value("volume", gst::ClockTime::from_mseconds(500))
+                .and_then(|v| v.get::<f64>()),
+            Some(1.0)
+        );
+
+        volume.sync_values(gst::ClockTime::from_seconds(1)).unwrap();
+        assert_eq!(
+            volume.get_property("volume").unwrap().get::<f64>(),
+            Some(2.0)
+        );
+
+        volume.remove_control_binding(&binding).unwrap();
+        assert!(volume.get_control_binding("volume").is_none());
+        assert!(volume.remove_control_binding(&binding).is_err());
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ffi;
+use InterpolationControlSource;
+
+use glib::object::Downcast;
+use glib::translate::*;
+use gst;
+
+impl InterpolationControlSource {
+    pub fn new() -> InterpolationControlSource {
+        assert_initialized_main_thread!();
+        let (major, minor, _, _) = gst::version();
+        if (major, minor) > (1, 12) {
+            unsafe {
+                gst::ControlSource::from_glib_full(ffi::gst_interpolation_control_source_new())
+                    .downcast_unchecked()
+            }
+        } else {
+            // Work-around for 1.14 switching from transfer-floating to transfer-full
+            unsafe {
+                gst::ControlSource::from_glib_none(ffi::gst_interpolation_control_source_new())
+                    .downcast_unchecked()
+            }
+        }
+    }
+}
+
+impl Default for InterpolationControlSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
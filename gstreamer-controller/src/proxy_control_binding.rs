@@ -0,0 +1,34 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ffi;
+use ProxyControlBinding;
+
+use glib::object::{Downcast, IsA};
+use glib::translate::*;
+use gst;
+
+impl ProxyControlBinding {
+    pub fn new<P: IsA<gst::Object>, Q: IsA<gst::Object>>(
+        object: &P,
+        property_name: &str,
+        ref_object: &Q,
+        ref_property_name: &str,
+    ) -> ProxyControlBinding {
+        assert_initialized_main_thread!();
+        unsafe {
+            gst::ControlBinding::from_glib_none(ffi::gst_proxy_control_binding_new(
+                object.to_glib_none().0,
+                property_name.to_glib_none().0,
+                ref_object.to_glib_none().0,
+                ref_property_name.to_glib_none().0,
+            ))
+            .downcast_unchecked()
+        }
+    }
+}
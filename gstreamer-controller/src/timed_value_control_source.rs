@@ -0,0 +1,107 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ffi;
+use glib;
+use glib::object::IsA;
+use glib::translate::*;
+use glib_ffi;
+use gst;
+use std::ptr;
+use TimedValueControlSource;
+
+pub trait TimedValueControlSourceExtManual {
+    fn get_all(&self) -> Vec<(gst::ClockTime, f64)>;
+
+    fn set_from_list(&self, values: &[(gst::ClockTime, f64)]) -> Result<(), glib::BoolError>;
+}
+
+impl<O: IsA<TimedValueControlSource>> TimedValueControlSourceExtManual for O {
+    fn get_all(&self) -> Vec<(gst::ClockTime, f64)> {
+        unsafe {
+            let list = ffi::gst_timed_value_control_source_get_all(self.to_glib_none().0);
+
+            let mut values = Vec::new();
+            let mut item = list;
+            while !item.is_null() {
+                let value = (*item).data as *const ffi::GstTimedValue;
+                values.push((from_glib((*value).timestamp), (*value).value));
+                item = (*item).next;
+            }
+
+            // The list is transfer container, the control points are owned by the control source
+            glib_ffi::g_list_free(list);
+
+            values
+        }
+    }
+
+    fn set_from_list(&self, values: &[(gst::ClockTime, f64)]) -> Result<(), glib::BoolError> {
+        let mut timed_values = values
+            .iter()
+            .map(|&(timestamp, value)| ffi::GstTimedValue {
+                timestamp: timestamp.to_glib(),
+                value,
+            })
+            .collect::<Vec<_>>();
+
+        unsafe {
+            let mut list = ptr::null_mut();
+            for timed_value in timed_values.iter_mut().rev() {
+                list = glib_ffi::g_slist_prepend(list, timed_value as *mut _ as glib_ffi::gpointer);
+            }
+
+            let res =
+                ffi::gst_timed_value_control_source_set_from_list(self.to_glib_none().0, list);
+            glib_ffi::g_slist_free(list);
+
+            glib::BoolError::from_glib(res, "Failed to set control points")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prelude::*;
+    use InterpolationControlSource;
+    use InterpolationMode;
+
+    #[test]
+    fn test_set_from_list() {
+        gst::init().unwrap();
+
+        let cs = InterpolationControlSource::new();
+        cs.set_property_mode(InterpolationMode::Linear);
+
+        let values = [
+            (gst::ClockTime::from_seconds(0), 0.0),
+            (gst::ClockTime::from_seconds(1), 1.0),
+        ];
+        cs.set_from_list(&values).unwrap();
+        assert_eq!(cs.get_count(), 2);
+        assert_eq!(cs.get_all(), values);
+
+        assert_eq!(
+            cs.control_source_get_value(gst::ClockTime::from_mseconds(500)),
+            Some(0.5)
+        );
+
+        let mut array = [0.0; 3];
+        cs.control_source_get_value_array(
+            gst::ClockTime::from_seconds(0),
+            gst::ClockTime::from_mseconds(500),
+            &mut array,
+        )
+        .unwrap();
+        assert_eq!(array, [0.0, 0.5, 1.0]);
+
+        cs.unset_all();
+        assert_eq!(cs.get_count(), 0);
+    }
+}
@@ -10,7 +10,7 @@ use std::fmt;
 
 use ffi;
 use glib;
-use glib::translate::{from_glib, ToGlib};
+use glib::translate::{from_glib, from_glib_none, ToGlib};
 use gst;
 use gst::prelude::*;
 use gst_ffi;
@@ -177,6 +177,38 @@ impl fmt::Debug for VideoOverlayCompositionMeta {
     }
 }
 
+/// Data of the video scale transformation that is passed to
+/// `gst::MetaImpl::transform()` when video frames are scaled.
+#[derive(Debug)]
+pub struct VideoMetaTransformScale {
+    in_info: ::VideoInfo,
+    out_info: ::VideoInfo,
+}
+
+impl VideoMetaTransformScale {
+    pub fn from_meta_transform(transform: &gst::MetaTransform) -> Option<Self> {
+        match *transform {
+            gst::MetaTransform::Other(ref other) if other.get_name() == "gst-video-scale" => unsafe {
+                let data = &*(other.get_data() as *const ffi::GstVideoMetaTransform);
+
+                Some(VideoMetaTransformScale {
+                    in_info: from_glib_none(data.in_info),
+                    out_info: from_glib_none(data.out_info),
+                })
+            },
+            _ => None,
+        }
+    }
+
+    pub fn get_in_info(&self) -> &::VideoInfo {
+        &self.in_info
+    }
+
+    pub fn get_out_info(&self) -> &::VideoInfo {
+        &self.out_info
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,180 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+use std::mem;
+use std::ptr;
+
+use ffi;
+use glib::translate::{from_glib, from_glib_full, ToGlib, ToGlibPtr};
+use glib::IsA;
+
+use Memory;
+use MemoryFlags;
+use Object;
+
+glib_wrapper! {
+    pub struct Allocator(Object<ffi::GstAllocator, ffi::GstAllocatorClass>): Object;
+
+    match fn {
+        get_type => || ffi::gst_allocator_get_type(),
+    }
+}
+
+impl Allocator {
+    pub fn find<'a, P: Into<Option<&'a str>>>(name: P) -> Option<Allocator> {
+        assert_initialized_main_thread!();
+        let name = name.into();
+        unsafe { from_glib_full(ffi::gst_allocator_find(name.to_glib_none().0)) }
+    }
+
+    pub fn register<P: IsA<Allocator>>(name: &str, allocator: &P) {
+        assert_initialized_main_thread!();
+        unsafe {
+            ffi::gst_allocator_register(name.to_glib_none().0, allocator.to_glib_full());
+        }
+    }
+}
+
+unsafe impl Send for Allocator {}
+unsafe impl Sync for Allocator {}
+
+#[derive(Clone)]
+pub struct AllocationParams(ffi::GstAllocationParams);
+
+unsafe impl Send for AllocationParams {}
+unsafe impl Sync for AllocationParams {}
+
+impl AllocationParams {
+    pub fn new(flags: MemoryFlags, align: usize, prefix: usize, padding: usize) -> Self {
+        skip_assert_initialized!();
+        unsafe {
+            let mut params: ffi::GstAllocationParams = mem::zeroed();
+
+            params.flags = flags.to_glib();
+            params.align = align;
+            params.prefix = prefix;
+            params.padding = padding;
+
+            AllocationParams(params)
+        }
+    }
+
+    pub fn get_flags(&self) -> MemoryFlags {
+        from_glib(self.0.flags)
+    }
+
+    pub fn get_align(&self) -> usize {
+        self.0.align
+    }
+
+    pub fn get_prefix(&self) -> usize {
+        self.0.prefix
+    }
+
+    pub fn get_padding(&self) -> usize {
+        self.0.padding
+    }
+
+    pub fn as_ptr(&self) -> *const ffi::GstAllocationParams {
+        &self.0
+    }
+}
+
+impl Default for AllocationParams {
+    fn default() -> Self {
+        skip_assert_initialized!();
+        unsafe {
+            let mut params: ffi::GstAllocationParams = mem::zeroed();
+            ffi::gst_allocation_params_init(&mut params);
+            AllocationParams(params)
+        }
+    }
+}
+
+impl fmt::Debug for AllocationParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AllocationParams")
+            .field("flags", &self.get_flags())
+            .field("align", &self.get_align())
+            .field("prefix", &self.get_prefix())
+            .field("padding", &self.get_padding())
+            .finish()
+    }
+}
+
+impl PartialEq for AllocationParams {
+    fn eq(&self, other: &AllocationParams) -> bool {
+        self.get_flags() == other.get_flags()
+            && self.get_align() == other.get_align()
+            && self.get_prefix() == other.get_prefix()
+            && self.get_padding() == other.get_padding()
+    }
+}
+
+impl Eq for AllocationParams {}
+
+impl From<ffi::GstAllocationParams> for AllocationParams {
+    fn from(params: ffi::GstAllocationParams) -> Self {
+        skip_assert_initialized!();
+        AllocationParams(params)
+    }
+}
+
+impl Into<ffi::GstAllocationParams> for AllocationParams {
+    fn into(self) -> ffi::GstAllocationParams {
+        self.0
+    }
+}
+
+pub trait AllocatorExtManual {
+    fn alloc(&self, size: usize, params: Option<&AllocationParams>) -> Option<Memory>;
+
+    fn set_default(&self);
+}
+
+impl<O: IsA<Allocator>> AllocatorExtManual for O {
+    fn alloc(&self, size: usize, params: Option<&AllocationParams>) -> Option<Memory> {
+        unsafe {
+            let params_ptr = params.map(|p| p.as_ptr()).unwrap_or(ptr::null());
+            from_glib_full(ffi::gst_allocator_alloc(
+                self.to_glib_none().0,
+                size,
+                params_ptr as *mut _,
+            ))
+        }
+    }
+
+    fn set_default(&self) {
+        unsafe {
+            ffi::gst_allocator_set_default(self.to_glib_full());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alloc() {
+        ::init().unwrap();
+
+        let allocator = Allocator::find(None).unwrap();
+        let params = AllocationParams::new(MemoryFlags::ZERO_PADDED, 15, 4, 8);
+        assert_eq!(params.get_align(), 15);
+        assert_eq!(params.get_prefix(), 4);
+        assert_eq!(params.get_padding(), 8);
+
+        let memory = allocator.alloc(16, Some(&params)).unwrap();
+        assert_eq!(memory.get_size(), 16);
+        assert_eq!(memory.get_offset(), 4);
+        assert!(memory.get_maxsize() >= 16 + 4 + 8);
+        assert_eq!(memory.get_allocator(), Some(allocator));
+    }
+}
@@ -0,0 +1,106 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use ClockTime;
+use Object;
+use ffi;
+use glib;
+use glib::StaticType;
+use glib::Value;
+use glib::object::Downcast;
+use glib::object::IsA;
+use glib::signal::SignalHandlerId;
+use glib::signal::connect;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+use std::boxed::Box as Box_;
+use std::mem;
+use std::mem::transmute;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct ControlBinding(Object<ffi::GstControlBinding, ffi::GstControlBindingClass>): Object;
+
+    match fn {
+        get_type => || ffi::gst_control_binding_get_type(),
+    }
+}
+
+unsafe impl Send for ControlBinding {}
+unsafe impl Sync for ControlBinding {}
+
+pub trait ControlBindingExt {
+    //fn get_g_value_array(&self, timestamp: ClockTime, interval: ClockTime, values: /*Ignored*/&[&glib::Value]) -> bool;
+
+    fn control_binding_get_value(&self, timestamp: ClockTime) -> Option<glib::Value>;
+
+    //fn get_value_array(&self, timestamp: ClockTime, interval: ClockTime, values: /*Unimplemented*/&[&Fundamental: Pointer]) -> bool;
+
+    fn is_disabled(&self) -> bool;
+
+    fn set_disabled(&self, disabled: bool);
+
+    fn control_binding_sync_values<P: IsA<Object>>(&self, object: &P, timestamp: ClockTime, last_sync: ClockTime) -> Result<(), glib::error::BoolError>;
+
+    fn get_property_object(&self) -> Option<Object>;
+
+    fn connect_property_object_notify<F: Fn(&Self) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId;
+}
+
+impl<O: IsA<ControlBinding> + IsA<glib::object::Object>> ControlBindingExt for O {
+    //fn get_g_value_array(&self, timestamp: ClockTime, interval: ClockTime, values: /*Ignored*/&[&glib::Value]) -> bool {
+    //    unsafe { TODO: call ffi::gst_control_binding_get_g_value_array() }
+    //}
+
+    fn control_binding_get_value(&self, timestamp: ClockTime) -> Option<glib::Value> {
+        unsafe {
+            from_glib_full(ffi::gst_control_binding_get_value(self.to_glib_none().0, timestamp.to_glib()))
+        }
+    }
+
+    //fn get_value_array(&self, timestamp: ClockTime, interval: ClockTime, values: /*Unimplemented*/&[&Fundamental: Pointer]) -> bool {
+    //    unsafe { TODO: call ffi::gst_control_binding_get_value_array() }
+    //}
+
+    fn is_disabled(&self) -> bool {
+        unsafe {
+            from_glib(ffi::gst_control_binding_is_disabled(self.to_glib_none().0))
+        }
+    }
+
+    fn set_disabled(&self, disabled: bool) {
+        unsafe {
+            ffi::gst_control_binding_set_disabled(self.to_glib_none().0, disabled.to_glib());
+        }
+    }
+
+    fn control_binding_sync_values<P: IsA<Object>>(&self, object: &P, timestamp: ClockTime, last_sync: ClockTime) -> Result<(), glib::error::BoolError> {
+        unsafe {
+            glib::error::BoolError::from_glib(ffi::gst_control_binding_sync_values(self.to_glib_none().0, object.to_glib_none().0, timestamp.to_glib(), last_sync.to_glib()), "Failed to sync values")
+        }
+    }
+
+    fn get_property_object(&self) -> Option<Object> {
+        unsafe {
+            let mut value = Value::from_type(<Object as StaticType>::static_type());
+            gobject_ffi::g_object_get_property(self.to_glib_none().0, "object".to_glib_none().0, value.to_glib_none_mut().0);
+            value.get()
+        }
+    }
+
+    fn connect_property_object_notify<F: Fn(&Self) + Send + Sync + 'static>(&self, f: F) -> SignalHandlerId {
+        unsafe {
+            let f: Box_<Box_<Fn(&Self) + Send + Sync + 'static>> = Box_::new(Box_::new(f));
+            connect(self.to_glib_none().0, "notify::object",
+                transmute(notify_object_trampoline::<Self> as usize), Box_::into_raw(f) as *mut _)
+        }
+    }
+}
+
+unsafe extern "C" fn notify_object_trampoline<P>(this: *mut ffi::GstControlBinding, _param_spec: glib_ffi::gpointer, f: glib_ffi::gpointer)
+where P: IsA<ControlBinding> {
+    let f: &&(Fn(&P) + Send + Sync + 'static) = transmute(f);
+    f(&ControlBinding::from_glib_borrow(this).downcast_unchecked())
+}
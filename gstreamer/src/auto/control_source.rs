@@ -0,0 +1,44 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use ClockTime;
+use Object;
+use ffi;
+use glib::object::IsA;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+use std::mem;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct ControlSource(Object<ffi::GstControlSource, ffi::GstControlSourceClass>): Object;
+
+    match fn {
+        get_type => || ffi::gst_control_source_get_type(),
+    }
+}
+
+unsafe impl Send for ControlSource {}
+unsafe impl Sync for ControlSource {}
+
+pub trait ControlSourceExt {
+    fn control_source_get_value(&self, timestamp: ClockTime) -> Option<f64>;
+
+    //fn control_source_get_value_array(&self, timestamp: ClockTime, interval: ClockTime, values: &[f64]) -> bool;
+}
+
+impl<O: IsA<ControlSource>> ControlSourceExt for O {
+    fn control_source_get_value(&self, timestamp: ClockTime) -> Option<f64> {
+        unsafe {
+            let mut value = mem::uninitialized();
+            let ret = from_glib(ffi::gst_control_source_get_value(self.to_glib_none().0, timestamp.to_glib(), &mut value));
+            if ret { Some(value) } else { None }
+        }
+    }
+
+    //fn control_source_get_value_array(&self, timestamp: ClockTime, interval: ClockTime, values: &[f64]) -> bool {
+    //    unsafe { TODO: call ffi::gst_control_source_get_value_array() }
+    //}
+}
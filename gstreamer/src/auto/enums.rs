@@ -547,6 +547,7 @@ impl SetValue for DebugLevel {
     }
 }
 
+    #[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
     #[derive(Debug, PartialEq, Eq, Hash)]
 #[derive(Clone, Copy)]
 pub enum EventType {
@@ -950,7 +951,8 @@ impl SetValue for LibraryError {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+    #[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(Clone, Copy)]
 pub enum PadDirection {
     Unknown,
@@ -1855,7 +1857,8 @@ impl SetValue for ResourceError {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+    #[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(Clone, Copy)]
 pub enum SeekType {
     None,
@@ -1916,7 +1919,8 @@ impl SetValue for SeekType {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+    #[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[derive(Clone, Copy)]
 pub enum State {
     VoidPending,
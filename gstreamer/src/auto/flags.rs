@@ -569,6 +569,54 @@ impl SetValue for PluginDependencyFlags {
     }
 }
 
+bitflags! {
+    pub struct PluginFlags: u32 {
+        const CACHED = 16;
+        const BLACKLISTED = 32;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for PluginFlags {
+    type GlibType = ffi::GstPluginFlags;
+
+    fn to_glib(&self) -> ffi::GstPluginFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstPluginFlags> for PluginFlags {
+    fn from_glib(value: ffi::GstPluginFlags) -> PluginFlags {
+        skip_assert_initialized!();
+        PluginFlags::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for PluginFlags {
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::gst_plugin_flags_get_type()) }
+    }
+}
+
+impl<'a> FromValueOptional<'a> for PluginFlags {
+    unsafe fn from_value_optional(value: &Value) -> Option<Self> {
+        Some(FromValue::from_value(value))
+    }
+}
+
+impl<'a> FromValue<'a> for PluginFlags {
+    unsafe fn from_value(value: &Value) -> Self {
+        from_glib(gobject_ffi::g_value_get_flags(value.to_glib_none().0))
+    }
+}
+
+impl SetValue for PluginFlags {
+    unsafe fn set_value(value: &mut Value, this: &Self) {
+        gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, this.to_glib())
+    }
+}
+
 bitflags! {
     pub struct SchedulingFlags: u32 {
         const SEEKABLE = 1;
@@ -619,6 +667,7 @@ impl SetValue for SchedulingFlags {
 }
 
 bitflags! {
+    #[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
     pub struct SeekFlags: u32 {
         const NONE = 0;
         const FLUSH = 1;
@@ -785,6 +834,7 @@ impl SetValue for StackTraceFlags {
 }
 
 bitflags! {
+    #[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
     pub struct StreamFlags: u32 {
         const NONE = 0;
         const SPARSE = 1;
@@ -21,6 +21,14 @@ mod clock;
 pub use self::clock::Clock;
 pub use self::clock::ClockExt;
 
+mod control_binding;
+pub use self::control_binding::ControlBinding;
+pub use self::control_binding::ControlBindingExt;
+
+mod control_source;
+pub use self::control_source::ControlSource;
+pub use self::control_source::ControlSourceExt;
+
 mod device;
 pub use self::device::Device;
 pub use self::device::DeviceExt;
@@ -164,6 +172,7 @@ pub use self::flags::PadLinkCheck;
 pub use self::flags::PadProbeType;
 pub use self::flags::ParseFlags;
 pub use self::flags::PluginDependencyFlags;
+pub use self::flags::PluginFlags;
 pub use self::flags::SchedulingFlags;
 pub use self::flags::SeekFlags;
 pub use self::flags::SegmentFlags;
@@ -185,6 +194,8 @@ pub mod traits {
     pub use super::BufferPoolExt;
     pub use super::ChildProxyExt;
     pub use super::ClockExt;
+    pub use super::ControlBindingExt;
+    pub use super::ControlSourceExt;
     pub use super::DeviceExt;
     pub use super::DeviceMonitorExt;
     pub use super::DeviceProviderExt;
@@ -51,12 +51,8 @@ unsafe impl Send for Object {}
 unsafe impl Sync for Object {}
 
 pub trait GstObjectExt {
-    //fn add_control_binding(&self, binding: /*Ignored*/&ControlBinding) -> bool;
-
     fn default_error<'a, P: Into<Option<&'a str>>>(&self, error: &Error, debug: P);
 
-    //fn get_control_binding(&self, property_name: &str) -> /*Ignored*/Option<ControlBinding>;
-
     fn get_control_rate(&self) -> ClockTime;
 
     //fn get_g_value_array(&self, property_name: &str, timestamp: ClockTime, interval: ClockTime, values: /*Ignored*/&[&glib::Value]) -> bool;
@@ -67,8 +63,6 @@ pub trait GstObjectExt {
 
     fn get_path_string(&self) -> String;
 
-    //fn get_value(&self, property_name: &str, timestamp: ClockTime) -> /*Ignored*/Option<glib::Value>;
-
     //fn get_value_array<P: Into<Option</*Unimplemented*/Fundamental: Pointer>>>(&self, property_name: &str, timestamp: ClockTime, interval: ClockTime, n_values: u32, values: P) -> bool;
 
     fn has_active_control_bindings(&self) -> bool;
@@ -79,8 +73,6 @@ pub trait GstObjectExt {
 
     fn has_as_parent<P: IsA<Object>>(&self, parent: &P) -> bool;
 
-    //fn remove_control_binding(&self, binding: /*Ignored*/&ControlBinding) -> bool;
-
     fn set_control_binding_disabled(&self, property_name: &str, disabled: bool);
 
     fn set_control_bindings_disabled(&self, disabled: bool);
@@ -105,10 +97,6 @@ pub trait GstObjectExt {
 }
 
 impl<O: IsA<Object> + IsA<glib::object::Object>> GstObjectExt for O {
-    //fn add_control_binding(&self, binding: /*Ignored*/&ControlBinding) -> bool {
-    //    unsafe { TODO: call ffi::gst_object_add_control_binding() }
-    //}
-
     fn default_error<'a, P: Into<Option<&'a str>>>(&self, error: &Error, debug: P) {
         let debug = debug.into();
         let debug = debug.to_glib_none();
@@ -117,10 +105,6 @@ impl<O: IsA<Object> + IsA<glib::object::Object>> GstObjectExt for O {
         }
     }
 
-    //fn get_control_binding(&self, property_name: &str) -> /*Ignored*/Option<ControlBinding> {
-    //    unsafe { TODO: call ffi::gst_object_get_control_binding() }
-    //}
-
     fn get_control_rate(&self) -> ClockTime {
         unsafe {
             from_glib(ffi::gst_object_get_control_rate(self.to_glib_none().0))
@@ -149,10 +133,6 @@ impl<O: IsA<Object> + IsA<glib::object::Object>> GstObjectExt for O {
         }
     }
 
-    //fn get_value(&self, property_name: &str, timestamp: ClockTime) -> /*Ignored*/Option<glib::Value> {
-    //    unsafe { TODO: call ffi::gst_object_get_value() }
-    //}
-
     //fn get_value_array<P: Into<Option</*Unimplemented*/Fundamental: Pointer>>>(&self, property_name: &str, timestamp: ClockTime, interval: ClockTime, n_values: u32, values: P) -> bool {
     //    unsafe { TODO: call ffi::gst_object_get_value_array() }
     //}
@@ -181,10 +161,6 @@ impl<O: IsA<Object> + IsA<glib::object::Object>> GstObjectExt for O {
         }
     }
 
-    //fn remove_control_binding(&self, binding: /*Ignored*/&ControlBinding) -> bool {
-    //    unsafe { TODO: call ffi::gst_object_remove_control_binding() }
-    //}
-
     fn set_control_binding_disabled(&self, property_name: &str, disabled: bool) {
         unsafe {
             ffi::gst_object_set_control_binding_disabled(self.to_glib_none().0, property_name.to_glib_none().0, disabled.to_glib());
@@ -7,14 +7,18 @@
 // except according to those terms.
 
 use Bin;
+use DebugGraphDetails;
 use Element;
+use Topology;
 
 use glib;
 use glib::translate::{from_glib, from_glib_full, FromGlibPtrContainer, ToGlib, ToGlibPtr};
-use glib::IsA;
+use glib::{Cast, IsA};
 
 use ffi;
 
+use std::path;
+
 pub trait BinExtManual {
     fn add_many<E: IsA<Element>>(&self, elements: &[&E]) -> Result<(), glib::BoolError>;
     fn remove_many<E: IsA<Element>>(&self, elements: &[&E]) -> Result<(), glib::BoolError>;
@@ -26,6 +30,18 @@ pub trait BinExtManual {
     fn iterate_sorted(&self) -> ::Iterator<Element>;
     fn iterate_sources(&self) -> ::Iterator<Element>;
     fn get_children(&self) -> Vec<Element>;
+
+    fn debug_to_dot_data(&self, details: DebugGraphDetails) -> String;
+    fn debug_to_dot_file<P: AsRef<path::Path>>(&self, details: DebugGraphDetails, file_name: P);
+    fn debug_to_dot_file_with_ts<P: AsRef<path::Path>>(
+        &self,
+        details: DebugGraphDetails,
+        file_name: P,
+    );
+
+    /// Returns a snapshot of all elements, pads and links inside the bin,
+    /// including the states of the elements and the negotiated caps.
+    fn topology(&self) -> Topology;
 }
 
 impl<O: IsA<Bin>> BinExtManual for O {
@@ -96,6 +112,26 @@ impl<O: IsA<Bin>> BinExtManual for O {
             FromGlibPtrContainer::from_glib_none(bin.children)
         }
     }
+
+    fn debug_to_dot_data(&self, details: DebugGraphDetails) -> String {
+        ::debug_bin_to_dot_data(self, details)
+    }
+
+    fn debug_to_dot_file<P: AsRef<path::Path>>(&self, details: DebugGraphDetails, file_name: P) {
+        ::debug_bin_to_dot_file(self, details, file_name)
+    }
+
+    fn debug_to_dot_file_with_ts<P: AsRef<path::Path>>(
+        &self,
+        details: DebugGraphDetails,
+        file_name: P,
+    ) {
+        ::debug_bin_to_dot_file_with_ts(self, details, file_name)
+    }
+
+    fn topology(&self) -> Topology {
+        Topology::new(self.upcast_ref())
+    }
 }
 
 #[cfg(test)]
@@ -124,4 +160,17 @@ mod tests {
             vec![String::from("identity0"), String::from("identity1")]
         );
     }
+
+    #[test]
+    fn test_debug_to_dot_data() {
+        ::init().unwrap();
+
+        let bin = ::Bin::new("bin");
+        bin.add(&::ElementFactory::make("identity", "identity0").unwrap())
+            .unwrap();
+
+        let dot = bin.debug_to_dot_data(::DebugGraphDetails::ALL);
+        assert!(dot.starts_with("digraph"));
+        assert!(dot.contains("identity0"));
+    }
 }
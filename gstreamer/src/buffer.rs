@@ -19,6 +19,8 @@ use meta::*;
 use miniobject::*;
 use BufferFlags;
 use ClockTime;
+use Memory;
+use MemoryRef;
 
 use ffi;
 use glib;
@@ -391,6 +393,22 @@ impl BufferRef {
         }
     }
 
+    pub fn add_meta<T: MetaImpl>(
+        &mut self,
+        params: T::Params,
+    ) -> Option<MetaRefMut<CustomMeta<T>, ::meta::Standalone>> {
+        let data = T::init(self, params)?;
+
+        unsafe {
+            let meta = CustomMeta::add(self, data);
+            if meta.is_null() {
+                None
+            } else {
+                Some(CustomMeta::<T>::from_mut_ptr(self, meta))
+            }
+        }
+    }
+
     pub fn iter_meta<T: MetaAPI>(&self) -> MetaIter<T> {
         MetaIter::new(self)
     }
@@ -398,8 +416,202 @@ impl BufferRef {
     pub fn iter_meta_mut<T: MetaAPI>(&mut self) -> MetaIterMut<T> {
         MetaIterMut::new(self)
     }
+
+    pub fn n_memory(&self) -> u32 {
+        unsafe { ffi::gst_buffer_n_memory(self.as_mut_ptr()) }
+    }
+
+    pub fn peek_memory(&self, idx: u32) -> &MemoryRef {
+        assert!(idx < self.n_memory());
+        unsafe { MemoryRef::from_ptr(ffi::gst_buffer_peek_memory(self.as_mut_ptr(), idx)) }
+    }
+
+    pub fn peek_memory_mut(&mut self, idx: u32) -> Result<&mut MemoryRef, glib::BoolError> {
+        assert!(idx < self.n_memory());
+        unsafe {
+            let mem = ffi::gst_buffer_peek_memory(self.as_mut_ptr(), idx);
+            if ffi::gst_mini_object_is_writable(mem as *mut ffi::GstMiniObject) == glib_ffi::GFALSE
+            {
+                Err(glib::BoolError("Memory not writable"))
+            } else {
+                Ok(MemoryRef::from_mut_ptr(mem))
+            }
+        }
+    }
+
+    pub fn get_memory(&self, idx: u32) -> Option<Memory> {
+        if idx >= self.n_memory() {
+            None
+        } else {
+            unsafe { from_glib_full(ffi::gst_buffer_get_memory(self.as_mut_ptr(), idx)) }
+        }
+    }
+
+    pub fn get_memory_range(&self, idx: u32, length: Option<u32>) -> Option<Memory> {
+        assert!(idx + length.unwrap_or(1) <= self.n_memory());
+        unsafe {
+            from_glib_full(ffi::gst_buffer_get_memory_range(
+                self.as_mut_ptr(),
+                idx,
+                match length {
+                    Some(val) => val as i32,
+                    None => -1,
+                },
+            ))
+        }
+    }
+
+    pub fn get_all_memory(&self) -> Option<Memory> {
+        unsafe { from_glib_full(ffi::gst_buffer_get_all_memory(self.as_mut_ptr())) }
+    }
+
+    pub fn find_memory(&self, offset: usize, size: Option<usize>) -> Option<(u32, u32, usize)> {
+        let mut idx = 0;
+        let mut length = 0;
+        let mut skip = 0;
+        let res: bool = unsafe {
+            from_glib(ffi::gst_buffer_find_memory(
+                self.as_mut_ptr(),
+                offset,
+                size.unwrap_or(usize::MAX),
+                &mut idx,
+                &mut length,
+                &mut skip,
+            ))
+        };
+
+        if res {
+            Some((idx, length, skip))
+        } else {
+            None
+        }
+    }
+
+    pub fn is_all_memory_writable(&self) -> bool {
+        unsafe { from_glib(ffi::gst_buffer_is_all_memory_writable(self.as_mut_ptr())) }
+    }
+
+    pub fn insert_memory(&mut self, idx: Option<u32>, mem: Memory) {
+        assert!(idx.map_or(true, |idx| idx <= self.n_memory()));
+
+        unsafe {
+            ffi::gst_buffer_insert_memory(
+                self.as_mut_ptr(),
+                match idx {
+                    Some(val) => val as i32,
+                    None => -1,
+                },
+                mem.into_ptr(),
+            )
+        }
+    }
+
+    pub fn append_memory(&mut self, mem: Memory) {
+        unsafe { ffi::gst_buffer_append_memory(self.as_mut_ptr(), mem.into_ptr()) }
+    }
+
+    pub fn prepend_memory(&mut self, mem: Memory) {
+        unsafe { ffi::gst_buffer_prepend_memory(self.as_mut_ptr(), mem.into_ptr()) }
+    }
+
+    pub fn replace_memory(&mut self, idx: u32, mem: Memory) {
+        assert!(idx < self.n_memory());
+        unsafe { ffi::gst_buffer_replace_memory(self.as_mut_ptr(), idx, mem.into_ptr()) }
+    }
+
+    pub fn replace_all_memory(&mut self, mem: Memory) {
+        unsafe { ffi::gst_buffer_replace_all_memory(self.as_mut_ptr(), mem.into_ptr()) }
+    }
+
+    pub fn remove_memory(&mut self, idx: u32) {
+        assert!(idx < self.n_memory());
+        unsafe { ffi::gst_buffer_remove_memory(self.as_mut_ptr(), idx) }
+    }
+
+    pub fn remove_all_memory(&mut self) {
+        unsafe { ffi::gst_buffer_remove_all_memory(self.as_mut_ptr()) }
+    }
+
+    pub fn iter_memories(&self) -> MemoryIter {
+        MemoryIter::new(self)
+    }
+
+    pub fn iter_memories_owned(&self) -> MemoryIterOwned {
+        MemoryIterOwned::new(self)
+    }
 }
 
+macro_rules! define_memory_iter(
+    ($name:ident, $typ:ty, $get_item:expr) => {
+    pub struct $name<'a> {
+        buffer: &'a BufferRef,
+        idx: u32,
+        n_memory: u32,
+    }
+
+    impl<'a> $name<'a> {
+        fn new(buffer: &'a BufferRef) -> $name<'a> {
+            skip_assert_initialized!();
+
+            let n_memory = buffer.n_memory();
+
+            $name {
+                buffer,
+                idx: 0,
+                n_memory,
+            }
+        }
+    }
+
+    impl<'a> Iterator for $name<'a> {
+        type Item = $typ;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.idx >= self.n_memory {
+                return None;
+            }
+
+            let item = $get_item(self.buffer, self.idx);
+            self.idx += 1;
+
+            item
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            if self.idx == self.n_memory {
+                return (0, Some(0));
+            }
+
+            let remaining = (self.n_memory - self.idx) as usize;
+
+            (remaining, Some(remaining))
+        }
+    }
+
+    impl<'a> DoubleEndedIterator for $name<'a> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+            if self.idx == self.n_memory {
+                return None;
+            }
+
+            self.n_memory -= 1;
+
+            $get_item(self.buffer, self.n_memory)
+        }
+    }
+
+    impl<'a> ExactSizeIterator for $name<'a> {}
+    }
+);
+
+define_memory_iter!(
+    MemoryIter,
+    &'a MemoryRef,
+    |buffer: &'a BufferRef, idx| Some(buffer.peek_memory(idx))
+);
+define_memory_iter!(MemoryIterOwned, Memory, |buffer: &BufferRef, idx| buffer
+    .get_memory(idx));
+
 macro_rules! define_iter(
     ($name:ident, $typ:ty, $mtyp:ty, $prepare_buffer:expr, $from_ptr:expr) => {
     pub struct $name<'a, T: MetaAPI + 'a> {
@@ -473,6 +685,7 @@ impl fmt::Debug for BufferRef {
             .field("offset", &self.get_offset())
             .field("offset_end", &self.get_offset_end())
             .field("flags", &self.get_flags())
+            .field("n_memory", &self.n_memory())
             .finish()
     }
 }
@@ -726,4 +939,54 @@ mod tests {
             assert_eq!(data.as_slice(), vec![0, 2, 3, 4].as_slice());
         }
     }
+
+    #[test]
+    fn test_memories() {
+        ::init().unwrap();
+
+        let mut buffer = Buffer::new();
+        {
+            let buffer = buffer.get_mut().unwrap();
+            buffer.append_memory(::Memory::from_mut_slice(vec![0; 5]).unwrap());
+            buffer.append_memory(::Memory::from_mut_slice(vec![0; 5]).unwrap());
+            buffer.append_memory(::Memory::from_mut_slice(vec![0; 5]).unwrap());
+            buffer.prepend_memory(::Memory::from_mut_slice(vec![0; 10]).unwrap());
+            buffer.insert_memory(Some(1), ::Memory::from_mut_slice(vec![0; 2]).unwrap());
+        }
+
+        assert!(buffer.is_all_memory_writable());
+        assert_eq!(buffer.n_memory(), 5);
+        assert_eq!(buffer.get_size(), 27);
+
+        let sizes = buffer
+            .iter_memories()
+            .map(|m| m.get_size())
+            .collect::<Vec<usize>>();
+        assert_eq!(sizes, vec![10, 2, 5, 5, 5]);
+        assert_eq!(buffer.iter_memories_owned().rev().count(), 5);
+
+        assert_eq!(buffer.find_memory(11, Some(3)), Some((1, 2, 1)));
+
+        {
+            let buffer = buffer.get_mut().unwrap();
+            {
+                let memory = buffer.peek_memory_mut(0).unwrap();
+                let mut map = memory.map_writable().unwrap();
+                map.as_mut_slice()[0] = 1;
+            }
+
+            buffer.remove_memory(1);
+            buffer.replace_memory(0, ::Memory::from_mut_slice(vec![1; 3]).unwrap());
+        }
+
+        assert_eq!(buffer.n_memory(), 4);
+        assert_eq!(buffer.peek_memory(0).get_size(), 3);
+        assert_eq!(buffer.get_size(), 18);
+
+        {
+            let buffer = buffer.get_mut().unwrap();
+            buffer.remove_all_memory();
+        }
+        assert_eq!(buffer.n_memory(), 0);
+    }
 }
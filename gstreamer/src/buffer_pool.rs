@@ -10,7 +10,7 @@ use BufferPool;
 use Structure;
 
 use glib;
-use glib::translate::{from_glib, from_glib_full, from_glib_none, ToGlib, ToGlibPtr, ToGlibPtrMut};
+use glib::translate::{from_glib, from_glib_full, from_glib_none, ToGlib, ToGlibPtr};
 use glib::IsA;
 
 use ffi;
@@ -23,16 +23,16 @@ use std::ptr;
 pub struct BufferPoolConfig(Structure);
 
 impl ops::Deref for BufferPoolConfig {
-    type Target = ::StructureRef;
+    type Target = BufferPoolConfigRef;
 
-    fn deref(&self) -> &::StructureRef {
-        self.0.deref()
+    fn deref(&self) -> &BufferPoolConfigRef {
+        unsafe { BufferPoolConfigRef::from_glib_borrow(self.0.as_ptr()) }
     }
 }
 
 impl ops::DerefMut for BufferPoolConfig {
-    fn deref_mut(&mut self) -> &mut ::StructureRef {
-        self.0.deref_mut()
+    fn deref_mut(&mut self) -> &mut BufferPoolConfigRef {
+        unsafe { BufferPoolConfigRef::from_glib_borrow_mut(self.0.as_mut_ptr()) }
     }
 }
 
@@ -48,20 +48,63 @@ impl AsMut<::StructureRef> for BufferPoolConfig {
     }
 }
 
-impl BufferPoolConfig {
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct BufferPoolConfigRef(::StructureRef);
+
+impl BufferPoolConfigRef {
+    pub unsafe fn from_glib_borrow<'a>(ptr: *const ffi::GstStructure) -> &'a BufferPoolConfigRef {
+        assert!(!ptr.is_null());
+
+        &*(ptr as *const BufferPoolConfigRef)
+    }
+
+    pub unsafe fn from_glib_borrow_mut<'a>(
+        ptr: *mut ffi::GstStructure,
+    ) -> &'a mut BufferPoolConfigRef {
+        assert!(!ptr.is_null());
+
+        &mut *(ptr as *mut BufferPoolConfigRef)
+    }
+}
+
+impl ops::Deref for BufferPoolConfigRef {
+    type Target = ::StructureRef;
+
+    fn deref(&self) -> &::StructureRef {
+        &self.0
+    }
+}
+
+impl ops::DerefMut for BufferPoolConfigRef {
+    fn deref_mut(&mut self) -> &mut ::StructureRef {
+        &mut self.0
+    }
+}
+
+impl AsRef<::StructureRef> for BufferPoolConfigRef {
+    fn as_ref(&self) -> &::StructureRef {
+        &self.0
+    }
+}
+
+impl AsMut<::StructureRef> for BufferPoolConfigRef {
+    fn as_mut(&mut self) -> &mut ::StructureRef {
+        &mut self.0
+    }
+}
+
+impl BufferPoolConfigRef {
     pub fn add_option(&mut self, option: &str) {
         unsafe {
-            ffi::gst_buffer_pool_config_add_option(
-                self.0.to_glib_none_mut().0,
-                option.to_glib_none().0,
-            );
+            ffi::gst_buffer_pool_config_add_option(self.0.as_mut_ptr(), option.to_glib_none().0);
         }
     }
 
     pub fn has_option(&self, option: &str) -> bool {
         unsafe {
             from_glib(ffi::gst_buffer_pool_config_has_option(
-                self.0.to_glib_none().0,
+                self.0.as_mut_ptr(),
                 option.to_glib_none().0,
             ))
         }
@@ -69,12 +112,12 @@ impl BufferPoolConfig {
 
     pub fn get_options(&self) -> Vec<String> {
         unsafe {
-            let n = ffi::gst_buffer_pool_config_n_options(self.0.to_glib_none().0) as usize;
+            let n = ffi::gst_buffer_pool_config_n_options(self.0.as_mut_ptr()) as usize;
             let mut options = Vec::with_capacity(n);
 
             for i in 0..n {
                 options.push(from_glib_none(ffi::gst_buffer_pool_config_get_option(
-                    self.0.to_glib_none().0,
+                    self.0.as_mut_ptr(),
                     i as u32,
                 )));
             }
@@ -94,7 +137,7 @@ impl BufferPoolConfig {
 
         unsafe {
             ffi::gst_buffer_pool_config_set_params(
-                self.0.to_glib_none_mut().0,
+                self.0.as_mut_ptr(),
                 caps.to_glib_none().0,
                 size,
                 min_buffers,
@@ -111,7 +154,7 @@ impl BufferPoolConfig {
             let mut max_buffers = mem::uninitialized();
 
             let ret: bool = from_glib(ffi::gst_buffer_pool_config_get_params(
-                self.0.to_glib_none().0,
+                self.0.as_mut_ptr(),
                 &mut caps,
                 &mut size,
                 &mut min_buffers,
@@ -136,7 +179,7 @@ impl BufferPoolConfig {
 
         unsafe {
             from_glib(ffi::gst_buffer_pool_config_validate_params(
-                self.0.to_glib_none().0,
+                self.0.as_mut_ptr(),
                 caps.to_glib_none().0,
                 size,
                 min_buffers,
@@ -145,14 +188,61 @@ impl BufferPoolConfig {
         }
     }
 
-    // TODO: get_allocator / set_allocator
+    pub fn get_allocator(&self) -> Option<(Option<::Allocator>, ::AllocationParams)> {
+        unsafe {
+            let mut allocator = ptr::null_mut();
+            let mut params = mem::zeroed();
+            let ret: bool = from_glib(ffi::gst_buffer_pool_config_get_allocator(
+                self.0.as_mut_ptr(),
+                &mut allocator,
+                &mut params,
+            ));
+            if ret {
+                Some((from_glib_none(allocator), params.into()))
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn set_allocator(
+        &mut self,
+        allocator: Option<&::Allocator>,
+        params: Option<&::AllocationParams>,
+    ) {
+        assert!(allocator.is_some() || params.is_some());
+        unsafe {
+            ffi::gst_buffer_pool_config_set_allocator(
+                self.0.as_mut_ptr(),
+                allocator.to_glib_none().0,
+                match params {
+                    Some(params) => params.as_ptr(),
+                    None => ptr::null(),
+                },
+            )
+        }
+    }
+
     // TODO: options iterator
 }
 
+#[repr(C)]
 #[derive(Debug)]
 pub struct BufferPoolAcquireParams(ffi::GstBufferPoolAcquireParams);
 
 impl BufferPoolAcquireParams {
+    pub unsafe fn from_glib_borrow<'a>(
+        ptr: *const ffi::GstBufferPoolAcquireParams,
+    ) -> &'a BufferPoolAcquireParams {
+        assert!(!ptr.is_null());
+
+        &*(ptr as *const BufferPoolAcquireParams)
+    }
+
+    pub fn as_ptr(&self) -> *const ffi::GstBufferPoolAcquireParams {
+        &self.0
+    }
+
     pub fn with_flags(flags: ::BufferPoolAcquireFlags) -> Self {
         BufferPoolAcquireParams(ffi::GstBufferPoolAcquireParams {
             format: ffi::GST_FORMAT_UNDEFINED,
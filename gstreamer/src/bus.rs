@@ -13,12 +13,19 @@ use glib::translate::*;
 use glib_ffi;
 use glib_ffi::{gboolean, gpointer};
 use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
 use std::mem::transmute;
 use std::ptr;
+use std::time::{Duration, Instant};
 
 use Bus;
 use BusSyncReply;
+use ClockTime;
+use GstObjectExt;
 use Message;
+use MessageType;
+use MessageView;
 
 unsafe extern "C" fn trampoline_watch(
     bus: *mut ffi::GstBus,
@@ -140,36 +147,232 @@ impl Bus {
     pub fn unset_sync_handler(&self) {
         unsafe { ffi::gst_bus_set_sync_handler(self.to_glib_none().0, None, ptr::null_mut(), None) }
     }
+
+    pub fn pop_filtered(&self, types: &[MessageType]) -> Option<Message> {
+        self.timed_pop_filtered(ClockTime::from_seconds(0), types)
+    }
+
+    pub fn timed_pop_filtered(&self, timeout: ClockTime, types: &[MessageType]) -> Option<Message> {
+        let mask = MessageType::to_glib_mask(types);
+        let deadline = deadline_from_timeout(timeout);
+
+        loop {
+            // Messages that are skipped below must not extend the total
+            // waiting time, so only wait for what is left until the deadline
+            let timeout = timeout_until(deadline);
+
+            let msg: Option<Message> = unsafe {
+                from_glib_full(ffi::gst_bus_timed_pop_filtered(
+                    self.to_glib_none().0,
+                    timeout.to_glib(),
+                    mask,
+                ))
+            };
+
+            // The extended message types share their lower bits with the
+            // other message types, so the mask alone is not exact
+            match msg {
+                Some(msg) => {
+                    if types.contains(&msg.get_type()) {
+                        return Some(msg);
+                    }
+                }
+                None => return None,
+            }
+        }
+    }
+
+    /// Iterates over all messages posted on the bus, waiting up to `timeout`
+    /// for each message.
+    ///
+    /// Iteration stops once no message arrived within `timeout`. Use
+    /// `ClockTime::none()` to wait forever.
+    pub fn iter_timed(&self, timeout: ClockTime) -> BusIter {
+        BusIter {
+            bus: self,
+            timeout,
+            types: None,
+        }
+    }
+
+    /// Iterates over all messages of the given `types` posted on the bus,
+    /// waiting up to `timeout` for each message.
+    ///
+    /// All other messages are dropped from the bus. Iteration stops once no
+    /// matching message arrived within `timeout`.
+    pub fn iter_timed_filtered<'a>(
+        &'a self,
+        timeout: ClockTime,
+        types: &'a [MessageType],
+    ) -> BusIter<'a> {
+        BusIter {
+            bus: self,
+            timeout,
+            types: Some(types),
+        }
+    }
+
+    /// Waits up to `timeout` for an EOS or error message on the bus.
+    ///
+    /// All other messages are dropped from the bus. An error message is
+    /// converted into `BusError::Error`.
+    pub fn wait_for_eos_or_error(&self, timeout: ClockTime) -> Result<(), BusError> {
+        match self.timed_pop_filtered(timeout, &[MessageType::Eos, MessageType::Error]) {
+            Some(msg) => BusError::from_message(&msg),
+            None => Err(BusError::NoMessage),
+        }
+    }
+}
+
+// Returns the point in time at which waiting for `timeout` ends, or `None` if
+// `timeout` is infinite
+pub(crate) fn deadline_from_timeout(timeout: ClockTime) -> Option<Instant> {
+    timeout
+        .nseconds()
+        .map(|timeout| Instant::now() + Duration::from_nanos(timeout))
+}
+
+// Returns the timeout that is left until `deadline`
+pub(crate) fn timeout_until(deadline: Option<Instant>) -> ClockTime {
+    match deadline {
+        Some(deadline) => {
+            let now = Instant::now();
+            if now < deadline {
+                let remaining = deadline - now;
+                ClockTime::from_nseconds(
+                    remaining.as_secs() * ::SECOND_VAL + u64::from(remaining.subsec_nanos()),
+                )
+            } else {
+                ClockTime::from_nseconds(0)
+            }
+        }
+        None => ClockTime::none(),
+    }
+}
+
+#[derive(Debug)]
+pub struct BusIter<'a> {
+    bus: &'a Bus,
+    timeout: ClockTime,
+    types: Option<&'a [MessageType]>,
+}
+
+impl<'a> Iterator for BusIter<'a> {
+    type Item = Message;
+
+    fn next(&mut self) -> Option<Message> {
+        match self.types {
+            Some(types) => self.bus.timed_pop_filtered(self.timeout, types),
+            None => self.bus.timed_pop(self.timeout),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum BusError {
+    NoMessage,
+    Error {
+        src: Option<String>,
+        error: glib::Error,
+        debug: Option<String>,
+    },
+}
+
+impl BusError {
+    pub(crate) fn from_message(msg: &Message) -> Result<(), BusError> {
+        match msg.view() {
+            MessageView::Error(err) => Err(BusError::Error {
+                src: msg.get_src().map(|src| src.get_path_string()),
+                error: err.get_error(),
+                debug: err.get_debug(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for BusError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BusError::NoMessage => write!(f, "Bus error: {}", self.description()),
+            BusError::Error {
+                ref src,
+                ref error,
+                ref debug,
+            } => {
+                write!(
+                    f,
+                    "Error from {}: {}",
+                    src.as_ref().map(String::as_str).unwrap_or("None"),
+                    error
+                )?;
+                if let Some(ref debug) = *debug {
+                    write!(f, " ({})", debug)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for BusError {
+    fn description(&self) -> &str {
+        match *self {
+            BusError::NoMessage => "No EOS or error message received",
+            BusError::Error { .. } => "Error message posted on the bus",
+        }
+    }
 }
 
 #[cfg(any(feature = "futures", feature = "dox"))]
 mod futures {
     use super::*;
+    use futures_channel::mpsc;
     use futures_core::stream::Stream;
-    use futures_core::task::{Context, Waker};
-    use futures_core::{Async, Poll};
-    use std::sync::{Arc, Mutex};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
 
-    pub struct BusStream(Bus, Arc<Mutex<Option<Waker>>>);
+    /// Stream of all messages posted on a `Bus`.
+    ///
+    /// The stream installs a sync handler on the bus that forwards all
+    /// messages to it, so it can't be combined with other sync handlers,
+    /// `Bus::pop()` or bus watches.
+    pub struct BusStream(Bus, mpsc::UnboundedReceiver<Message>);
 
     impl BusStream {
         pub fn new(bus: &Bus) -> Self {
             skip_assert_initialized!();
-            let waker = Arc::new(Mutex::new(None));
-            let waker_clone = Arc::clone(&waker);
-
-            bus.set_sync_handler(move |_, _| {
-                let mut waker = waker_clone.lock().unwrap();
-                if let Some(waker) = waker.take() {
-                    // FIXME: Force type...
-                    let waker: Waker = waker;
-                    waker.wake();
-                }
+            Self::new_internal(bus, None)
+        }
 
-                BusSyncReply::Pass
+        /// Creates a stream of only the messages of the given `types`.
+        ///
+        /// All other messages stay on the bus and can still be retrieved with
+        /// `Bus::pop()` or a bus watch.
+        pub fn new_filtered(bus: &Bus, types: &[MessageType]) -> Self {
+            skip_assert_initialized!();
+            Self::new_internal(bus, Some(types.to_vec()))
+        }
+
+        fn new_internal(bus: &Bus, types: Option<Vec<MessageType>>) -> Self {
+            let (sender, receiver) = mpsc::unbounded();
+
+            bus.set_sync_handler(move |_, msg| {
+                let matches = match types {
+                    Some(ref types) => types.contains(&msg.get_type()),
+                    None => true,
+                };
+
+                if matches {
+                    let _ = sender.unbounded_send(msg.clone());
+                    BusSyncReply::Drop
+                } else {
+                    BusSyncReply::Pass
+                }
             });
 
-            BusStream(bus.clone(), waker)
+            BusStream(bus.clone(), receiver)
         }
     }
 
@@ -181,22 +384,213 @@ mod futures {
 
     impl Stream for BusStream {
         type Item = Message;
-        type Error = ();
 
-        fn poll_next(&mut self, ctx: &mut Context) -> Poll<Option<Self::Item>, Self::Error> {
-            let BusStream(ref bus, ref waker) = *self;
+        fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Self::Item>> {
+            Pin::new(&mut self.1).poll_next(ctx)
+        }
+    }
 
-            let msg = bus.pop();
-            if let Some(msg) = msg {
-                Ok(Async::Ready(Some(msg)))
-            } else {
-                let mut waker = waker.lock().unwrap();
-                *waker = Some(ctx.waker().clone());
-                Ok(Async::Pending)
+    /// Future resolving to the first EOS or error message posted on a `Bus`.
+    ///
+    /// Created by `Bus::wait_for_eos_or_error_future()`.
+    pub struct BusEosOrErrorFuture(BusStream);
+
+    impl Future for BusEosOrErrorFuture {
+        type Output = Result<(), BusError>;
+
+        fn poll(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
+            match Pin::new(&mut self.0).poll_next(ctx) {
+                Poll::Ready(Some(msg)) => Poll::Ready(BusError::from_message(&msg)),
+                Poll::Ready(None) => Poll::Ready(Err(BusError::NoMessage)),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    impl Bus {
+        pub fn stream(&self) -> BusStream {
+            BusStream::new(self)
+        }
+
+        pub fn stream_filtered(&self, types: &[MessageType]) -> BusStream {
+            BusStream::new_filtered(self, types)
+        }
+
+        /// Asynchronous version of `Bus::wait_for_eos_or_error()`.
+        ///
+        /// Like `BusStream` this installs a sync handler on the bus until the
+        /// future is dropped.
+        pub fn wait_for_eos_or_error_future(&self) -> BusEosOrErrorFuture {
+            BusEosOrErrorFuture(self.stream_filtered(&[MessageType::Eos, MessageType::Error]))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use futures_executor::block_on;
+
+        struct Next<'a>(&'a mut BusStream);
+
+        impl<'a> Future for Next<'a> {
+            type Output = Option<Message>;
+
+            fn poll(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
+                Pin::new(&mut *self.0).poll_next(ctx)
+            }
+        }
+
+        #[test]
+        fn test_bus_stream() {
+            ::init().unwrap();
+
+            let bus = Bus::new();
+            let mut stream = BusStream::new(&bus);
+
+            bus.post(&::Message::new_eos().build()).unwrap();
+
+            let msg = block_on(Next(&mut stream)).unwrap();
+            match msg.view() {
+                ::MessageView::Eos(_) => (),
+                _ => unreachable!(),
+            }
+        }
+
+        #[test]
+        fn test_bus_stream_filtered() {
+            ::init().unwrap();
+
+            let bus = Bus::new();
+            let mut stream = bus.stream_filtered(&[MessageType::Eos]);
+
+            bus.post(&::Message::new_buffering(50).build()).unwrap();
+            bus.post(&::Message::new_eos().build()).unwrap();
+
+            let msg = block_on(Next(&mut stream)).unwrap();
+            assert_eq!(msg.get_type(), MessageType::Eos);
+
+            // Messages that are not streamed are still delivered
+            let msg = bus.pop().unwrap();
+            assert_eq!(msg.get_type(), MessageType::Buffering);
+            assert!(bus.pop().is_none());
+        }
+
+        #[test]
+        fn test_wait_for_eos_or_error_future() {
+            ::init().unwrap();
+
+            let bus = Bus::new();
+            let future = bus.wait_for_eos_or_error_future();
+
+            bus.post(&::Message::new_buffering(50).build()).unwrap();
+            bus.post(&::Message::new_error(::CoreError::Failed, "failed").build())
+                .unwrap();
+
+            match block_on(future) {
+                Err(BusError::Error { error, .. }) => {
+                    assert_eq!(error.to_string(), "failed");
+                }
+                _ => unreachable!(),
             }
         }
     }
 }
 
 #[cfg(any(feature = "futures", feature = "dox"))]
-pub use bus::futures::BusStream;
+pub use bus::futures::{BusEosOrErrorFuture, BusStream};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter_timed_filtered() {
+        ::init().unwrap();
+
+        let bus = Bus::new();
+        bus.post(&::Message::new_eos().build()).unwrap();
+        bus.post(&::Message::new_buffering(50).build()).unwrap();
+        bus.post(&::Message::new_eos().build()).unwrap();
+
+        let types: Vec<_> = bus
+            .iter_timed_filtered(ClockTime::from_seconds(0), &[MessageType::Buffering])
+            .map(|msg| msg.get_type())
+            .collect();
+        assert_eq!(types, vec![MessageType::Buffering]);
+
+        // All other messages were dropped
+        assert!(bus.pop().is_none());
+    }
+
+    #[test]
+    fn test_timed_pop_filtered_deadline() {
+        use std::thread;
+        use std::time;
+
+        ::init().unwrap();
+
+        let bus = Bus::new();
+
+        // EOS messages match the mask of the device-added messages but are
+        // skipped, which must not extend the timeout
+        let bus_clone = bus.clone();
+        let poster = thread::spawn(move || {
+            for _ in 0..200 {
+                bus_clone.post(&::Message::new_eos().build()).unwrap();
+                thread::sleep(time::Duration::from_millis(10));
+            }
+        });
+
+        let start = time::Instant::now();
+        assert!(bus
+            .timed_pop_filtered(ClockTime::from_mseconds(100), &[MessageType::DeviceAdded])
+            .is_none());
+        assert!(start.elapsed() < time::Duration::from_secs(1));
+
+        poster.join().unwrap();
+    }
+
+    #[test]
+    fn test_wait_for_eos_or_error() {
+        ::init().unwrap();
+
+        let bus = Bus::new();
+        assert!(
+            match bus.wait_for_eos_or_error(ClockTime::from_seconds(0)) {
+                Err(BusError::NoMessage) => true,
+                _ => false,
+            }
+        );
+
+        bus.post(&::Message::new_eos().build()).unwrap();
+        assert!(bus
+            .wait_for_eos_or_error(ClockTime::from_seconds(0))
+            .is_ok());
+
+        let bin = ::Bin::new("bin");
+        bus.post(
+            &::Message::new_error(::CoreError::Failed, "failed")
+                .src(Some(&bin))
+                .debug("some debug")
+                .build(),
+        )
+        .unwrap();
+
+        let err = bus
+            .wait_for_eos_or_error(ClockTime::from_seconds(0))
+            .unwrap_err();
+        match err {
+            BusError::Error {
+                ref src, ref debug, ..
+            } => {
+                assert_eq!(src.as_ref().map(String::as_str), Some("/GstBin:bin"));
+                assert_eq!(debug.as_ref().map(String::as_str), Some("some debug"));
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(
+            err.to_string(),
+            "Error from /GstBin:bin: failed (some debug)"
+        );
+    }
+}
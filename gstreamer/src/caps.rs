@@ -9,11 +9,20 @@
 use caps_features::*;
 use miniobject::*;
 use std::fmt;
+use std::ops;
 use std::ptr;
 use std::str;
 use structure::*;
 
+use Array;
+use Bitmask;
 use CapsIntersectMode;
+use DoubleRange;
+use FlagSet;
+use Fraction;
+use FractionRange;
+use IntRange;
+use List;
 
 use ffi;
 use glib;
@@ -31,6 +40,11 @@ impl Caps {
         Builder::new(name)
     }
 
+    pub fn builder_full() -> BuilderFull {
+        assert_initialized_main_thread!();
+        BuilderFull::new()
+    }
+
     pub fn new_empty() -> Self {
         assert_initialized_main_thread!();
         unsafe { from_glib_full(ffi::gst_caps_new_empty()) }
@@ -537,6 +551,167 @@ impl<'a> Builder<'a> {
     }
 }
 
+pub struct BuilderFull {
+    caps: Caps,
+}
+
+impl BuilderFull {
+    fn new() -> Self {
+        BuilderFull {
+            caps: Caps::new_empty(),
+        }
+    }
+
+    pub fn structure(self, structure: Structure) -> Self {
+        self.append_structure(structure, None)
+    }
+
+    pub fn structure_with_features(self, structure: Structure, features: CapsFeatures) -> Self {
+        self.append_structure(structure, Some(features))
+    }
+
+    pub fn structure_with_any_features(self, structure: Structure) -> Self {
+        self.append_structure(structure, Some(CapsFeatures::new_any()))
+    }
+
+    fn append_structure(mut self, structure: Structure, features: Option<CapsFeatures>) -> Self {
+        self.caps
+            .get_mut()
+            .unwrap()
+            .append_structure_full(structure, features);
+        self
+    }
+
+    pub fn build(self) -> Caps {
+        self.caps
+    }
+}
+
+// Only implemented for the value types that can be stored in caps fields,
+// so that `gst_caps!` rejects anything else at compile time
+pub trait ToCapsValue {
+    fn to_caps_value(&self) -> glib::SendValue;
+}
+
+macro_rules! impl_to_caps_value(
+    ($($t:ty),*) => {
+        $(
+            impl ToCapsValue for $t {
+                fn to_caps_value(&self) -> glib::SendValue {
+                    self.to_send_value()
+                }
+            }
+        )*
+    };
+);
+
+impl_to_caps_value!(
+    bool,
+    i32,
+    u32,
+    i64,
+    u64,
+    f64,
+    String,
+    Fraction,
+    IntRange<i32>,
+    IntRange<i64>,
+    FractionRange,
+    DoubleRange,
+    Bitmask,
+    FlagSet,
+    Structure
+);
+
+impl<'a> ToCapsValue for &'a str {
+    fn to_caps_value(&self) -> glib::SendValue {
+        self.to_send_value()
+    }
+}
+
+impl<'a> ToCapsValue for Array<'a> {
+    fn to_caps_value(&self) -> glib::SendValue {
+        self.to_send_value()
+    }
+}
+
+impl<'a> ToCapsValue for List<'a> {
+    fn to_caps_value(&self) -> glib::SendValue {
+        self.to_send_value()
+    }
+}
+
+macro_rules! impl_to_caps_value_range(
+    ($t:ty, $range:ty) => {
+        impl ToCapsValue for ops::RangeInclusive<$t> {
+            fn to_caps_value(&self) -> glib::SendValue {
+                <$range>::from(self.clone()).to_send_value()
+            }
+        }
+    };
+);
+
+impl_to_caps_value_range!(i32, IntRange<i32>);
+impl_to_caps_value_range!(i64, IntRange<i64>);
+impl_to_caps_value_range!(f64, DoubleRange);
+impl_to_caps_value_range!(Fraction, FractionRange);
+
+// Builds caps from one or more structures, each optionally followed by its
+// caps features in parentheses, e.g.
+//
+// gst_caps!(
+//     "video/x-raw" { format: ["I420", "NV12"], width: 320..=1920 },
+//     "video/x-raw"("memory:GLMemory") { format: "RGBA" }
+// )
+//
+// Field values in brackets become a `List`, inclusive ranges become the
+// corresponding range type. Use `Array::new()` for arrays.
+#[macro_export]
+macro_rules! gst_caps(
+    (@name $name:ident) => { stringify!($name) };
+    (@name $name:expr) => { $name };
+
+    (@fields $s:ident $(,)*) => {};
+    (@fields $s:ident, $field:tt : [$($value:expr),* $(,)*] $($rest:tt)*) => {
+        $s.set_value(
+            gst_caps!(@name $field),
+            $crate::caps::ToCapsValue::to_caps_value(&$crate::List::from_owned(vec![
+                $($crate::caps::ToCapsValue::to_caps_value(&$value)),*
+            ])),
+        );
+        gst_caps!(@fields $s $($rest)*);
+    };
+    (@fields $s:ident, $field:tt : $value:expr, $($rest:tt)*) => {
+        $s.set_value(
+            gst_caps!(@name $field),
+            $crate::caps::ToCapsValue::to_caps_value(&$value),
+        );
+        gst_caps!(@fields $s, $($rest)*);
+    };
+    (@fields $s:ident, $field:tt : $value:expr) => {
+        gst_caps!(@fields $s, $field: $value,);
+    };
+
+    ($($name:tt $(($($feature:expr),*))* { $($fields:tt)* }),+ $(,)*) => {{
+        let builder = $crate::Caps::builder_full();
+        $(
+            let builder = {
+                #[allow(unused_mut)]
+                let mut structure = $crate::Structure::new_empty($name);
+                gst_caps!(@fields structure, $($fields)*);
+
+                let features: &[&str] = &[$($($feature),*)*];
+                if features.is_empty() {
+                    builder.structure(structure)
+                } else {
+                    builder.structure_with_features(structure, $crate::CapsFeatures::new(features))
+                }
+            };
+        )+
+        builder.build()
+    }};
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -624,4 +799,49 @@ mod tests {
             .build();
         assert_eq!(caps.to_string(), "foo/bar(foo:bla, foo:baz), int=(int)12");
     }
+
+    #[test]
+    fn test_builder_full() {
+        ::init().unwrap();
+
+        let caps = Caps::builder_full()
+            .structure(Structure::builder("foo/bar").field("int", &12).build())
+            .structure_with_features(
+                Structure::builder("foo/bar").field("int", &13).build(),
+                CapsFeatures::new(&["foo:bla"]),
+            )
+            .structure_with_any_features(Structure::new_empty("foo/baz"))
+            .build();
+        assert_eq!(
+            caps.to_string(),
+            "foo/bar, int=(int)12; foo/bar(foo:bla), int=(int)13; foo/baz(ANY)"
+        );
+    }
+
+    #[test]
+    fn test_macro() {
+        ::init().unwrap();
+
+        let caps = gst_caps!(
+            "video/x-raw" {
+                format: ["I420", "NV12"],
+                width: 320..=1920,
+                "pixel-aspect-ratio": Fraction::new(1, 1),
+                framerate: Fraction::new(0, 1)..=Fraction::new(30, 1),
+            },
+            "video/x-raw"("memory:GLMemory") { format: "RGBA", interlaced: false }
+        );
+        assert_eq!(
+            caps,
+            Caps::from_string(
+                "video/x-raw, format=(string){ I420, NV12 }, width=(int)[ 320, 1920 ], \
+                 pixel-aspect-ratio=(fraction)1/1, framerate=(fraction)[ 0/1, 30/1 ]; \
+                 video/x-raw(memory:GLMemory), format=(string)RGBA, interlaced=(boolean)false"
+            )
+            .unwrap()
+        );
+
+        let caps = gst_caps!("foo/bar" {});
+        assert_eq!(caps.to_string(), "foo/bar");
+    }
 }
@@ -7,6 +7,8 @@
 // except according to those terms.
 
 use ffi;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use futures_channel::oneshot;
 use glib;
 use glib::translate::*;
 use glib::IsA;
@@ -14,10 +16,20 @@ use glib_ffi;
 use glib_ffi::{gboolean, gpointer};
 use libc::c_void;
 use std::cmp;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::future::Future;
 use std::mem;
 use std::mem::transmute;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::pin::Pin;
 use std::ptr;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::sync::Mutex;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::task::{Context, Poll};
 use Clock;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use ClockExt;
 use ClockReturn;
 use ClockTime;
 use ClockTimeDiff;
@@ -92,6 +104,64 @@ impl ClockId {
     }
 }
 
+#[cfg(any(feature = "futures", feature = "dox"))]
+impl ClockId {
+    /// Asynchronously waits for the clock id and returns a future that
+    /// resolves to the result of the wait and the jitter once it is over.
+    ///
+    /// For periodic clock ids only the first timeout is reported.
+    pub fn wait_async_future(&self) -> ClockIdWaitFuture {
+        let (sender, receiver) = oneshot::channel();
+        let sender = Mutex::new(Some(sender));
+
+        let res = self.wait_async(move |clock, time, _| {
+            if let Some(sender) = sender.lock().unwrap().take() {
+                let res = match time.0 {
+                    Some(time) => {
+                        let now = clock.get_time().0.unwrap_or(time);
+                        (
+                            ClockReturn::Ok,
+                            now as ClockTimeDiff - time as ClockTimeDiff,
+                        )
+                    }
+                    None => (ClockReturn::Unscheduled, 0),
+                };
+                let _ = sender.send(res);
+            }
+
+            true
+        });
+
+        if res == ClockReturn::Ok {
+            ClockIdWaitFuture(Ok(receiver))
+        } else {
+            ClockIdWaitFuture(Err(res))
+        }
+    }
+}
+
+/// Future returned by `ClockId::wait_async_future()`.
+#[cfg(any(feature = "futures", feature = "dox"))]
+#[derive(Debug)]
+pub struct ClockIdWaitFuture(Result<oneshot::Receiver<(ClockReturn, ClockTimeDiff)>, ClockReturn>);
+
+#[cfg(any(feature = "futures", feature = "dox"))]
+impl Future for ClockIdWaitFuture {
+    type Output = (ClockReturn, ClockTimeDiff);
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
+        match self.0 {
+            Ok(ref mut receiver) => match Pin::new(receiver).poll(ctx) {
+                Poll::Ready(Ok(res)) => Poll::Ready(res),
+                // The callback was dropped without being called
+                Poll::Ready(Err(_)) => Poll::Ready((ClockReturn::Unscheduled, 0)),
+                Poll::Pending => Poll::Pending,
+            },
+            Err(res) => Poll::Ready((res, 0)),
+        }
+    }
+}
+
 impl PartialOrd for ClockId {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         Some(self.cmp(other))
@@ -274,4 +344,20 @@ mod tests {
 
         assert_eq!(receiver.recv(), Ok(()));
     }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_wait_async_future() {
+        use futures_executor::block_on;
+
+        ::init().unwrap();
+
+        let clock = SystemClock::obtain();
+        let now = clock.get_time();
+        let id = clock.new_single_shot_id(now + 20 * ::MSECOND).unwrap();
+
+        let (res, jitter) = block_on(id.wait_async_future());
+        assert_eq!(res, ClockReturn::Ok);
+        assert!(jitter >= 0);
+    }
 }
@@ -0,0 +1,46 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ffi;
+use glib;
+use glib::translate::*;
+use glib::IsA;
+
+use ClockTime;
+use ControlSource;
+
+pub trait ControlSourceExtManual {
+    fn control_source_get_value_array(
+        &self,
+        timestamp: ClockTime,
+        interval: ClockTime,
+        values: &mut [f64],
+    ) -> Result<(), glib::BoolError>;
+}
+
+impl<O: IsA<ControlSource>> ControlSourceExtManual for O {
+    fn control_source_get_value_array(
+        &self,
+        timestamp: ClockTime,
+        interval: ClockTime,
+        values: &mut [f64],
+    ) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::BoolError::from_glib(
+                ffi::gst_control_source_get_value_array(
+                    self.to_glib_none().0,
+                    timestamp.to_glib(),
+                    interval.to_glib(),
+                    values.len() as u32,
+                    values.as_mut_ptr(),
+                ),
+                "Failed to get control source values",
+            )
+        }
+    }
+}
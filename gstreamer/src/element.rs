@@ -16,19 +16,48 @@ use glib::translate::FromGlibPtrBorrow;
 use glib::translate::{
     from_glib, from_glib_full, from_glib_none, FromGlib, FromGlibPtrContainer, ToGlib, ToGlibPtr,
 };
-use glib::IsA;
+use glib::{Cast, IsA, ObjectExt};
 use miniobject::MiniObject;
+use Bus;
+use BusError;
+use ClockTime;
+use ElementExt;
 use Event;
 use Format;
 use FormattedValue;
 use GenericFormattedValue;
+use GstObjectExt;
+use Message;
+use MessageType;
+use MessageView;
 use Pad;
 use PadTemplate;
 use QueryRef;
 use SpecificFormattedValue;
-
+use State;
+use StateChangeError;
+use StateChangeReturn;
+use StateChangeSuccess;
+
+use bus;
+
+#[cfg(any(feature = "futures", feature = "dox"))]
+use futures_channel::mpsc as futures_mpsc;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use futures_core::stream::Stream;
+use std::error::Error;
 use std::ffi::CStr;
+use std::fmt;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::future::Future;
 use std::mem;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::sync::Mutex;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::task::{Context, Poll};
+use std::time::Instant;
 
 use libc;
 
@@ -189,6 +218,31 @@ pub trait ElementExtManual {
     fn call_async<F>(&self, func: F)
     where
         F: FnOnce(&Self) + Send + 'static;
+
+    /// Changes the state of the element and waits up to `timeout` for an
+    /// asynchronous state change to complete.
+    ///
+    /// Completion is detected from the async-done and error messages on the
+    /// bus of the element, so this is meant for top-level elements like
+    /// pipelines. Error messages posted by the element or its children are
+    /// reported as part of the returned error. The messages are observed via
+    /// the sync-message signal of the bus and stay on the bus for the
+    /// application, but messages dropped by a sync handler are not seen. If
+    /// the state change did not complete within `timeout`,
+    /// `StateChangeSuccess::Async` is returned.
+    fn set_state_and_wait(
+        &self,
+        state: State,
+        timeout: ClockTime,
+    ) -> Result<StateChangeSuccess, StateChangeWaitError>;
+
+    /// Asynchronous version of `set_state_and_wait()` without a timeout.
+    ///
+    /// The state change itself is started immediately. The bus of the
+    /// element is observed the same way as by `set_state_and_wait()` until
+    /// the future is dropped.
+    #[cfg(any(feature = "futures", feature = "dox"))]
+    fn set_state_and_wait_future(&self, state: State) -> StateChangeFuture;
 }
 
 impl<O: IsA<Element>> ElementExtManual for O {
@@ -612,6 +666,240 @@ impl<O: IsA<Element>> ElementExtManual for O {
             );
         }
     }
+
+    fn set_state_and_wait(
+        &self,
+        state: State,
+        timeout: ClockTime,
+    ) -> Result<StateChangeSuccess, StateChangeWaitError> {
+        let element = self.upcast_ref::<Element>();
+
+        let bus = match element.get_bus() {
+            Some(bus) => bus,
+            None => {
+                let ret = match self.set_state(state) {
+                    StateChangeReturn::Async => self.get_state(timeout).0,
+                    ret => ret,
+                };
+                return ret.into_result().map_err(StateChangeWaitError::from);
+            }
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        let _watch = StateChangeWatch::new(bus, move |msg| {
+            let _ = sender.lock().unwrap().send(msg);
+        });
+
+        // Error messages explaining a failure were already posted, only
+        // asynchronous state changes have to be waited for
+        let ret = self.set_state(state);
+        let deadline = match ret {
+            StateChangeReturn::Failure => Some(Instant::now()),
+            StateChangeReturn::Async => bus::deadline_from_timeout(timeout),
+            _ => return ret.into_result().map_err(StateChangeWaitError::from),
+        };
+
+        loop {
+            let msg = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now < deadline {
+                        receiver.recv_timeout(deadline - now).ok()
+                    } else {
+                        receiver.try_recv().ok()
+                    }
+                }
+                None => receiver.recv().ok(),
+            };
+
+            let msg = match msg {
+                Some(msg) => msg,
+                None => break,
+            };
+
+            if ret == StateChangeReturn::Failure && msg.get_type() != MessageType::Error {
+                continue;
+            }
+
+            if let Some(res) = state_change_result(element, &msg) {
+                return res;
+            }
+        }
+
+        ret.into_result().map_err(StateChangeWaitError::from)
+    }
+
+    #[cfg(any(feature = "futures", feature = "dox"))]
+    fn set_state_and_wait_future(&self, state: State) -> StateChangeFuture {
+        let element = self.upcast_ref::<Element>().clone();
+
+        // Messages are collected from before the state change so that none
+        // of them is missed
+        let messages = element.get_bus().map(|bus| {
+            let (sender, receiver) = futures_mpsc::unbounded();
+            let watch = StateChangeWatch::new(bus, move |msg| {
+                let _ = sender.unbounded_send(msg);
+            });
+            (watch, receiver)
+        });
+        let ret = self.set_state(state);
+
+        StateChangeFuture {
+            element,
+            messages,
+            ret,
+        }
+    }
+}
+
+// Passes the async-done and error messages posted on `bus` to a function for
+// as long as it is alive. The sync-message signal is used for this instead of
+// a sync handler, so that the messages stay on the bus and a sync handler of
+// the application keeps working.
+struct StateChangeWatch {
+    bus: Bus,
+    handler_id: Option<glib::SignalHandlerId>,
+}
+
+impl StateChangeWatch {
+    fn new<F: Fn(Message) + Send + Sync + 'static>(bus: Bus, func: F) -> Self {
+        bus.enable_sync_message_emission();
+        let handler_id = bus.connect_sync_message(move |_, msg| match msg.get_type() {
+            MessageType::AsyncDone | MessageType::Error => func(msg.clone()),
+            _ => (),
+        });
+
+        StateChangeWatch {
+            bus,
+            handler_id: Some(handler_id),
+        }
+    }
+}
+
+impl Drop for StateChangeWatch {
+    fn drop(&mut self) {
+        if let Some(handler_id) = self.handler_id.take() {
+            self.bus.disconnect(handler_id);
+        }
+        self.bus.disable_sync_message_emission();
+    }
+}
+
+// Returns the result of the state change of `element` if `msg` finishes it
+fn state_change_result(
+    element: &Element,
+    msg: &::MessageRef,
+) -> Option<Result<StateChangeSuccess, StateChangeWaitError>> {
+    match msg.view() {
+        MessageView::Error(_) => match msg.get_src() {
+            Some(ref src) if src.has_as_ancestor(element) => {
+                BusError::from_message(msg).err().map(|err| Err(err.into()))
+            }
+            _ => None,
+        },
+        MessageView::AsyncDone(_) => {
+            // The message might belong to an earlier state change or to
+            // another element, so check if the state change is still pending
+            match element.get_state(ClockTime::from_seconds(0)).0 {
+                StateChangeReturn::Async => None,
+                ret => Some(ret.into_result().map_err(StateChangeWaitError::from)),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Error returned by `ElementExtManual::set_state_and_wait()`.
+#[derive(Clone, Debug)]
+pub enum StateChangeWaitError {
+    Failure,
+    Error(BusError),
+}
+
+impl From<StateChangeError> for StateChangeWaitError {
+    fn from(_: StateChangeError) -> Self {
+        skip_assert_initialized!();
+        StateChangeWaitError::Failure
+    }
+}
+
+impl From<BusError> for StateChangeWaitError {
+    fn from(err: BusError) -> Self {
+        skip_assert_initialized!();
+        StateChangeWaitError::Error(err)
+    }
+}
+
+impl fmt::Display for StateChangeWaitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StateChangeWaitError::Failure => write!(f, "{}", StateChangeError),
+            StateChangeWaitError::Error(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for StateChangeWaitError {
+    fn description(&self) -> &str {
+        match *self {
+            StateChangeWaitError::Failure => StateChangeError.description(),
+            StateChangeWaitError::Error(ref err) => err.description(),
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            StateChangeWaitError::Failure => None,
+            StateChangeWaitError::Error(ref err) => Some(err),
+        }
+    }
+}
+
+/// Future returned by `ElementExtManual::set_state_and_wait_future()`.
+#[cfg(any(feature = "futures", feature = "dox"))]
+pub struct StateChangeFuture {
+    element: Element,
+    messages: Option<(StateChangeWatch, futures_mpsc::UnboundedReceiver<Message>)>,
+    ret: StateChangeReturn,
+}
+
+#[cfg(any(feature = "futures", feature = "dox"))]
+impl Future for StateChangeFuture {
+    type Output = Result<StateChangeSuccess, StateChangeWaitError>;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        if this.ret == StateChangeReturn::Async || this.ret == StateChangeReturn::Failure {
+            if let Some((_, ref mut receiver)) = this.messages {
+                loop {
+                    match Pin::new(&mut *receiver).poll_next(ctx) {
+                        Poll::Ready(Some(msg)) => {
+                            if this.ret == StateChangeReturn::Failure
+                                && msg.get_type() != MessageType::Error
+                            {
+                                continue;
+                            }
+
+                            if let Some(res) = state_change_result(&this.element, &msg) {
+                                return Poll::Ready(res);
+                            }
+                        }
+                        Poll::Ready(None) => break,
+                        // Error messages explaining a failure were already
+                        // posted, only asynchronous state changes are waited for
+                        Poll::Pending if this.ret == StateChangeReturn::Async => {
+                            return Poll::Pending;
+                        }
+                        Poll::Pending => break,
+                    }
+                }
+            }
+        }
+
+        Poll::Ready(this.ret.into_result().map_err(StateChangeWaitError::from))
+    }
 }
 
 lazy_static! {
@@ -1164,4 +1452,109 @@ mod tests {
 
         assert_eq!(receiver.recv(), Ok(()));
     }
+
+    #[test]
+    fn test_set_state_and_wait() {
+        ::init().unwrap();
+
+        let pipeline = ::Pipeline::new(None);
+        let src = ::ElementFactory::make("fakesrc", None).unwrap();
+        let sink = ::ElementFactory::make("fakesink", None).unwrap();
+        pipeline.add_many(&[&src, &sink]).unwrap();
+        src.link(&sink).unwrap();
+
+        assert_eq!(
+            pipeline
+                .set_state_and_wait(::State::Paused, ClockTime::none())
+                .unwrap(),
+            StateChangeSuccess::Success
+        );
+        assert_eq!(
+            pipeline.get_state(ClockTime::from_seconds(0)).1,
+            ::State::Paused
+        );
+
+        // All messages are left on the bus for the application
+        let bus = pipeline.get_bus().unwrap();
+        assert!(bus.pop_filtered(&[::MessageType::StateChanged]).is_some());
+        assert!(bus.pop_filtered(&[::MessageType::AsyncDone]).is_some());
+
+        assert_eq!(
+            pipeline
+                .set_state_and_wait(::State::Null, ClockTime::none())
+                .unwrap(),
+            StateChangeSuccess::Success
+        );
+    }
+
+    #[test]
+    fn test_set_state_and_wait_error() {
+        ::init().unwrap();
+
+        let pipeline = ::Pipeline::new(None);
+        let src = ::ElementFactory::make("filesrc", "src").unwrap();
+        src.set_property("location", &"/does/not/exist").unwrap();
+        let sink = ::ElementFactory::make("fakesink", None).unwrap();
+        pipeline.add_many(&[&src, &sink]).unwrap();
+        src.link(&sink).unwrap();
+
+        // Errors are also reported if the application has its own sync handler
+        pipeline
+            .get_bus()
+            .unwrap()
+            .set_sync_handler(|_, _| ::BusSyncReply::Pass);
+
+        match pipeline.set_state_and_wait(::State::Paused, ClockTime::none()) {
+            Err(StateChangeWaitError::Error(::BusError::Error {
+                src: Some(ref path),
+                ref error,
+                ..
+            })) => {
+                assert_eq!(*path, src.get_path_string());
+                assert!(error.kind::<::ResourceError>().is_some());
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+
+        pipeline.set_state(::State::Null).into_result().unwrap();
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_set_state_and_wait_future() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        ::init().unwrap();
+
+        let pipeline = ::Pipeline::new(None);
+        let src = ::ElementFactory::make("fakesrc", None).unwrap();
+        let sink = ::ElementFactory::make("fakesink", None).unwrap();
+        pipeline.add_many(&[&src, &sink]).unwrap();
+        src.link(&sink).unwrap();
+
+        // The sync handler of the application is neither replaced nor
+        // removed, and it still gets to see all messages
+        let n_messages = Arc::new(AtomicUsize::new(0));
+        let n_messages_clone = n_messages.clone();
+        let bus = pipeline.get_bus().unwrap();
+        bus.set_sync_handler(move |_, _| {
+            n_messages_clone.fetch_add(1, Ordering::SeqCst);
+            ::BusSyncReply::Pass
+        });
+
+        let future = pipeline.set_state_and_wait_future(::State::Paused);
+        assert_eq!(
+            ::futures_executor::block_on(future).unwrap(),
+            StateChangeSuccess::Success
+        );
+        assert!(n_messages.load(Ordering::SeqCst) > 0);
+        assert!(bus.pop_filtered(&[::MessageType::AsyncDone]).is_some());
+
+        let n = n_messages.load(Ordering::SeqCst);
+        bus.post(&::Message::new_eos().build()).unwrap();
+        assert_eq!(n_messages.load(Ordering::SeqCst), n + 1);
+
+        pipeline.set_state(::State::Null).into_result().unwrap();
+    }
 }
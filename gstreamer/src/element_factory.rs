@@ -0,0 +1,371 @@
+// Copyright (C) 2019 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use glib;
+use glib::translate::{from_glib, ToGlib};
+use glib::value::ToSendValue;
+use glib::{Cast, ObjectExt};
+use gobject_ffi;
+
+use std::error::Error;
+use std::fmt;
+
+use Element;
+use ElementFactory;
+use PluginFeatureExt;
+use PluginFlags;
+use Rank;
+use Registry;
+
+impl ElementFactory {
+    pub fn builder(factory_name: &str) -> ElementBuilder {
+        assert_initialized_main_thread!();
+        ElementBuilder::new(factory_name)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ElementFactoryError {
+    NoSuchFactory {
+        factory: String,
+    },
+    /// The plugin providing the factory could not be loaded.
+    LoadFailed {
+        factory: String,
+    },
+    /// The plugin providing the factory is blacklisted because it failed to
+    /// load before.
+    Blacklisted {
+        factory: String,
+    },
+    RankTooLow {
+        factory: String,
+        rank: Rank,
+        min_rank: Rank,
+    },
+    CreateFailed {
+        factory: String,
+    },
+    NoSuchProperty {
+        factory: String,
+        property: String,
+    },
+    PropertyTypeMismatch {
+        factory: String,
+        property: String,
+        expected: glib::Type,
+        actual: glib::Type,
+    },
+    CouldNotSetProperty {
+        factory: String,
+        property: String,
+    },
+}
+
+impl fmt::Display for ElementFactoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ElementFactoryError::NoSuchFactory { ref factory }
+            | ElementFactoryError::LoadFailed { ref factory }
+            | ElementFactoryError::Blacklisted { ref factory }
+            | ElementFactoryError::CreateFailed { ref factory } => {
+                write!(f, "{}: {}", self.description(), factory)
+            }
+            ElementFactoryError::RankTooLow {
+                ref factory,
+                rank,
+                min_rank,
+            } => write!(
+                f,
+                "{}: {} has rank {}, required {}",
+                self.description(),
+                factory,
+                rank_to_string(rank),
+                rank_to_string(min_rank)
+            ),
+            ElementFactoryError::NoSuchProperty {
+                ref factory,
+                ref property,
+            }
+            | ElementFactoryError::CouldNotSetProperty {
+                ref factory,
+                ref property,
+            } => write!(f, "{}: {}::{}", self.description(), factory, property),
+            ElementFactoryError::PropertyTypeMismatch {
+                ref factory,
+                ref property,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: {}::{} expects {}, got {}",
+                self.description(),
+                factory,
+                property,
+                expected,
+                actual
+            ),
+        }
+    }
+}
+
+impl Error for ElementFactoryError {
+    fn description(&self) -> &str {
+        match *self {
+            ElementFactoryError::NoSuchFactory { .. } => "No such element factory",
+            ElementFactoryError::LoadFailed { .. } => "Failed to load element factory",
+            ElementFactoryError::Blacklisted { .. } => "Element factory plugin is blacklisted",
+            ElementFactoryError::RankTooLow { .. } => "Element factory rank too low",
+            ElementFactoryError::CreateFailed { .. } => "Failed to create element",
+            ElementFactoryError::NoSuchProperty { .. } => "No such property",
+            ElementFactoryError::PropertyTypeMismatch { .. } => "Property type mismatch",
+            ElementFactoryError::CouldNotSetProperty { .. } => "Could not set property",
+        }
+    }
+}
+
+// Unknown ranks don't have a name and are printed as their numeric value
+fn rank_to_string(rank: Rank) -> String {
+    match rank {
+        Rank::__Unknown(rank) => rank.to_string(),
+        rank => format!("{:?}", rank),
+    }
+}
+
+fn load_error(factory: &ElementFactory, factory_name: &str) -> ElementFactoryError {
+    let blacklisted = factory
+        .get_plugin_name()
+        .and_then(|plugin_name| Registry::get().find_plugin(&plugin_name))
+        .map_or(false, |plugin| {
+            plugin.get_plugin_flags().contains(PluginFlags::BLACKLISTED)
+        });
+
+    if blacklisted {
+        ElementFactoryError::Blacklisted {
+            factory: factory_name.into(),
+        }
+    } else {
+        ElementFactoryError::LoadFailed {
+            factory: factory_name.into(),
+        }
+    }
+}
+
+pub struct ElementBuilder<'a> {
+    factory_name: &'a str,
+    name: Option<&'a str>,
+    min_rank: Option<Rank>,
+    properties: Vec<(&'a str, glib::SendValue)>,
+}
+
+impl<'a> ElementBuilder<'a> {
+    fn new(factory_name: &'a str) -> Self {
+        ElementBuilder {
+            factory_name,
+            name: None,
+            min_rank: None,
+            properties: Vec::new(),
+        }
+    }
+
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn min_rank(mut self, min_rank: Rank) -> Self {
+        self.min_rank = Some(min_rank);
+        self
+    }
+
+    pub fn property<V: ToSendValue>(mut self, name: &'a str, value: V) -> Self {
+        self.properties.push((name, value.to_send_value()));
+        self
+    }
+
+    pub fn build(self) -> Result<Element, ElementFactoryError> {
+        let factory_name = self.factory_name;
+
+        let factory = ElementFactory::find(factory_name).ok_or_else(|| {
+            ElementFactoryError::NoSuchFactory {
+                factory: factory_name.into(),
+            }
+        })?;
+
+        if let Some(min_rank) = self.min_rank {
+            let rank: Rank = from_glib(factory.get_rank() as i32);
+            if rank < min_rank {
+                return Err(ElementFactoryError::RankTooLow {
+                    factory: factory_name.into(),
+                    rank,
+                    min_rank,
+                });
+            }
+        }
+
+        let factory = factory
+            .load()
+            .and_then(|feature| feature.downcast::<ElementFactory>().ok())
+            .ok_or_else(|| load_error(&factory, factory_name))?;
+
+        let element =
+            factory
+                .create(self.name)
+                .ok_or_else(|| ElementFactoryError::CreateFailed {
+                    factory: factory_name.into(),
+                })?;
+
+        for (name, value) in self.properties {
+            let pspec =
+                element
+                    .find_property(name)
+                    .ok_or_else(|| ElementFactoryError::NoSuchProperty {
+                        factory: factory_name.into(),
+                        property: name.into(),
+                    })?;
+
+            let expected = pspec.get_value_type();
+            let actual = value.type_();
+            let compatible: bool = unsafe {
+                from_glib(gobject_ffi::g_type_is_a(
+                    actual.to_glib(),
+                    expected.to_glib(),
+                ))
+            };
+            if !compatible {
+                return Err(ElementFactoryError::PropertyTypeMismatch {
+                    factory: factory_name.into(),
+                    property: name.into(),
+                    expected,
+                    actual,
+                });
+            }
+
+            element.set_property(name, &*value).map_err(|_| {
+                ElementFactoryError::CouldNotSetProperty {
+                    factory: factory_name.into(),
+                    property: name.into(),
+                }
+            })?;
+        }
+
+        Ok(element)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use GstObjectExt;
+
+    #[test]
+    fn test_builder() {
+        ::init().unwrap();
+
+        let element = ElementFactory::builder("fakesrc")
+            .name("src")
+            .property("num-buffers", 10i32)
+            .property("is-live", true)
+            .build()
+            .unwrap();
+        assert_eq!(element.get_name(), "src");
+        assert_eq!(
+            element.get_property("num-buffers").unwrap().get::<i32>(),
+            Some(10)
+        );
+        assert_eq!(
+            element.get_property("is-live").unwrap().get::<bool>(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        ::init().unwrap();
+
+        assert_eq!(
+            ElementFactory::builder("does-not-exist").build(),
+            Err(ElementFactoryError::NoSuchFactory {
+                factory: "does-not-exist".into()
+            })
+        );
+
+        assert_eq!(
+            ElementFactory::builder("fakesrc")
+                .min_rank(Rank::Primary)
+                .build(),
+            Err(ElementFactoryError::RankTooLow {
+                factory: "fakesrc".into(),
+                rank: Rank::None,
+                min_rank: Rank::Primary,
+            })
+        );
+
+        assert_eq!(
+            ElementFactory::builder("fakesrc")
+                .property("does-not-exist", 1i32)
+                .build(),
+            Err(ElementFactoryError::NoSuchProperty {
+                factory: "fakesrc".into(),
+                property: "does-not-exist".into(),
+            })
+        );
+
+        assert_eq!(
+            ElementFactory::builder("fakesrc")
+                .property("num-buffers", 10u32)
+                .build(),
+            Err(ElementFactoryError::PropertyTypeMismatch {
+                factory: "fakesrc".into(),
+                property: "num-buffers".into(),
+                expected: glib::Type::I32,
+                actual: glib::Type::U32,
+            })
+        );
+
+        assert_eq!(
+            ElementFactory::builder("queue")
+                .property("current-level-buffers", 1u32)
+                .build(),
+            Err(ElementFactoryError::CouldNotSetProperty {
+                factory: "queue".into(),
+                property: "current-level-buffers".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_load_error() {
+        ::init().unwrap();
+
+        let factory = ElementFactory::find("fakesrc").unwrap();
+        let err = load_error(&factory, "fakesrc");
+        assert_eq!(
+            err,
+            ElementFactoryError::LoadFailed {
+                factory: "fakesrc".into()
+            }
+        );
+        assert_eq!(err.to_string(), "Failed to load element factory: fakesrc");
+    }
+
+    #[test]
+    fn test_rank_too_low_display() {
+        ::init().unwrap();
+
+        let err = ElementFactoryError::RankTooLow {
+            factory: "fakesrc".into(),
+            rank: Rank::__Unknown(42),
+            min_rank: Rank::Primary,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Element factory rank too low: fakesrc has rank 42, required Primary"
+        );
+    }
+}
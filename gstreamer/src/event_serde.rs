@@ -0,0 +1,416 @@
+// Copyright (C) 2019 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ffi;
+use glib::translate::{from_glib, from_glib_full, ToGlib};
+
+use serde::de;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use std::ptr;
+
+use miniobject::MiniObject;
+use Caps;
+use ClockTime;
+use Event;
+use EventRef;
+use EventType;
+use EventView;
+use GenericFormattedValue;
+use GroupId;
+use SeekFlags;
+use SeekType;
+use Segment;
+use Seqnum;
+use StreamFlags;
+use Structure;
+use TagList;
+
+// Events with commonly used fields that can't be represented by the
+// `Structure` serialization get their own variant, all others are
+// serialized with their `Structure`
+#[derive(Serialize, Deserialize)]
+enum EventFields {
+    FlushStop {
+        reset_time: bool,
+    },
+    StreamStart {
+        stream_id: String,
+        flags: StreamFlags,
+        group_id: Option<u32>,
+    },
+    Caps(Caps),
+    Segment(Segment),
+    Tag(TagList),
+    Gap {
+        timestamp: ClockTime,
+        duration: ClockTime,
+    },
+    Seek {
+        rate: f64,
+        flags: SeekFlags,
+        start_type: SeekType,
+        start: GenericFormattedValue,
+        stop_type: SeekType,
+        stop: GenericFormattedValue,
+    },
+    Structure(Option<Structure>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Event")]
+struct EventSerde {
+    type_: EventType,
+    seqnum: u32,
+    running_time_offset: i64,
+    fields: EventFields,
+}
+
+fn get_group_id(event: &EventRef) -> Option<u32> {
+    unsafe {
+        let mut group_id = 0;
+        if from_glib(ffi::gst_event_parse_group_id(
+            event.as_mut_ptr(),
+            &mut group_id,
+        )) {
+            Some(group_id)
+        } else {
+            None
+        }
+    }
+}
+
+impl Serialize for EventRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields = match self.view() {
+            EventView::FlushStop(ref flush_stop) => EventFields::FlushStop {
+                reset_time: flush_stop.get_reset_time(),
+            },
+            EventView::StreamStart(ref stream_start) => EventFields::StreamStart {
+                stream_id: stream_start.get_stream_id().to_owned(),
+                flags: stream_start.get_stream_flags(),
+                group_id: get_group_id(self),
+            },
+            EventView::Caps(ref caps) => EventFields::Caps(caps.get_caps().to_owned()),
+            EventView::Segment(ref segment) => EventFields::Segment(segment.get_segment().clone()),
+            EventView::Tag(ref tag) => EventFields::Tag(tag.get_tag().to_owned()),
+            EventView::Gap(ref gap) => {
+                let (timestamp, duration) = gap.get();
+                EventFields::Gap {
+                    timestamp,
+                    duration,
+                }
+            }
+            EventView::Seek(ref seek) => {
+                let (rate, flags, start_type, start, stop_type, stop) = seek.get();
+                EventFields::Seek {
+                    rate,
+                    flags,
+                    start_type,
+                    start,
+                    stop_type,
+                    stop,
+                }
+            }
+            _ => EventFields::Structure(self.get_structure().map(|s| s.to_owned())),
+        };
+
+        EventSerde {
+            type_: self.get_type(),
+            seqnum: self.get_seqnum().0,
+            running_time_offset: self.get_running_time_offset(),
+            fields,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl EventSerde {
+    fn into_event(self) -> Result<Event, &'static str> {
+        let seqnum = Seqnum(self.seqnum);
+        let running_time_offset = self.running_time_offset;
+
+        let type_ = match self.fields {
+            EventFields::FlushStop { .. } => Some(EventType::FlushStop),
+            EventFields::StreamStart { .. } => Some(EventType::StreamStart),
+            EventFields::Caps(_) => Some(EventType::Caps),
+            EventFields::Segment(_) => Some(EventType::Segment),
+            EventFields::Tag(_) => Some(EventType::Tag),
+            EventFields::Gap { .. } => Some(EventType::Gap),
+            EventFields::Seek { .. } => Some(EventType::Seek),
+            EventFields::Structure(_) => None,
+        };
+        if let Some(type_) = type_ {
+            if type_ != self.type_ {
+                return Err("event type does not match the event fields");
+            }
+        }
+
+        let event = match self.fields {
+            EventFields::FlushStop { reset_time } => Event::new_flush_stop(reset_time)
+                .seqnum(seqnum)
+                .running_time_offset(running_time_offset)
+                .build(),
+            EventFields::StreamStart {
+                ref stream_id,
+                flags,
+                group_id,
+            } => {
+                let builder = Event::new_stream_start(stream_id).flags(flags);
+                let builder = match group_id {
+                    Some(group_id) => builder.group_id(GroupId(group_id)),
+                    None => builder,
+                };
+
+                builder
+                    .seqnum(seqnum)
+                    .running_time_offset(running_time_offset)
+                    .build()
+            }
+            EventFields::Caps(ref caps) => Event::new_caps(caps)
+                .seqnum(seqnum)
+                .running_time_offset(running_time_offset)
+                .build(),
+            EventFields::Segment(ref segment) => Event::new_segment(segment)
+                .seqnum(seqnum)
+                .running_time_offset(running_time_offset)
+                .build(),
+            EventFields::Tag(tags) => Event::new_tag(tags)
+                .seqnum(seqnum)
+                .running_time_offset(running_time_offset)
+                .build(),
+            EventFields::Gap {
+                timestamp,
+                duration,
+            } => {
+                if timestamp == ClockTime::none() {
+                    return Err("gap timestamp is invalid");
+                }
+
+                Event::new_gap(timestamp, duration)
+                    .seqnum(seqnum)
+                    .running_time_offset(running_time_offset)
+                    .build()
+            }
+            EventFields::Seek {
+                rate,
+                flags,
+                start_type,
+                start,
+                stop_type,
+                stop,
+            } => {
+                if start.get_format() != stop.get_format() {
+                    return Err("seek start and stop have different formats");
+                }
+
+                Event::new_seek(rate, flags, start_type, start, stop_type, stop)
+                    .seqnum(seqnum)
+                    .running_time_offset(running_time_offset)
+                    .build()
+            }
+            EventFields::Structure(structure) => unsafe {
+                let event = ffi::gst_event_new_custom(
+                    self.type_.to_glib(),
+                    structure
+                        .map(|structure| structure.into_ptr())
+                        .unwrap_or_else(ptr::null_mut),
+                );
+                ffi::gst_event_set_seqnum(event, seqnum.to_glib());
+                ffi::gst_event_set_running_time_offset(event, running_time_offset);
+
+                from_glib_full(event)
+            },
+        };
+
+        Ok(event)
+    }
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        EventSerde::deserialize(deserializer)
+            .and_then(|event_de| event_de.into_event().map_err(de::Error::custom))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate ron;
+    extern crate serde_json;
+
+    use Caps;
+    use ClockTime;
+    use Event;
+    use EventType;
+    use EventView;
+    use Format;
+    use GenericFormattedValue;
+    use SeekFlags;
+    use SeekType;
+    use Seqnum;
+    use Structure;
+
+    fn roundtrip(event: &Event) -> Event {
+        let res = ron::ser::to_string(event).unwrap();
+        ron::de::from_str(&res).unwrap()
+    }
+
+    #[test]
+    fn test_serialize() {
+        ::init().unwrap();
+
+        let event = Event::new_flush_stop(true).seqnum(Seqnum(10)).build();
+
+        let res = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            concat!(
+                "{",
+                "\"type_\":\"FlushStop\",",
+                "\"seqnum\":10,",
+                "\"running_time_offset\":0,",
+                "\"fields\":{\"FlushStop\":{\"reset_time\":true}}",
+                "}"
+            ),
+            res
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_seek() {
+        ::init().unwrap();
+
+        let event = Event::new_seek(
+            1.5,
+            SeekFlags::FLUSH | SeekFlags::ACCURATE,
+            SeekType::Set,
+            ClockTime::from_seconds(1),
+            SeekType::None,
+            ClockTime::none(),
+        )
+        .seqnum(Seqnum(42))
+        .running_time_offset(10)
+        .build();
+
+        let event_de = roundtrip(&event);
+        assert_eq!(event_de.get_type(), EventType::Seek);
+        assert_eq!(event_de.get_seqnum(), Seqnum(42));
+        assert_eq!(event_de.get_running_time_offset(), 10);
+        match event_de.view() {
+            EventView::Seek(ref seek) => {
+                let (rate, flags, start_type, start, stop_type, stop) = seek.get();
+                assert_eq!(rate, 1.5);
+                assert_eq!(flags, SeekFlags::FLUSH | SeekFlags::ACCURATE);
+                assert_eq!(start_type, SeekType::Set);
+                assert_eq!(
+                    start,
+                    GenericFormattedValue::Time(ClockTime::from_seconds(1))
+                );
+                assert_eq!(stop_type, SeekType::None);
+                assert_eq!(stop, GenericFormattedValue::Time(ClockTime::none()));
+            }
+            _ => panic!("Expected a seek event"),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_caps() {
+        ::init().unwrap();
+
+        let caps = Caps::builder("foo/bar").field("int", &12).build();
+        let event = Event::new_caps(&caps).seqnum(Seqnum(1)).build();
+
+        let event_de = roundtrip(&event);
+        match event_de.view() {
+            EventView::Caps(ref caps_ev) => assert_eq!(caps_ev.get_caps(), caps.as_ref()),
+            _ => panic!("Expected a caps event"),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_segment() {
+        ::init().unwrap();
+
+        let mut segment = ::FormattedSegment::<ClockTime>::new();
+        segment.set_start(ClockTime::from_seconds(2));
+        let event = Event::new_segment(&segment).seqnum(Seqnum(2)).build();
+
+        let event_de = roundtrip(&event);
+        match event_de.view() {
+            EventView::Segment(ref segment_ev) => {
+                let segment_de = segment_ev.get_segment();
+                assert_eq!(segment_de.get_format(), Format::Time);
+                assert_eq!(
+                    segment_de.get_start(),
+                    GenericFormattedValue::Time(ClockTime::from_seconds(2))
+                );
+            }
+            _ => panic!("Expected a segment event"),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_custom() {
+        ::init().unwrap();
+
+        let structure = Structure::builder("custom-event")
+            .field("int", &12)
+            .field("string", &"foo")
+            .build();
+        let event = Event::new_custom_downstream(structure.clone())
+            .seqnum(Seqnum(3))
+            .build();
+
+        let event_de = roundtrip(&event);
+        assert_eq!(event_de.get_type(), EventType::CustomDownstream);
+        assert_eq!(event_de.get_seqnum(), Seqnum(3));
+        assert_eq!(event_de.get_structure(), Some(&*structure));
+    }
+
+    #[test]
+    fn test_deserialize_invalid_gap() {
+        ::init().unwrap();
+
+        let event_ron = r#"
+            (
+                type_: Gap,
+                seqnum: 1,
+                running_time_offset: 0,
+                fields: Gap(
+                    timestamp: None,
+                    duration: Some(1000000000),
+                ),
+            )
+        "#;
+        assert!(ron::de::from_str::<Event>(event_ron).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_mismatched_type() {
+        ::init().unwrap();
+
+        let event_ron = r#"
+            (
+                type_: Seek,
+                seqnum: 1,
+                running_time_offset: 0,
+                fields: FlushStop(
+                    reset_time: true,
+                ),
+            )
+        "#;
+        assert!(ron::de::from_str::<Event>(event_ron).is_err());
+    }
+}
@@ -6,23 +6,33 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use ffi;
+use glib::translate::{from_glib, ToGlib, ToGlibPtr};
 use muldiv::MulDiv;
+use std::ffi::CStr;
 use std::ops;
 use ClockTime;
 use Format;
 
+/// Maximum value of `Percent`, corresponding to 100%.
+pub const FORMAT_PERCENT_MAX: u32 = ffi::GST_FORMAT_PERCENT_MAX as u32;
+/// Scale of `Percent` values, corresponding to 1%.
+pub const FORMAT_PERCENT_SCALE: u32 = ffi::GST_FORMAT_PERCENT_SCALE as u32;
+
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 #[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
 pub enum GenericFormattedValue {
-    Undefined(i64),
+    Undefined(Undefined),
     Default(Default),
     Bytes(Bytes),
     Time(ClockTime),
     Buffers(Buffers),
-    Percent(Option<u32>),
+    Percent(Percent),
     Other(Format, i64),
 }
 
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub struct Undefined(pub i64);
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
 pub struct Default(pub Option<u64>);
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
@@ -30,6 +40,10 @@ pub struct Bytes(pub Option<u64>);
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
 pub struct Buffers(pub Option<u64>);
 pub type Time = ClockTime;
+/// Percentage in units of `FORMAT_PERCENT_SCALE`, between 0 and
+/// `FORMAT_PERCENT_MAX`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub struct Percent(pub Option<u32>);
 
 pub trait FormattedValue: Copy + Clone + Sized + 'static {
     fn get_default_format() -> Format;
@@ -37,6 +51,12 @@ pub trait FormattedValue: Copy + Clone + Sized + 'static {
 
     fn get_format(&self) -> Format;
 
+    // Whether this type can hold values of any format
+    #[doc(hidden)]
+    fn is_generic() -> bool {
+        false
+    }
+
     unsafe fn from_raw(format: Format, value: i64) -> Self;
     unsafe fn to_raw_value(&self) -> i64;
 }
@@ -56,6 +76,10 @@ impl FormattedValue for GenericFormattedValue {
         self.get_format()
     }
 
+    fn is_generic() -> bool {
+        true
+    }
+
     unsafe fn from_raw(format: Format, value: i64) -> Self {
         GenericFormattedValue::new(format, value)
     }
@@ -68,7 +92,7 @@ impl FormattedValue for GenericFormattedValue {
 impl GenericFormattedValue {
     pub fn new(format: Format, value: i64) -> Self {
         match format {
-            Format::Undefined => GenericFormattedValue::Undefined(value),
+            Format::Undefined => GenericFormattedValue::Undefined(Undefined(value)),
             Format::Default => GenericFormattedValue::Default(if value == -1 {
                 Default(None)
             } else {
@@ -90,16 +114,16 @@ impl GenericFormattedValue {
                 Buffers(Some(value as u64))
             }),
             Format::Percent => GenericFormattedValue::Percent(if value == -1 {
-                None
+                Percent(None)
             } else {
-                Some(value as u32)
+                Percent(Some(value as u32))
             }),
             Format::__Unknown(_) => GenericFormattedValue::Other(format, value),
         }
     }
 
-    pub fn from_undefined(v: i64) -> Self {
-        GenericFormattedValue::Undefined(v)
+    pub fn from_undefined<V: Into<Undefined>>(v: V) -> Self {
+        GenericFormattedValue::Undefined(v.into())
     }
 
     pub fn from_default<V: Into<Default>>(v: V) -> Self {
@@ -118,7 +142,7 @@ impl GenericFormattedValue {
         GenericFormattedValue::Buffers(v.into())
     }
 
-    pub fn from_percent<V: Into<Option<u32>>>(v: V) -> Self {
+    pub fn from_percent<V: Into<Percent>>(v: V) -> Self {
         GenericFormattedValue::Percent(v.into())
     }
 
@@ -140,7 +164,7 @@ impl GenericFormattedValue {
 
     pub fn get_value(&self) -> i64 {
         match *self {
-            GenericFormattedValue::Undefined(v) => v,
+            GenericFormattedValue::Undefined(v) => v.0,
             GenericFormattedValue::Default(v) => v.map(|v| v as i64).unwrap_or(-1),
             GenericFormattedValue::Bytes(v) => v.map(|v| v as i64).unwrap_or(-1),
             GenericFormattedValue::Time(v) => v.map(|v| v as i64).unwrap_or(-1),
@@ -151,16 +175,10 @@ impl GenericFormattedValue {
     }
 
     pub fn try_into<F: FormattedValue>(self) -> Result<F, Self> {
-        if F::get_default_format() == self.get_format()
-            || F::get_default_format() == Format::Undefined
-        {
-            Ok(unsafe { F::from_raw(self.get_format(), self.to_raw_value()) })
-        } else {
-            Err(self)
-        }
+        F::try_from(self).ok_or(self)
     }
 
-    pub fn try_into_undefined(self) -> Result<i64, Self> {
+    pub fn try_into_undefined(self) -> Result<Undefined, Self> {
         if let GenericFormattedValue::Undefined(v) = self {
             Ok(v)
         } else {
@@ -200,7 +218,7 @@ impl GenericFormattedValue {
         }
     }
 
-    pub fn try_into_percent(self) -> Result<Option<u32>, Self> {
+    pub fn try_into_percent(self) -> Result<Percent, Self> {
         if let GenericFormattedValue::Percent(v) = self {
             Ok(v)
         } else {
@@ -255,12 +273,12 @@ macro_rules! impl_op_same(
     };
 );
 
-macro_rules! impl_op_u64(
-    ($name:ident, $op:ident, $op_name:ident, $op_assign:ident, $op_assign_name:ident, $e:expr) => {
-        impl ops::$op<u64> for $name {
+macro_rules! impl_op_inner(
+    ($name:ident, $inner_type:ty, $op:ident, $op_name:ident, $op_assign:ident, $op_assign_name:ident, $e:expr) => {
+        impl ops::$op<$inner_type> for $name {
             type Output = $name;
 
-            fn $op_name(self, other: u64) -> $name {
+            fn $op_name(self, other: $inner_type) -> $name {
                 match self.0 {
                     Some(a) => $name(Some($e(a, other))),
                     _ => $name(None),
@@ -268,16 +286,16 @@ macro_rules! impl_op_u64(
             }
         }
 
-        impl<'a> ops::$op<&'a u64> for $name {
+        impl<'a> ops::$op<&'a $inner_type> for $name {
             type Output = $name;
 
-            fn $op_name(self, other: &'a u64) -> $name {
+            fn $op_name(self, other: &'a $inner_type) -> $name {
                 self.$op_name(*other)
             }
         }
 
-        impl ops::$op_assign<u64> for $name {
-            fn $op_assign_name(&mut self, other: u64) {
+        impl ops::$op_assign<$inner_type> for $name {
+            fn $op_assign_name(&mut self, other: $inner_type) {
                 match self.0 {
                     Some(a) => self.0 = Some($e(a, other)),
                     _ => self.0 = None,
@@ -285,8 +303,8 @@ macro_rules! impl_op_u64(
             }
         }
 
-        impl<'a> ops::$op_assign<&'a u64> for $name {
-            fn $op_assign_name(&mut self, other: &'a u64) {
+        impl<'a> ops::$op_assign<&'a $inner_type> for $name {
+            fn $op_assign_name(&mut self, other: &'a $inner_type) {
                 self.$op_assign_name(*other)
             }
         }
@@ -294,7 +312,7 @@ macro_rules! impl_op_u64(
 );
 
 macro_rules! impl_format_value_traits(
-    ($name:ident, $format:ident, $format_value:ident) => {
+    ($name:ident, $format:ident, $format_value:ident, $inner_type:ty) => {
         impl From<$name> for GenericFormattedValue {
             fn from(v: $name) -> GenericFormattedValue {
                 GenericFormattedValue::$format_value(v)
@@ -323,7 +341,7 @@ macro_rules! impl_format_value_traits(
                 if value == -1 {
                     $name(None)
                 } else {
-                    $name(Some(value as u64))
+                    $name(Some(value as $inner_type))
                 }
             }
 
@@ -334,46 +352,46 @@ macro_rules! impl_format_value_traits(
 
         impl SpecificFormattedValue for $name { }
 
-        impl From<u64> for $name {
-            fn from(v: u64) -> $name {
+        impl From<$inner_type> for $name {
+            fn from(v: $inner_type) -> $name {
                 $name(Some(v))
             }
         }
 
-        impl From<Option<u64>> for $name {
-            fn from(v: Option<u64>) -> $name {
+        impl From<Option<$inner_type>> for $name {
+            fn from(v: Option<$inner_type>) -> $name {
                 $name(v)
             }
         }
 
-        impl Into<Option<u64>> for $name {
-            fn into(self) -> Option<u64> {
+        impl Into<Option<$inner_type>> for $name {
+            fn into(self) -> Option<$inner_type> {
                 self.0
             }
         }
 
         impl ops::Deref for $name {
-            type Target = Option<u64>;
+            type Target = Option<$inner_type>;
 
-            fn deref(&self) -> &Option<u64> {
+            fn deref(&self) -> &Option<$inner_type> {
                 &self.0
             }
         }
 
         impl ops::DerefMut for $name {
-            fn deref_mut(&mut self) -> &mut Option<u64> {
+            fn deref_mut(&mut self) -> &mut Option<$inner_type> {
                 &mut self.0
             }
         }
 
-        impl AsRef<Option<u64>> for $name {
-            fn as_ref(&self) -> &Option<u64> {
+        impl AsRef<Option<$inner_type>> for $name {
+            fn as_ref(&self) -> &Option<$inner_type> {
                 &self.0
             }
         }
 
-        impl AsMut<Option<u64>> for $name {
-            fn as_mut(&mut self) -> &mut Option<u64> {
+        impl AsMut<Option<$inner_type>> for $name {
+            fn as_mut(&mut self) -> &mut Option<$inner_type> {
                 &mut self.0
             }
         }
@@ -384,11 +402,11 @@ macro_rules! impl_format_value_traits(
         impl_op_same!($name, Div, div, DivAssign, div_assign, |a, b| a / b);
         impl_op_same!($name, Rem, rem, RemAssign, rem_assign, |a, b| a % b);
 
-        impl_op_u64!($name, Mul, mul, MulAssign, mul_assign, |a, b| a * b);
-        impl_op_u64!($name, Div, div, DivAssign, div_assign, |a, b| a / b);
-        impl_op_u64!($name, Rem, rem, RemAssign, rem_assign, |a, b| a % b);
+        impl_op_inner!($name, $inner_type, Mul, mul, MulAssign, mul_assign, |a, b| a * b);
+        impl_op_inner!($name, $inner_type, Div, div, DivAssign, div_assign, |a, b| a / b);
+        impl_op_inner!($name, $inner_type, Rem, rem, RemAssign, rem_assign, |a, b| a % b);
 
-        impl ops::Mul<$name> for u64 {
+        impl ops::Mul<$name> for $inner_type {
             type Output = $name;
 
             fn mul(self, other: $name) -> $name {
@@ -396,7 +414,7 @@ macro_rules! impl_format_value_traits(
             }
         }
 
-        impl<'a> ops::Mul<&'a $name> for u64 {
+        impl<'a> ops::Mul<&'a $name> for $inner_type {
             type Output = $name;
 
             fn mul(self, other: &'a $name) -> $name {
@@ -445,41 +463,261 @@ macro_rules! impl_format_value_traits(
             }
         }
 
-        impl<'a> MulDiv<u64> for $name {
+        impl<'a> MulDiv<$inner_type> for $name {
             type Output = $name;
 
-            fn mul_div_floor(self, num: u64, denom: u64) -> Option<Self::Output> {
+            fn mul_div_floor(self, num: $inner_type, denom: $inner_type) -> Option<Self::Output> {
                 self.mul_div_floor($name(Some(num)), $name(Some(denom)))
             }
 
-            fn mul_div_round(self, num: u64, denom: u64) -> Option<Self::Output> {
+            fn mul_div_round(self, num: $inner_type, denom: $inner_type) -> Option<Self::Output> {
                 self.mul_div_round($name(Some(num)), $name(Some(denom)))
             }
 
-            fn mul_div_ceil(self, num: u64, denom: u64) -> Option<Self::Output> {
+            fn mul_div_ceil(self, num: $inner_type, denom: $inner_type) -> Option<Self::Output> {
                 self.mul_div_ceil($name(Some(num)), $name(Some(denom)))
             }
         }
 
-        impl<'a> MulDiv<&'a u64> for $name {
+        impl<'a> MulDiv<&'a $inner_type> for $name {
             type Output = $name;
 
-            fn mul_div_floor(self, num: &u64, denom: &u64) -> Option<Self::Output> {
+            fn mul_div_floor(self, num: &$inner_type, denom: &$inner_type) -> Option<Self::Output> {
                 self.mul_div_floor(*num, *denom)
             }
 
-            fn mul_div_round(self, num: &u64, denom: &u64) -> Option<Self::Output> {
+            fn mul_div_round(self, num: &$inner_type, denom: &$inner_type) -> Option<Self::Output> {
                 self.mul_div_round(*num, *denom)
             }
 
-            fn mul_div_ceil(self, num: &u64, denom: &u64) -> Option<Self::Output> {
+            fn mul_div_ceil(self, num: &$inner_type, denom: &$inner_type) -> Option<Self::Output> {
                 self.mul_div_ceil(*num, *denom)
             }
         }
     };
 );
 
-impl_format_value_traits!(Default, Default, Default);
-impl_format_value_traits!(Bytes, Bytes, Bytes);
-impl_format_value_traits!(ClockTime, Time, Time);
-impl_format_value_traits!(Buffers, Buffers, Buffers);
+impl_format_value_traits!(Default, Default, Default, u64);
+impl_format_value_traits!(Bytes, Bytes, Bytes, u64);
+impl_format_value_traits!(ClockTime, Time, Time, u64);
+impl_format_value_traits!(Buffers, Buffers, Buffers, u64);
+impl_format_value_traits!(Percent, Percent, Percent, u32);
+
+impl From<Undefined> for GenericFormattedValue {
+    fn from(v: Undefined) -> GenericFormattedValue {
+        GenericFormattedValue::Undefined(v)
+    }
+}
+
+impl FormattedValue for Undefined {
+    fn get_default_format() -> Format {
+        Format::Undefined
+    }
+
+    fn try_from(v: GenericFormattedValue) -> Option<Self> {
+        if let GenericFormattedValue::Undefined(v) = v {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    fn get_format(&self) -> Format {
+        Format::Undefined
+    }
+
+    unsafe fn from_raw(format: Format, value: i64) -> Self {
+        debug_assert_eq!(format, Format::Undefined);
+        Undefined(value)
+    }
+
+    unsafe fn to_raw_value(&self) -> i64 {
+        self.0
+    }
+}
+
+impl SpecificFormattedValue for Undefined {}
+
+impl From<i64> for Undefined {
+    fn from(v: i64) -> Undefined {
+        Undefined(v)
+    }
+}
+
+impl Into<i64> for Undefined {
+    fn into(self) -> i64 {
+        self.0
+    }
+}
+
+impl ops::Deref for Undefined {
+    type Target = i64;
+
+    fn deref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl ops::DerefMut for Undefined {
+    fn deref_mut(&mut self) -> &mut i64 {
+        &mut self.0
+    }
+}
+
+impl AsRef<i64> for Undefined {
+    fn as_ref(&self) -> &i64 {
+        &self.0
+    }
+}
+
+impl AsMut<i64> for Undefined {
+    fn as_mut(&mut self) -> &mut i64 {
+        &mut self.0
+    }
+}
+
+impl Percent {
+    /// Creates a `Percent` from a ratio between `0.0` and `1.0`.
+    ///
+    /// Returns `None` if the ratio is outside of this range.
+    pub fn from_ratio(ratio: f64) -> Option<Self> {
+        if ratio >= 0.0 && ratio <= 1.0 {
+            Some(Percent(Some(
+                (ratio * f64::from(FORMAT_PERCENT_MAX)).round() as u32,
+            )))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the percentage as a ratio between `0.0` and `1.0`.
+    pub fn get_ratio(&self) -> Option<f64> {
+        self.0.map(|v| f64::from(v) / f64::from(FORMAT_PERCENT_MAX))
+    }
+}
+
+/// Format registered at runtime with `CustomFormat::register()`.
+///
+/// Values in a custom format are represented by
+/// `GenericFormattedValue::Other`.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct CustomFormat(Format);
+
+impl CustomFormat {
+    /// Registers a new format with the given nick and description.
+    ///
+    /// If a format with the same nick already exists, that format is returned
+    /// instead.
+    pub fn register(nick: &str, description: &str) -> Self {
+        assert_initialized_main_thread!();
+        unsafe {
+            CustomFormat(from_glib(ffi::gst_format_register(
+                nick.to_glib_none().0,
+                description.to_glib_none().0,
+            )))
+        }
+    }
+
+    pub fn get_by_nick(nick: &str) -> Option<Self> {
+        assert_initialized_main_thread!();
+        let format = unsafe { ffi::gst_format_get_by_nick(nick.to_glib_none().0) };
+        // Only formats after the predefined ones are custom formats
+        if format <= ffi::GST_FORMAT_PERCENT {
+            None
+        } else {
+            Some(CustomFormat(from_glib(format)))
+        }
+    }
+
+    pub fn get_format(&self) -> Format {
+        self.0
+    }
+
+    pub fn get_nick(&self) -> Option<&'static str> {
+        unsafe {
+            let nick = ffi::gst_format_get_name(self.0.to_glib());
+            if nick.is_null() {
+                None
+            } else {
+                CStr::from_ptr(nick).to_str().ok()
+            }
+        }
+    }
+
+    pub fn get_description(&self) -> Option<&'static str> {
+        unsafe {
+            let details = ffi::gst_format_get_details(self.0.to_glib());
+            if details.is_null() || (*details).description.is_null() {
+                None
+            } else {
+                CStr::from_ptr((*details).description).to_str().ok()
+            }
+        }
+    }
+
+    pub fn value(&self, value: i64) -> GenericFormattedValue {
+        GenericFormattedValue::new(self.0, value)
+    }
+}
+
+impl From<CustomFormat> for Format {
+    fn from(v: CustomFormat) -> Format {
+        v.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent() {
+        ::init().unwrap();
+
+        assert_eq!(Percent::from_ratio(0.5), Some(Percent(Some(500_000))));
+        assert_eq!(
+            Percent::from_ratio(1.0),
+            Some(Percent(Some(FORMAT_PERCENT_MAX)))
+        );
+        assert_eq!(Percent::from_ratio(1.5), None);
+        assert_eq!(Percent::from_ratio(-0.1), None);
+        assert_eq!(Percent(Some(250_000)).get_ratio(), Some(0.25));
+        assert_eq!(Percent(None).get_ratio(), None);
+
+        let value =
+            GenericFormattedValue::new(Format::Percent, 10 * i64::from(FORMAT_PERCENT_SCALE));
+        assert_eq!(value.try_into_percent(), Ok(Percent(Some(100_000))));
+        assert_eq!(value.try_into::<Percent>(), Ok(Percent(Some(100_000))));
+        assert_eq!(value.try_into::<Undefined>(), Err(value));
+
+        let value = GenericFormattedValue::new(Format::Undefined, -5);
+        assert_eq!(value.try_into::<Undefined>(), Ok(Undefined(-5)));
+        assert_eq!(value.get_value(), -5);
+    }
+
+    #[test]
+    fn test_custom_format() {
+        ::init().unwrap();
+
+        let format = CustomFormat::register("test-custom-format", "Test custom format");
+        assert_eq!(format.get_nick(), Some("test-custom-format"));
+        assert_eq!(format.get_description(), Some("Test custom format"));
+        assert_eq!(
+            CustomFormat::get_by_nick("test-custom-format"),
+            Some(format)
+        );
+        assert_eq!(
+            CustomFormat::register("test-custom-format", "Other description"),
+            format
+        );
+
+        // Predefined formats are not custom formats
+        assert_eq!(CustomFormat::get_by_nick("time"), None);
+        assert_eq!(CustomFormat::get_by_nick("percent"), None);
+        assert_eq!(CustomFormat::get_by_nick("does-not-exist"), None);
+
+        let value = format.value(42);
+        assert_eq!(value, GenericFormattedValue::Other(format.get_format(), 42));
+        assert_eq!(value.get_format(), format.into());
+    }
+}
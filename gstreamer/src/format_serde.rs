@@ -9,7 +9,7 @@
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 
-use format::{Buffers, Bytes, Default};
+use format::{Buffers, Bytes, Default, Percent, Undefined};
 
 // Manual implementation for some types that would otherwise yield representations such as:
 // "Default((Some(42)))"
@@ -24,7 +24,7 @@ macro_rules! impl_ser_de(
 
         impl<'de> Deserialize<'de> for $t {
             fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-                Option::<u64>::deserialize(deserializer).map($t)
+                Deserialize::deserialize(deserializer).map($t)
             }
         }
     }
@@ -33,13 +33,15 @@ macro_rules! impl_ser_de(
 impl_ser_de!(Buffers);
 impl_ser_de!(Bytes);
 impl_ser_de!(Default);
+impl_ser_de!(Percent);
+impl_ser_de!(Undefined);
 
 #[cfg(test)]
 mod tests {
     extern crate ron;
     extern crate serde_json;
 
-    use format::{Buffers, Bytes, Default};
+    use format::{Buffers, Bytes, Default, Percent, Undefined};
     use ClockTime;
     use Format;
     use GenericFormattedValue;
@@ -51,7 +53,7 @@ mod tests {
         let mut pretty_config = ron::ser::PrettyConfig::default();
         pretty_config.new_line = "".to_string();
 
-        let value = GenericFormattedValue::Undefined(42);
+        let value = GenericFormattedValue::Undefined(Undefined(42));
         let res = ron::ser::to_string_pretty(&value, pretty_config.clone());
         assert_eq!(Ok("Undefined(42)".to_owned()), res);
         let res = serde_json::to_string(&value).unwrap();
@@ -87,7 +89,7 @@ mod tests {
         let res = serde_json::to_string(&value).unwrap();
         assert_eq!("{\"Buffers\":42}".to_owned(), res);
 
-        let value = GenericFormattedValue::Percent(Some(42));
+        let value = GenericFormattedValue::Percent(Percent(Some(42)));
         let res = ron::ser::to_string_pretty(&value, pretty_config.clone());
         assert_eq!(Ok("Percent(Some(42))".to_owned()), res);
         let res = serde_json::to_string(&value).unwrap();
@@ -118,6 +120,14 @@ mod tests {
         let value_de: GenericFormattedValue = serde_json::from_str(value_json).unwrap();
         assert_eq!(value_de, GenericFormattedValue::Default(Default(Some(42))));
 
+        let value_ron = "Percent(Some(42))";
+        let value_de: GenericFormattedValue = ron::de::from_str(value_ron).unwrap();
+        assert_eq!(value_de, GenericFormattedValue::Percent(Percent(Some(42))));
+
+        let value_json = "{\"Undefined\":-1}";
+        let value_de: GenericFormattedValue = serde_json::from_str(value_json).unwrap();
+        assert_eq!(value_de, GenericFormattedValue::Undefined(Undefined(-1)));
+
         let value_ron = "Other(Percent, 42)";
         let value_de: GenericFormattedValue = ron::de::from_str(value_ron).unwrap();
         assert_eq!(value_de, GenericFormattedValue::Other(Format::Percent, 42));
@@ -139,14 +149,14 @@ mod tests {
             }
         );
 
-        test_roundrip!(GenericFormattedValue::Undefined(42));
+        test_roundrip!(GenericFormattedValue::Undefined(Undefined(42)));
         test_roundrip!(GenericFormattedValue::Default(Default(Some(42))));
         test_roundrip!(GenericFormattedValue::Bytes(Bytes(Some(42))));
         test_roundrip!(GenericFormattedValue::Time(ClockTime::from_nseconds(
             42_123_456_789
         )));
         test_roundrip!(GenericFormattedValue::Buffers(Buffers(Some(42))));
-        test_roundrip!(GenericFormattedValue::Percent(Some(42)));
+        test_roundrip!(GenericFormattedValue::Percent(Percent(Some(42))));
         test_roundrip!(GenericFormattedValue::Other(Format::Percent, 42));
         test_roundrip!(GenericFormattedValue::Other(Format::__Unknown(7), 42));
     }
@@ -0,0 +1,1198 @@
+// Copyright (C) 2019 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Pure Rust parser for the `gst-launch` pipeline description syntax.
+//!
+//! Unlike `parse_launch()`, parsing does not instantiate any elements and
+//! errors carry the byte range of the offending input. The resulting
+//! `Description` can be inspected, modified, printed back via its `Display`
+//! implementation and finally instantiated with `Description::build()`.
+//!
+//! The supported subset of the syntax covers elements with properties,
+//! links (`!`), caps filters, bins (`( ... )` and `factory.( ... )`) and
+//! references to named elements and their pads (`name.` and `name.pad`).
+//! Values containing whitespace or any of `!()"\` have to be put in double
+//! quotes, caps filters without a `/` in them as well.
+
+use std::error;
+use std::fmt;
+use std::iter;
+use std::str;
+use std::vec;
+
+use glib;
+use glib::{Cast, ObjectExt};
+
+use Bin;
+use Caps;
+use DebugCategory;
+use DebugColorFlags;
+use Element;
+use ElementExt;
+use ElementExtManual;
+use ElementFactory;
+use GhostPad;
+use GstBinExt;
+use GstObjectExt;
+use GstValueExt;
+use IteratorError;
+use PadDirection;
+use PadExt;
+use PadPresence;
+use ParseError;
+use Pipeline;
+
+lazy_static! {
+    static ref CAT: DebugCategory = DebugCategory::new(
+        "GST_RUST_LAUNCH",
+        DebugColorFlags::empty(),
+        "Rust launch description builder",
+    );
+}
+
+/// Byte range of a part of the parsed description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LaunchError {
+    kind: ParseError,
+    message: String,
+    span: Span,
+}
+
+impl LaunchError {
+    fn new<T: Into<String>>(kind: ParseError, message: T, span: Span) -> LaunchError {
+        LaunchError {
+            kind,
+            message: message.into(),
+            span,
+        }
+    }
+
+    pub fn kind(&self) -> ParseError {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.span)
+    }
+}
+
+impl error::Error for LaunchError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    pub name: String,
+    /// The value with any quoting removed.
+    pub value: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementNode {
+    pub factory: String,
+    pub properties: Vec<Property>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapsNode {
+    pub caps: String,
+    pub span: Span,
+}
+
+/// Reference to a named element, optionally restricted to one of its pads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PadRef {
+    pub element: String,
+    pub pad: Option<String>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinNode {
+    /// Factory of the bin, a plain `Bin` is created if `None`.
+    pub factory: Option<String>,
+    pub properties: Vec<Property>,
+    pub chains: Vec<Chain>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Element(ElementNode),
+    Caps(CapsNode),
+    PadRef(PadRef),
+    Bin(BinNode),
+}
+
+impl Node {
+    pub fn span(&self) -> Span {
+        match *self {
+            Node::Element(ref element) => element.span,
+            Node::Caps(ref caps) => caps.span,
+            Node::PadRef(ref pad_ref) => pad_ref.span,
+            Node::Bin(ref bin) => bin.span,
+        }
+    }
+}
+
+/// Nodes linked one after another, i.e. separated by `!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chain {
+    pub nodes: Vec<Node>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Description {
+    pub chains: Vec<Chain>,
+}
+
+pub fn parse(description: &str) -> Result<Description, LaunchError> {
+    let tokens = tokenize(description)?;
+    let mut parser = Parser {
+        tokens: tokens.into_iter().peekable(),
+        len: description.len(),
+    };
+
+    let chains = parser.parse_chains()?;
+    if let Some(token) = parser.tokens.next() {
+        return Err(LaunchError::new(
+            ParseError::Syntax,
+            "unexpected ')'",
+            token.span,
+        ));
+    }
+
+    if chains.is_empty() {
+        return Err(LaunchError::new(
+            ParseError::Empty,
+            "empty pipeline description",
+            Span::new(0, description.len()),
+        ));
+    }
+
+    Ok(Description { chains })
+}
+
+impl str::FromStr for Description {
+    type Err = LaunchError;
+
+    fn from_str(s: &str) -> Result<Self, LaunchError> {
+        parse(s)
+    }
+}
+
+#[derive(Debug)]
+struct Word {
+    // With quotes and escapes removed
+    text: String,
+    // Position of the first unquoted '=' in `text`
+    eq: Option<usize>,
+    quoted: bool,
+}
+
+#[derive(Debug)]
+enum TokenKind {
+    Link,
+    Open,
+    Close,
+    Word(Word),
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: TokenKind,
+    span: Span,
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, LaunchError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        let kind = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '!' => TokenKind::Link,
+            '(' => TokenKind::Open,
+            ')' => TokenKind::Close,
+            _ => {
+                let (word, end) = tokenize_word(s, &mut chars)?;
+                tokens.push(Token {
+                    kind: TokenKind::Word(word),
+                    span: Span::new(start, end),
+                });
+                continue;
+            }
+        };
+
+        chars.next();
+        tokens.push(Token {
+            kind,
+            span: Span::new(start, start + 1),
+        });
+    }
+
+    Ok(tokens)
+}
+
+fn tokenize_word(
+    s: &str,
+    chars: &mut iter::Peekable<str::CharIndices>,
+) -> Result<(Word, usize), LaunchError> {
+    let mut word = Word {
+        text: String::new(),
+        eq: None,
+        quoted: false,
+    };
+    let mut end = 0;
+    // Parentheses are part of a word if balanced, e.g. `width=(int)320`
+    let mut depth = 0;
+
+    while let Some(&(pos, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() || c == '!' => break,
+            '(' if word.text.is_empty() || word.text.ends_with('.') => break,
+            '(' => depth += 1,
+            ')' if depth == 0 => break,
+            ')' => depth -= 1,
+            '"' => {
+                chars.next();
+                word.quoted = true;
+                end = tokenize_quoted(s, pos, chars, &mut word.text)?;
+                continue;
+            }
+            '=' if word.eq.is_none() => word.eq = Some(word.text.len()),
+            _ => (),
+        }
+
+        chars.next();
+        word.text.push(c);
+        end = pos + c.len_utf8();
+    }
+
+    Ok((word, end))
+}
+
+fn tokenize_quoted(
+    s: &str,
+    start: usize,
+    chars: &mut iter::Peekable<str::CharIndices>,
+    text: &mut String,
+) -> Result<usize, LaunchError> {
+    loop {
+        match chars.next() {
+            Some((pos, '"')) => return Ok(pos + 1),
+            Some((_, '\\')) => match chars.next() {
+                Some((_, c)) => text.push(c),
+                None => break,
+            },
+            Some((_, c)) => text.push(c),
+            None => break,
+        }
+    }
+
+    Err(LaunchError::new(
+        ParseError::Syntax,
+        "unterminated quoted string",
+        Span::new(start, s.len()),
+    ))
+}
+
+fn is_identifier(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+fn is_property(word: &Word) -> bool {
+    match word.eq {
+        Some(eq) => {
+            let name = &word.text[..eq];
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == ':')
+        }
+        None => false,
+    }
+}
+
+struct Parser {
+    tokens: iter::Peekable<vec::IntoIter<Token>>,
+    len: usize,
+}
+
+impl Parser {
+    fn parse_chains(&mut self) -> Result<Vec<Chain>, LaunchError> {
+        let mut chains = Vec::new();
+
+        loop {
+            match self.tokens.peek() {
+                None
+                | Some(&Token {
+                    kind: TokenKind::Close,
+                    ..
+                }) => break,
+                _ => chains.push(self.parse_chain()?),
+            }
+        }
+
+        Ok(chains)
+    }
+
+    fn parse_chain(&mut self) -> Result<Chain, LaunchError> {
+        let mut nodes = vec![self.parse_node()?];
+
+        while let Some(link) = self.next_link() {
+            match self.tokens.peek() {
+                Some(&Token {
+                    kind: TokenKind::Word(_),
+                    ..
+                })
+                | Some(&Token {
+                    kind: TokenKind::Open,
+                    ..
+                }) => nodes.push(self.parse_node()?),
+                _ => {
+                    return Err(LaunchError::new(
+                        ParseError::Syntax,
+                        "expected element after '!'",
+                        link,
+                    ));
+                }
+            }
+        }
+
+        let last = nodes.len() - 1;
+        for (i, node) in nodes.iter().enumerate() {
+            if let Node::Caps(ref caps) = *node {
+                if i == 0 || i == last {
+                    return Err(LaunchError::new(
+                        ParseError::Syntax,
+                        "caps filter must be linked on both sides",
+                        caps.span,
+                    ));
+                }
+            }
+        }
+
+        let span = Span::new(nodes[0].span().start, nodes[last].span().end);
+        Ok(Chain { nodes, span })
+    }
+
+    fn next_link(&mut self) -> Option<Span> {
+        match self.tokens.peek() {
+            Some(&Token {
+                kind: TokenKind::Link,
+                span,
+            }) => {
+                self.tokens.next();
+                Some(span)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_node(&mut self) -> Result<Node, LaunchError> {
+        let token = match self.tokens.next() {
+            Some(token) => token,
+            None => {
+                return Err(LaunchError::new(
+                    ParseError::Syntax,
+                    "unexpected end of input",
+                    Span::new(self.len, self.len),
+                ));
+            }
+        };
+
+        match token.kind {
+            TokenKind::Link => Err(LaunchError::new(
+                ParseError::Syntax,
+                "unexpected '!'",
+                token.span,
+            )),
+            TokenKind::Close => Err(LaunchError::new(
+                ParseError::Syntax,
+                "unexpected ')'",
+                token.span,
+            )),
+            TokenKind::Open => self.parse_bin(None, token.span.start),
+            TokenKind::Word(word) => self.parse_word(word, token.span),
+        }
+    }
+
+    fn parse_word(&mut self, word: Word, span: Span) -> Result<Node, LaunchError> {
+        if is_property(&word) {
+            return Err(LaunchError::new(
+                ParseError::Syntax,
+                format!(
+                    "property '{}' without element",
+                    &word.text[..word.eq.unwrap()]
+                ),
+                span,
+            ));
+        }
+
+        if word.quoted || word.text.contains('/') {
+            return Ok(Node::Caps(CapsNode {
+                caps: word.text,
+                span,
+            }));
+        }
+
+        if let Some(dot) = word.text.find('.') {
+            let element = word.text[..dot].to_owned();
+            let pad = &word.text[dot + 1..];
+
+            if !is_identifier(&element) {
+                return Err(LaunchError::new(
+                    ParseError::Syntax,
+                    format!("invalid element reference '{}'", word.text),
+                    span,
+                ));
+            }
+
+            if pad.is_empty() {
+                let opens_bin = match self.tokens.peek() {
+                    Some(&Token {
+                        kind: TokenKind::Open,
+                        span: open,
+                    }) => open.start == span.end,
+                    _ => false,
+                };
+
+                if opens_bin {
+                    self.tokens.next();
+                    return self.parse_bin(Some(element), span.start);
+                }
+            }
+
+            return Ok(Node::PadRef(PadRef {
+                element,
+                pad: if pad.is_empty() {
+                    None
+                } else {
+                    Some(pad.to_owned())
+                },
+                span,
+            }));
+        }
+
+        if !is_identifier(&word.text) {
+            return Err(LaunchError::new(
+                ParseError::Syntax,
+                format!("invalid element factory name '{}'", word.text),
+                span,
+            ));
+        }
+
+        let properties = self.parse_properties();
+        let end = properties.last().map(|p| p.span.end).unwrap_or(span.end);
+
+        Ok(Node::Element(ElementNode {
+            factory: word.text,
+            properties,
+            span: Span::new(span.start, end),
+        }))
+    }
+
+    fn parse_properties(&mut self) -> Vec<Property> {
+        let mut properties = Vec::new();
+
+        loop {
+            match self.tokens.peek() {
+                Some(&Token {
+                    kind: TokenKind::Word(ref word),
+                    ..
+                }) if is_property(word) => (),
+                _ => break,
+            }
+
+            if let Some(Token {
+                kind: TokenKind::Word(word),
+                span,
+            }) = self.tokens.next()
+            {
+                let eq = word.eq.unwrap();
+                let mut name = word.text;
+                let value = name.split_off(eq + 1);
+                name.truncate(eq);
+
+                properties.push(Property { name, value, span });
+            }
+        }
+
+        properties
+    }
+
+    fn parse_bin(&mut self, factory: Option<String>, start: usize) -> Result<Node, LaunchError> {
+        let properties = self.parse_properties();
+        let chains = self.parse_chains()?;
+
+        match self.tokens.next() {
+            Some(Token {
+                kind: TokenKind::Close,
+                span,
+            }) => {
+                let span = Span::new(start, span.end);
+                if chains.is_empty() {
+                    return Err(LaunchError::new(ParseError::EmptyBin, "empty bin", span));
+                }
+
+                Ok(Node::Bin(BinNode {
+                    factory,
+                    properties,
+                    chains,
+                    span,
+                }))
+            }
+            _ => Err(LaunchError::new(
+                ParseError::Syntax,
+                "unclosed bin",
+                Span::new(start, self.len),
+            )),
+        }
+    }
+}
+
+fn needs_quoting(s: &str) -> bool {
+    s.is_empty()
+        || s.chars()
+            .any(|c| c.is_whitespace() || "!()\"\\".contains(c))
+}
+
+fn write_quoted(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            f.write_str("\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    f.write_str("\"")
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}=", self.name)?;
+        if needs_quoting(&self.value) {
+            write_quoted(f, &self.value)
+        } else {
+            f.write_str(&self.value)
+        }
+    }
+}
+
+impl fmt::Display for ElementNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.factory)?;
+        for property in &self.properties {
+            write!(f, " {}", property)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CapsNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if needs_quoting(&self.caps) || !self.caps.contains('/') {
+            write_quoted(f, &self.caps)
+        } else {
+            f.write_str(&self.caps)
+        }
+    }
+}
+
+impl fmt::Display for PadRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.", self.element)?;
+        if let Some(ref pad) = self.pad {
+            f.write_str(pad)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for BinNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref factory) = self.factory {
+            write!(f, "{}.", factory)?;
+        }
+        f.write_str("(")?;
+        for property in &self.properties {
+            write!(f, " {}", property)?;
+        }
+        for chain in &self.chains {
+            write!(f, " {}", chain)?;
+        }
+        f.write_str(" )")
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Node::Element(ref element) => element.fmt(f),
+            Node::Caps(ref caps) => caps.fmt(f),
+            Node::PadRef(ref pad_ref) => pad_ref.fmt(f),
+            Node::Bin(ref bin) => bin.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ! ")?;
+            }
+            node.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Description {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, chain) in self.chains.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            chain.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+enum Endpoint {
+    Element(Element),
+    Bin {
+        bin: Bin,
+        first: Option<Element>,
+        last: Option<Element>,
+    },
+    Ref(PadRef),
+}
+
+struct PendingLink {
+    src: Endpoint,
+    sink: Endpoint,
+    span: Span,
+}
+
+impl Description {
+    /// Instantiates all elements of the description inside a new `Pipeline`
+    /// and links them.
+    ///
+    /// Links from pads that don't exist yet but could appear later via a
+    /// sometimes pad template are delayed until the pad is added.
+    ///
+    /// Like `gst_parse_launch()`, bins that are linked get ghost pads for the
+    /// unlinked sink pads of their first element and the unlinked source pads
+    /// of their last element.
+    pub fn build(&self) -> Result<Pipeline, LaunchError> {
+        assert_initialized_main_thread!();
+
+        let pipeline = Pipeline::new(None);
+        let mut links = Vec::new();
+        build_chains(pipeline.upcast_ref(), &self.chains, &mut links)?;
+
+        for link in links {
+            link_endpoints(&pipeline, &link)?;
+        }
+
+        Ok(pipeline)
+    }
+}
+
+fn build_chains(
+    bin: &Bin,
+    chains: &[Chain],
+    links: &mut Vec<PendingLink>,
+) -> Result<(Option<Element>, Option<Element>), LaunchError> {
+    let mut first = None;
+    let mut last = None;
+
+    for (chain_idx, chain) in chains.iter().enumerate() {
+        let mut prev: Option<(Endpoint, Span)> = None;
+
+        for (node_idx, node) in chain.nodes.iter().enumerate() {
+            let endpoint = build_node(bin, node, links)?;
+
+            let element = match endpoint {
+                Endpoint::Element(ref element) => Some(element.clone()),
+                Endpoint::Bin { ref bin, .. } => Some(bin.clone().upcast()),
+                Endpoint::Ref(_) => None,
+            };
+            if chain_idx == 0 && node_idx == 0 {
+                first = element.clone();
+            }
+            if chain_idx == chains.len() - 1 && node_idx == chain.nodes.len() - 1 {
+                last = element;
+            }
+
+            if let Some((src, src_span)) = prev.take() {
+                links.push(PendingLink {
+                    src,
+                    sink: endpoint.clone(),
+                    span: Span::new(src_span.start, node.span().end),
+                });
+            }
+            prev = Some((endpoint, node.span()));
+        }
+    }
+
+    Ok((first, last))
+}
+
+fn build_node(
+    bin: &Bin,
+    node: &Node,
+    links: &mut Vec<PendingLink>,
+) -> Result<Endpoint, LaunchError> {
+    let element = match *node {
+        Node::Element(ref element_node) => {
+            let element = make_element(&element_node.factory, element_node.span)?;
+            set_properties(&element, &element_node.properties)?;
+            element
+        }
+        Node::Caps(ref caps_node) => {
+            let caps = Caps::from_string(&caps_node.caps).ok_or_else(|| {
+                LaunchError::new(
+                    ParseError::Syntax,
+                    format!("invalid caps '{}'", caps_node.caps),
+                    caps_node.span,
+                )
+            })?;
+            let element = make_element("capsfilter", caps_node.span)?;
+            element.set_property("caps", &caps).map_err(|_| {
+                LaunchError::new(
+                    ParseError::CouldNotSetProperty,
+                    "could not set caps on capsfilter",
+                    caps_node.span,
+                )
+            })?;
+            element
+        }
+        Node::PadRef(ref pad_ref) => return Ok(Endpoint::Ref(pad_ref.clone())),
+        Node::Bin(ref bin_node) => {
+            let element = match bin_node.factory {
+                Some(ref factory) => {
+                    let element = make_element(factory, bin_node.span)?;
+                    if !element.is::<Bin>() {
+                        return Err(LaunchError::new(
+                            ParseError::Syntax,
+                            format!("element '{}' is not a bin", factory),
+                            bin_node.span,
+                        ));
+                    }
+                    element
+                }
+                None => Bin::new(None).upcast(),
+            };
+            set_properties(&element, &bin_node.properties)?;
+            let bin_element = element.downcast::<Bin>().unwrap();
+            let (first, last) = build_chains(&bin_element, &bin_node.chains, links)?;
+            add_to_bin(bin, bin_element.upcast_ref(), node.span())?;
+
+            return Ok(Endpoint::Bin {
+                bin: bin_element,
+                first,
+                last,
+            });
+        }
+    };
+
+    add_to_bin(bin, &element, node.span())?;
+
+    Ok(Endpoint::Element(element))
+}
+
+fn add_to_bin(bin: &Bin, element: &Element, span: Span) -> Result<(), LaunchError> {
+    bin.add(element).map_err(|_| {
+        LaunchError::new(
+            ParseError::Syntax,
+            format!("could not add element '{}' to bin", element.get_name()),
+            span,
+        )
+    })
+}
+
+fn make_element(factory: &str, span: Span) -> Result<Element, LaunchError> {
+    ElementFactory::make(factory, None).ok_or_else(|| {
+        LaunchError::new(
+            ParseError::NoSuchElement,
+            format!("no element '{}'", factory),
+            span,
+        )
+    })
+}
+
+fn set_properties(element: &Element, properties: &[Property]) -> Result<(), LaunchError> {
+    for property in properties {
+        let pspec = element
+            .find_property(property.name.as_str())
+            .ok_or_else(|| {
+                LaunchError::new(
+                    ParseError::NoSuchProperty,
+                    format!("no property '{}' in element", property.name),
+                    property.span,
+                )
+            })?;
+
+        let could_not_set = || {
+            LaunchError::new(
+                ParseError::CouldNotSetProperty,
+                format!(
+                    "could not set property '{}' to '{}'",
+                    property.name, property.value
+                ),
+                property.span,
+            )
+        };
+
+        let value =
+            glib::Value::deserialize_with_type(property.value.as_str(), pspec.get_value_type())
+                .ok_or_else(could_not_set)?;
+        element
+            .set_property(property.name.as_str(), &value)
+            .map_err(|_| could_not_set())?;
+    }
+
+    Ok(())
+}
+
+fn resolve_endpoint(
+    pipeline: &Pipeline,
+    endpoint: &Endpoint,
+    direction: PadDirection,
+    span: Span,
+) -> Result<(Element, Option<String>), LaunchError> {
+    match *endpoint {
+        Endpoint::Element(ref element) => Ok((element.clone(), None)),
+        Endpoint::Bin {
+            ref bin,
+            ref first,
+            ref last,
+        } => {
+            let inner = if direction == PadDirection::Src {
+                last
+            } else {
+                first
+            };
+            if let Some(ref inner) = *inner {
+                ghost_unlinked_pads(bin, inner, direction, span)?;
+            }
+            Ok((bin.clone().upcast(), None))
+        }
+        Endpoint::Ref(ref pad_ref) => pipeline
+            .get_by_name(&pad_ref.element)
+            .map(|element| (element, pad_ref.pad.clone()))
+            .ok_or_else(|| {
+                LaunchError::new(
+                    ParseError::NoSuchElement,
+                    format!("no element named '{}'", pad_ref.element),
+                    pad_ref.span,
+                )
+            }),
+    }
+}
+
+fn ghost_unlinked_pads(
+    bin: &Bin,
+    element: &Element,
+    direction: PadDirection,
+    span: Span,
+) -> Result<(), LaunchError> {
+    let mut pads = Vec::new();
+
+    let mut iter = if direction == PadDirection::Src {
+        element.iterate_src_pads()
+    } else {
+        element.iterate_sink_pads()
+    };
+    loop {
+        match iter.next() {
+            Ok(Some(pad)) => {
+                if !pad.is_linked() {
+                    pads.push(pad);
+                }
+            }
+            Ok(None) => break,
+            Err(IteratorError::Resync) => {
+                pads.clear();
+                iter.resync();
+            }
+            Err(IteratorError::Error) => break,
+        }
+    }
+
+    for pad in pads {
+        let could_not_ghost = || {
+            LaunchError::new(
+                ParseError::Link,
+                format!(
+                    "could not ghost pad '{}:{}' on bin '{}'",
+                    element.get_name(),
+                    pad.get_name(),
+                    bin.get_name()
+                ),
+                span,
+            )
+        };
+
+        let ghost = GhostPad::new(pad.get_name().as_str(), &pad).ok_or_else(could_not_ghost)?;
+        bin.add_pad(&ghost).map_err(|_| could_not_ghost())?;
+    }
+
+    Ok(())
+}
+
+fn link_endpoints(pipeline: &Pipeline, link: &PendingLink) -> Result<(), LaunchError> {
+    let (src, src_pad) = resolve_endpoint(pipeline, &link.src, PadDirection::Src, link.span)?;
+    let (sink, sink_pad) = resolve_endpoint(pipeline, &link.sink, PadDirection::Sink, link.span)?;
+
+    if src
+        .link_pads(
+            src_pad.as_ref().map(String::as_str),
+            &sink,
+            sink_pad.as_ref().map(String::as_str),
+        )
+        .is_ok()
+    {
+        return Ok(());
+    }
+
+    let has_sometimes_pads = src
+        .get_pad_template_list()
+        .iter()
+        .any(|templ| templ.get_property_presence() == PadPresence::Sometimes);
+    if !has_sometimes_pads {
+        return Err(LaunchError::new(
+            ParseError::Link,
+            format!(
+                "could not link '{}' to '{}'",
+                src.get_name(),
+                sink.get_name()
+            ),
+            link.span,
+        ));
+    }
+
+    src.connect_pad_added(move |src, pad| {
+        let pad_name = pad.get_name();
+        if src_pad.as_ref().map_or(true, |name| *name == pad_name) {
+            if src
+                .link_pads(
+                    pad_name.as_str(),
+                    &sink,
+                    sink_pad.as_ref().map(String::as_str),
+                )
+                .is_err()
+            {
+                gst_warning!(
+                    CAT,
+                    obj: src,
+                    "Failed to link pad '{}' to '{}'",
+                    pad_name,
+                    sink.get_name()
+                );
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let description = parse(
+            "fakesrc num-buffers=10 name=\"my src\" ! video/x-raw,width=(int)320 ! tee name=t \
+             t. ! queue ! fakesink t.src_1 ! bin.( identity ) ! fakesink",
+        )
+        .unwrap();
+
+        assert_eq!(description.chains.len(), 3);
+        let chain = &description.chains[0];
+        assert_eq!(chain.nodes.len(), 3);
+        match chain.nodes[0] {
+            Node::Element(ref element) => {
+                assert_eq!(element.factory, "fakesrc");
+                assert_eq!(element.properties.len(), 2);
+                assert_eq!(element.properties[1].name, "name");
+                assert_eq!(element.properties[1].value, "my src");
+                assert_eq!(element.span, Span::new(0, 36));
+            }
+            _ => panic!("Expected an element"),
+        }
+        match chain.nodes[1] {
+            Node::Caps(ref caps) => assert_eq!(caps.caps, "video/x-raw,width=(int)320"),
+            _ => panic!("Expected caps"),
+        }
+
+        let chain = &description.chains[2];
+        assert_eq!(chain.nodes.len(), 3);
+        match chain.nodes[0] {
+            Node::PadRef(ref pad_ref) => {
+                assert_eq!(pad_ref.element, "t");
+                assert_eq!(pad_ref.pad, Some("src_1".to_owned()));
+            }
+            _ => panic!("Expected a pad reference"),
+        }
+        match chain.nodes[1] {
+            Node::Bin(ref bin) => {
+                assert_eq!(bin.factory, Some("bin".to_owned()));
+                assert_eq!(bin.chains.len(), 1);
+            }
+            _ => panic!("Expected a bin"),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let s = "fakesrc name=\"my src\" ! \"video/x-raw, width=(int)320\" ! tee name=t \
+                 t. ! queue ! fakesink ( name=b identity ! fakesink ) bin.( identity )";
+        let description = parse(s).unwrap();
+        assert_eq!(description.to_string(), s);
+
+        let description_de = parse(&description.to_string()).unwrap();
+        assert_eq!(description_de, description);
+
+        let description = parse("fakesrc  !video/x-raw,width=(int)320!fakesink").unwrap();
+        assert_eq!(
+            description.to_string(),
+            "fakesrc ! \"video/x-raw,width=(int)320\" ! fakesink"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = parse("fakesrc ! ").unwrap_err();
+        assert_eq!(err.kind(), ParseError::Syntax);
+        assert_eq!(err.span(), Span::new(8, 9));
+
+        let err = parse("fakesrc name=\"foo").unwrap_err();
+        assert_eq!(err.kind(), ParseError::Syntax);
+        assert_eq!(err.span(), Span::new(13, 17));
+
+        let err = parse("fakesrc ! ( )").unwrap_err();
+        assert_eq!(err.kind(), ParseError::EmptyBin);
+        assert_eq!(err.span(), Span::new(10, 13));
+
+        let err = parse("fakesrc ! ( fakesink").unwrap_err();
+        assert_eq!(err.kind(), ParseError::Syntax);
+        assert_eq!(err.span(), Span::new(10, 20));
+
+        let err = parse("video/x-raw ! fakesink").unwrap_err();
+        assert_eq!(err.span(), Span::new(0, 11));
+
+        let err = parse("  ").unwrap_err();
+        assert_eq!(err.kind(), ParseError::Empty);
+    }
+
+    #[test]
+    fn test_build() {
+        ::init().unwrap();
+
+        let pipeline = parse(
+            "fakesrc name=src num-buffers=1 ! \"video/x-raw\" ! tee name=t \
+             t. ! queue ! fakesink ( name=b identity name=id )",
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let src = pipeline.get_by_name("src").unwrap();
+        assert_eq!(
+            src.get_property("num-buffers").unwrap().get::<i32>(),
+            Some(1)
+        );
+        assert!(pipeline.get_by_name("b").unwrap().is::<Bin>());
+        assert!(pipeline.get_by_name("id").is_some());
+
+        let pipeline = parse("fakesrc name=src ! ( name=b identity ) ! fakesink name=sink")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let bin = pipeline.get_by_name("b").unwrap();
+        let sink_pad = bin.get_static_pad("sink").unwrap();
+        let src_pad = bin.get_static_pad("src").unwrap();
+        assert!(sink_pad.is::<::GhostPad>());
+        assert!(src_pad.is::<::GhostPad>());
+        assert_eq!(
+            sink_pad.get_peer().unwrap().get_parent_element(),
+            pipeline.get_by_name("src")
+        );
+        assert_eq!(
+            src_pad.get_peer().unwrap().get_parent_element(),
+            pipeline.get_by_name("sink")
+        );
+
+        let err = parse("fakesrc ! doesnotexist")
+            .unwrap()
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), ParseError::NoSuchElement);
+        assert_eq!(err.span(), Span::new(10, 22));
+
+        let err = parse("fakesrc foo=1").unwrap().build().unwrap_err();
+        assert_eq!(err.kind(), ParseError::NoSuchProperty);
+        assert_eq!(err.span(), Span::new(8, 13));
+
+        let err = parse("fakesrc num-buffers=foo")
+            .unwrap()
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), ParseError::CouldNotSetProperty);
+
+        let err = parse("fakesrc ! missing.").unwrap().build().unwrap_err();
+        assert_eq!(err.kind(), ParseError::NoSuchElement);
+        assert_eq!(err.span(), Span::new(10, 18));
+    }
+}
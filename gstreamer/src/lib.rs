@@ -16,21 +16,32 @@ extern crate cfg_if;
 extern crate lazy_static;
 extern crate libc;
 
-extern crate glib_sys as glib_ffi;
+#[doc(hidden)]
+pub extern crate glib_sys as glib_ffi;
 extern crate gobject_sys as gobject_ffi;
-extern crate gstreamer_sys as ffi;
+#[doc(hidden)]
+pub extern crate gstreamer_sys as ffi;
 
 #[macro_use]
-extern crate glib;
+#[doc(hidden)]
+pub extern crate glib;
 
 extern crate num_rational;
 
+#[cfg(any(feature = "futures", feature = "dox"))]
+extern crate futures_channel;
 #[cfg(any(feature = "futures", feature = "dox"))]
 extern crate futures_core;
+#[cfg(all(test, feature = "futures"))]
+extern crate futures_executor;
 
 extern crate muldiv;
 
+#[cfg(any(feature = "log", feature = "dox"))]
+extern crate log as rust_log;
+
 #[cfg(feature = "ser_de")]
+#[macro_use]
 extern crate serde;
 #[cfg(feature = "ser_de")]
 extern crate serde_bytes;
@@ -67,6 +78,11 @@ pub use auto::*;
 mod log;
 pub use log::*;
 
+#[cfg(any(feature = "log", feature = "dox"))]
+mod log_bridge;
+#[cfg(any(feature = "log", feature = "dox"))]
+pub use log_bridge::*;
+
 #[macro_use]
 mod error;
 pub use error::*;
@@ -75,7 +91,9 @@ pub use error::*;
 pub mod miniobject;
 pub use miniobject::{GstRc, MiniObject};
 pub mod message;
-pub use message::{Message, MessageErrorDomain, MessageRef, MessageView};
+pub use message::{Message, MessageErrorDomain, MessageRef, MessageType, MessageView};
+#[cfg(feature = "ser_de")]
+mod message_serde;
 
 mod value;
 pub use value::*;
@@ -87,7 +105,12 @@ pub mod structure;
 pub use structure::{Structure, StructureRef};
 #[cfg(feature = "ser_de")]
 mod structure_serde;
+#[cfg(feature = "ser_de")]
+pub mod structure_mapping;
+#[cfg(feature = "ser_de")]
+pub use structure_mapping::StructureMappingError;
 
+#[macro_use]
 pub mod caps;
 pub use caps::{Caps, CapsRef};
 mod caps_features;
@@ -101,12 +124,19 @@ pub use caps_features::{
 mod caps_features_serde;
 
 pub mod tags;
-pub use tags::{Tag, TagList, TagListRef};
+pub use tags::{Tag, TagFlag, TagList, TagListRef};
 #[cfg(feature = "ser_de")]
 mod tags_serde;
 
 pub mod meta;
-pub use meta::{Meta, MetaAPI, MetaRef, MetaRefMut, ParentBufferMeta};
+pub use meta::{
+    CustomMeta, Meta, MetaAPI, MetaImpl, MetaRef, MetaRefMut, MetaTransform, MetaTransformOther,
+    ParentBufferMeta, ProtectionMeta,
+};
+#[cfg(any(feature = "v1_14", feature = "dox"))]
+pub use meta::ReferenceTimestampMeta;
+pub mod memory;
+pub use memory::{MapFlags, MappedMemory, Memory, MemoryFlags, MemoryMap, MemoryRef};
 pub mod buffer;
 pub use buffer::{
     Buffer, BufferMap, BufferRef, MappedBuffer, BUFFER_COPY_ALL, BUFFER_COPY_METADATA,
@@ -126,8 +156,12 @@ mod bufferlist_serde;
 
 pub mod query;
 pub use query::{Query, QueryRef, QueryView};
+#[cfg(feature = "ser_de")]
+mod query_serde;
 pub mod event;
 pub use event::{Event, EventRef, EventView, GroupId, Seqnum, GROUP_ID_INVALID, SEQNUM_INVALID};
+#[cfg(feature = "ser_de")]
+mod event_serde;
 pub mod context;
 pub use context::{Context, ContextRef};
 mod static_caps;
@@ -143,6 +177,7 @@ pub use promise::*;
 mod bin;
 mod bus;
 mod element;
+mod element_factory;
 
 // OS dependent Bus extensions (also import the other plateform mod for doc)
 #[cfg(any(feature = "v1_14", feature = "dox"))]
@@ -162,6 +197,7 @@ mod child_proxy;
 mod clock_time;
 #[cfg(feature = "ser_de")]
 mod clock_time_serde;
+mod control_source;
 mod date_time;
 #[cfg(feature = "ser_de")]
 mod date_time_serde;
@@ -176,12 +212,17 @@ mod pad;
 mod parse_context;
 mod proxy_pad;
 mod tag_setter;
+mod task;
+mod task_pool;
 pub use bin::BinExtManual;
-pub use element::{ElementExtManual, ElementMessageType, NotifyWatchId};
+pub use element::{ElementExtManual, ElementMessageType, NotifyWatchId, StateChangeWaitError};
+#[cfg(any(feature = "futures", feature = "dox"))]
+pub use element::StateChangeFuture;
 pub use element::{
     ELEMENT_METADATA_AUTHOR, ELEMENT_METADATA_DESCRIPTION, ELEMENT_METADATA_DOC_URI,
     ELEMENT_METADATA_ICON_NAME, ELEMENT_METADATA_KLASS, ELEMENT_METADATA_LONGNAME,
 };
+pub use element_factory::{ElementBuilder, ElementFactoryError};
 pub use object::GstObjectExtManual;
 
 // OS dependent Bus extensions (also import the other plateform trait for doc)
@@ -200,9 +241,11 @@ cfg_if! {
 
 pub use self::iterator::{Iterator, IteratorError, IteratorImpl};
 #[cfg(any(feature = "futures", feature = "dox"))]
-pub use bus::BusStream;
+pub use bus::{BusEosOrErrorFuture, BusStream};
+pub use bus::{BusError, BusIter};
 pub use child_proxy::ChildProxyExtManual;
 pub use clock_time::ClockTime;
+pub use control_source::ControlSourceExtManual;
 pub use device_provider::DeviceProviderExtManual;
 pub use enums::{
     ClockError, ClockSuccess, FlowError, FlowSuccess, PadLinkError, PadLinkSuccess,
@@ -212,6 +255,8 @@ pub use gobject::GObjectExtManualGst;
 pub use pad::{PadExtManual, PadProbeData, PadProbeId, PadProbeInfo};
 pub use parse_context::ParseContext;
 pub use tag_setter::TagSetterExtManual;
+pub use task::{Task, TaskExt, TaskExtManual, TaskLock, TaskLockGuard};
+pub use task_pool::{TaskPool, TaskPoolExt};
 
 mod plugin;
 #[cfg(any(feature = "v1_10", feature = "dox"))]
@@ -223,7 +268,10 @@ mod typefind;
 pub use typefind::*;
 
 pub mod format;
-pub use format::{FormattedValue, GenericFormattedValue, SpecificFormattedValue};
+pub use format::{
+    CustomFormat, FormattedValue, GenericFormattedValue, SpecificFormattedValue,
+    FORMAT_PERCENT_MAX, FORMAT_PERCENT_SCALE,
+};
 #[cfg(feature = "ser_de")]
 mod format_serde;
 
@@ -237,8 +285,19 @@ pub use toc::{Toc, TocEntry, TocEntryRef, TocRef};
 #[cfg(feature = "ser_de")]
 mod toc_serde;
 
+pub mod topology;
+pub use topology::{Topology, TopologyLink, TopologyNode, TopologyPad};
+
+pub mod launch;
+pub use launch::LaunchError;
+
 mod clock;
 pub use clock::{ClockExtManual, ClockId};
+#[cfg(any(feature = "futures", feature = "dox"))]
+pub use clock::ClockIdWaitFuture;
+
+mod allocator;
+pub use allocator::*;
 
 mod buffer_pool;
 pub use buffer_pool::*;
@@ -311,14 +370,18 @@ pub mod prelude {
         }
     }
 
+    pub use allocator::AllocatorExtManual;
     pub use buffer_pool::BufferPoolExtManual;
     pub use child_proxy::ChildProxyExtManual;
     pub use clock::ClockExtManual;
+    pub use control_source::ControlSourceExtManual;
     pub use device_provider::DeviceProviderExtManual;
     pub use gobject::GObjectExtManualGst;
     pub use object::GstObjectExtManual;
     pub use pad::PadExtManual;
     pub use tag_setter::TagSetterExtManual;
+    pub use task::{TaskExt, TaskExtManual};
+    pub use task_pool::TaskPoolExt;
     pub use value::GstValueExt;
 
     pub use miniobject::MiniObject;
@@ -330,3 +393,7 @@ pub mod prelude {
 }
 
 mod utils;
+
+#[cfg(any(feature = "subclassing", feature = "dox"))]
+#[macro_use]
+pub mod subclass;
@@ -6,17 +6,43 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use libc;
 use libc::c_char;
 use std::ffi::CStr;
 use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
 use std::ptr;
 
 use ffi;
+use glib_ffi;
 use gobject_ffi;
 
-use glib::translate::{from_glib, ToGlib, ToGlibPtr};
+use glib;
+use glib::translate::{from_glib, from_glib_none, ToGlib, ToGlibPtr};
 use glib::IsA;
 
+pub struct DebugMessage(ptr::NonNull<ffi::GstDebugMessage>);
+
+impl fmt::Debug for DebugMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("DebugMessage").field(&self.get()).finish()
+    }
+}
+
+impl DebugMessage {
+    pub fn get(&self) -> Option<&CStr> {
+        unsafe {
+            let message = ffi::gst_debug_message_get(self.0.as_ptr());
+
+            if message.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(message))
+            }
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub struct DebugCategory(ptr::NonNull<ffi::GstDebugCategory>);
 
@@ -238,9 +264,107 @@ macro_rules! gst_log_with_level(
     }};
 );
 
+#[derive(Debug, PartialEq, Eq)]
+pub struct DebugLogFunction(ptr::NonNull<libc::c_void>);
+
+unsafe impl Send for DebugLogFunction {}
+unsafe impl Sync for DebugLogFunction {}
+
+unsafe extern "C" fn log_handler<T>(
+    category: *mut ffi::GstDebugCategory,
+    level: ffi::GstDebugLevel,
+    file: *const c_char,
+    function: *const c_char,
+    line: i32,
+    object: *mut gobject_ffi::GObject,
+    message: *mut ffi::GstDebugMessage,
+    user_data: glib_ffi::gpointer,
+) where
+    T: Fn(DebugCategory, ::DebugLevel, &str, &str, u32, Option<&glib::Object>, &DebugMessage)
+        + Send
+        + Sync
+        + 'static,
+{
+    let category = DebugCategory(ptr::NonNull::new_unchecked(category));
+    let level = from_glib(level);
+    let file = if file.is_null() {
+        "".into()
+    } else {
+        CStr::from_ptr(file).to_string_lossy()
+    };
+    let function = if function.is_null() {
+        "".into()
+    } else {
+        CStr::from_ptr(function).to_string_lossy()
+    };
+    let line = line as u32;
+    // Objects are also logged from their finalize function, at which point
+    // no new reference can be taken anymore
+    let object: Option<glib::Object> = if object.is_null() || (*object).ref_count == 0 {
+        None
+    } else {
+        from_glib_none(object)
+    };
+    let message = DebugMessage(ptr::NonNull::new_unchecked(message));
+    let handler = &*(user_data as *mut T);
+    // Panics must not unwind into C. There is nowhere sensible to report them
+    // from inside a log handler, so the message is dropped in that case
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        (handler)(
+            category,
+            level,
+            &file,
+            &function,
+            line,
+            object.as_ref(),
+            &message,
+        )
+    }));
+}
+
+unsafe extern "C" fn log_handler_data_free<T>(data: glib_ffi::gpointer) {
+    let data = Box::from_raw(data as *mut T);
+    drop(data);
+}
+
+pub fn debug_add_log_function<T>(function: T) -> DebugLogFunction
+where
+    T: Fn(DebugCategory, ::DebugLevel, &str, &str, u32, Option<&glib::Object>, &DebugMessage)
+        + Send
+        + Sync
+        + 'static,
+{
+    skip_assert_initialized!();
+    unsafe {
+        let user_data = Box::new(function);
+        let user_data_ptr = Box::into_raw(user_data) as glib_ffi::gpointer;
+        ffi::gst_debug_add_log_function(
+            Some(log_handler::<T>),
+            user_data_ptr,
+            Some(log_handler_data_free::<T>),
+        );
+        DebugLogFunction(ptr::NonNull::new_unchecked(user_data_ptr))
+    }
+}
+
+pub fn debug_remove_default_log_function() {
+    skip_assert_initialized!();
+    unsafe {
+        ffi::gst_debug_remove_log_function(Some(ffi::gst_debug_log_default));
+    }
+}
+
+pub fn debug_remove_log_function(log_fn: DebugLogFunction) {
+    skip_assert_initialized!();
+    unsafe {
+        ffi::gst_debug_remove_log_function_by_data(log_fn.0.as_ptr());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn get_existing() {
@@ -278,4 +402,73 @@ mod tests {
         gst_trace!(cat, obj: &obj, "meh");
         gst_memdump!(cat, obj: &obj, "meh");
     }
+
+    #[test]
+    fn log_function() {
+        ::init().unwrap();
+
+        let cat = DebugCategory::new(
+            "test-cat-log",
+            ::DebugColorFlags::empty(),
+            "some debug category",
+        );
+        cat.set_threshold(::DebugLevel::Info);
+
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        let messages_clone = messages.clone();
+        let log_fn = debug_add_log_function(
+            move |category, level, _file, _function, _line, object, message| {
+                if category.get_name() != "test-cat-log" {
+                    return;
+                }
+
+                messages_clone.lock().unwrap().push((
+                    level,
+                    object.is_some(),
+                    message.get().unwrap().to_str().unwrap().to_string(),
+                ));
+            },
+        );
+
+        let obj = ::Bin::new("meh");
+        gst_info!(cat, obj: &obj, "meh {}", 1);
+        gst_error!(cat, "meh {}", 2);
+        gst_debug!(cat, "not logged");
+
+        debug_remove_log_function(log_fn);
+
+        gst_error!(cat, "meh {}", 3);
+
+        assert_eq!(
+            *messages.lock().unwrap(),
+            vec![
+                (::DebugLevel::Info, true, String::from("meh 1")),
+                (::DebugLevel::Error, false, String::from("meh 2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn log_function_panic() {
+        ::init().unwrap();
+
+        let cat = DebugCategory::new(
+            "test-cat-log-panic",
+            ::DebugColorFlags::empty(),
+            "some debug category",
+        );
+        cat.set_threshold(::DebugLevel::Info);
+
+        let log_fn = debug_add_log_function(
+            move |category, _level, _file, _function, _line, _object, _message| {
+                if category.get_name() == "test-cat-log-panic" {
+                    panic!("log handler panicked");
+                }
+            },
+        );
+
+        gst_error!(cat, "meh");
+
+        debug_remove_log_function(log_fn);
+    }
 }
@@ -0,0 +1,243 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rust_log;
+
+use glib::Cast;
+
+use std::cell::Cell;
+
+use DebugCategory;
+use DebugLevel;
+use DebugLogFunction;
+use GstObjectExt;
+use Object;
+
+fn debug_level_to_log_level(level: DebugLevel) -> Option<rust_log::Level> {
+    match level {
+        DebugLevel::Error => Some(rust_log::Level::Error),
+        DebugLevel::Warning | DebugLevel::Fixme => Some(rust_log::Level::Warn),
+        DebugLevel::Info => Some(rust_log::Level::Info),
+        DebugLevel::Debug => Some(rust_log::Level::Debug),
+        DebugLevel::Log | DebugLevel::Trace | DebugLevel::Memdump => Some(rust_log::Level::Trace),
+        _ => None,
+    }
+}
+
+fn log_level_to_debug_level(level: rust_log::Level) -> DebugLevel {
+    match level {
+        rust_log::Level::Error => DebugLevel::Error,
+        rust_log::Level::Warn => DebugLevel::Warning,
+        rust_log::Level::Info => DebugLevel::Info,
+        rust_log::Level::Debug => DebugLevel::Debug,
+        rust_log::Level::Trace => DebugLevel::Trace,
+    }
+}
+
+fn log_level_filter_to_debug_level(level: rust_log::LevelFilter) -> DebugLevel {
+    match level.to_level() {
+        None => DebugLevel::None,
+        Some(level) => log_level_to_debug_level(level),
+    }
+}
+
+thread_local! {
+    static FORWARDING: Cell<bool> = Cell::new(false);
+}
+
+// Calls `func` unless this thread is already forwarding a message between
+// GStreamer and the `log` crate. Without this, forwarding in both directions
+// at once would bounce every message back and forth forever.
+fn forward<F: FnOnce()>(func: F) {
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            FORWARDING.with(|forwarding| forwarding.set(false));
+        }
+    }
+
+    if FORWARDING.with(|forwarding| forwarding.replace(true)) {
+        return;
+    }
+
+    let _guard = Guard;
+    func();
+}
+
+/// Forwards all GStreamer debug messages to the `log` crate.
+///
+/// The name of the debug category is used as target of the log records. Which
+/// messages reach the log function is still controlled by the GStreamer debug
+/// thresholds, e.g. via the `GST_DEBUG` environment variable. If no threshold
+/// was configured that way, the default threshold is set to the maximum level
+/// of the `log` crate at the time this function is called. Later changes via
+/// `log::set_max_level()` are not reflected in the GStreamer threshold and
+/// need to be applied with `debug_set_default_threshold()`.
+///
+/// The returned handle can be passed to `debug_remove_log_function()` to stop
+/// forwarding again.
+pub fn debug_forward_to_log() -> DebugLogFunction {
+    skip_assert_initialized!();
+
+    if ::std::env::var_os("GST_DEBUG").is_none() {
+        ::debug_set_default_threshold(log_level_filter_to_debug_level(rust_log::max_level()));
+    }
+
+    ::debug_add_log_function(|category, level, file, _function, line, object, message| {
+        let level = match debug_level_to_log_level(level) {
+            Some(level) => level,
+            None => return,
+        };
+
+        if level > rust_log::max_level() {
+            return;
+        }
+
+        let target = category.get_name();
+        let logger = rust_log::logger();
+        if !logger.enabled(
+            &rust_log::Metadata::builder()
+                .level(level)
+                .target(target)
+                .build(),
+        ) {
+            return;
+        }
+
+        let message = match message.get() {
+            Some(message) => message.to_string_lossy(),
+            None => return,
+        };
+
+        let object_name = object.and_then(|object| {
+            object
+                .downcast_ref::<Object>()
+                .map(|object| object.get_name())
+        });
+
+        forward(|| match object_name {
+            Some(object_name) => logger.log(
+                &rust_log::Record::builder()
+                    .args(format_args!("<{}> {}", object_name, message))
+                    .level(level)
+                    .target(target)
+                    .file(Some(file))
+                    .line(Some(line))
+                    .build(),
+            ),
+            None => logger.log(
+                &rust_log::Record::builder()
+                    .args(format_args!("{}", message))
+                    .level(level)
+                    .target(target)
+                    .file(Some(file))
+                    .line(Some(line))
+                    .build(),
+            ),
+        });
+    })
+}
+
+/// `log::Log` implementation that emits all log records into a GStreamer
+/// `DebugCategory`.
+///
+/// Whether a record is emitted is decided by the threshold of the debug
+/// category, which follows the `GST_DEBUG` environment variable and
+/// `DebugCategory::set_threshold()`.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugCategoryLogger(DebugCategory);
+
+impl DebugCategoryLogger {
+    pub fn new(category: DebugCategory) -> DebugCategoryLogger {
+        skip_assert_initialized!();
+        DebugCategoryLogger(category)
+    }
+
+    pub fn get_category(&self) -> DebugCategory {
+        self.0
+    }
+
+    /// Installs this logger as the global logger of the `log` crate.
+    ///
+    /// The maximum level of the `log` crate is set to `Trace` so that the
+    /// threshold of the debug category is the only filter that applies.
+    pub fn init(self) -> Result<(), rust_log::SetLoggerError> {
+        rust_log::set_boxed_logger(Box::new(self))?;
+        rust_log::set_max_level(rust_log::LevelFilter::Trace);
+        Ok(())
+    }
+}
+
+impl rust_log::Log for DebugCategoryLogger {
+    fn enabled(&self, metadata: &rust_log::Metadata) -> bool {
+        log_level_to_debug_level(metadata.level()) <= self.0.get_threshold()
+    }
+
+    fn log(&self, record: &rust_log::Record) {
+        forward(|| {
+            self.0.log(
+                None as Option<&Object>,
+                log_level_to_debug_level(record.level()),
+                record.file().unwrap_or(""),
+                record.module_path().unwrap_or_else(|| record.target()),
+                record.line().unwrap_or(0),
+                *record.args(),
+            )
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_log::Log;
+
+    #[test]
+    fn level_mapping() {
+        assert_eq!(
+            debug_level_to_log_level(DebugLevel::Fixme),
+            Some(rust_log::Level::Warn)
+        );
+        assert_eq!(debug_level_to_log_level(DebugLevel::None), None);
+        assert_eq!(
+            log_level_filter_to_debug_level(rust_log::LevelFilter::Off),
+            DebugLevel::None
+        );
+        assert_eq!(
+            log_level_filter_to_debug_level(rust_log::LevelFilter::Debug),
+            DebugLevel::Debug
+        );
+    }
+
+    #[test]
+    fn logger_threshold() {
+        ::init().unwrap();
+
+        let cat = DebugCategory::new(
+            "test-cat-log-bridge",
+            ::DebugColorFlags::empty(),
+            "some debug category",
+        );
+        cat.set_threshold(DebugLevel::Info);
+
+        let logger = DebugCategoryLogger::new(cat);
+        assert!(logger.enabled(
+            &rust_log::Metadata::builder()
+                .level(rust_log::Level::Warn)
+                .build()
+        ));
+        assert!(!logger.enabled(
+            &rust_log::Metadata::builder()
+                .level(rust_log::Level::Debug)
+                .build()
+        ));
+    }
+}
@@ -0,0 +1,593 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops;
+use std::ptr;
+use std::slice;
+
+use miniobject::*;
+use Allocator;
+
+use ffi;
+use glib;
+use glib::translate::{from_glib, from_glib_full, from_glib_none, FromGlib, ToGlib, ToGlibPtr};
+use glib::value::{FromValue, FromValueOptional, SetValue, Value};
+use glib::{StaticType, Type};
+use glib_ffi;
+use gobject_ffi;
+
+use buffer::{Readable, Writable};
+
+bitflags! {
+    pub struct MapFlags: u32 {
+        const READ = 1;
+        const WRITE = 2;
+        const FLAG_LAST = 65536;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for MapFlags {
+    type GlibType = ffi::GstMapFlags;
+
+    fn to_glib(&self) -> ffi::GstMapFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMapFlags> for MapFlags {
+    fn from_glib(value: ffi::GstMapFlags) -> MapFlags {
+        skip_assert_initialized!();
+        MapFlags::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for MapFlags {
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::gst_map_flags_get_type()) }
+    }
+}
+
+impl<'a> FromValueOptional<'a> for MapFlags {
+    unsafe fn from_value_optional(value: &Value) -> Option<Self> {
+        Some(FromValue::from_value(value))
+    }
+}
+
+impl<'a> FromValue<'a> for MapFlags {
+    unsafe fn from_value(value: &Value) -> Self {
+        from_glib(gobject_ffi::g_value_get_flags(value.to_glib_none().0))
+    }
+}
+
+impl SetValue for MapFlags {
+    unsafe fn set_value(value: &mut Value, this: &Self) {
+        gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, this.to_glib())
+    }
+}
+
+bitflags! {
+    pub struct MemoryFlags: u32 {
+        const READONLY = 2;
+        const NO_SHARE = 16;
+        const ZERO_PREFIXED = 32;
+        const ZERO_PADDED = 64;
+        const PHYSICALLY_CONTIGUOUS = 128;
+        const NOT_MAPPABLE = 256;
+        const LAST = 1048576;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for MemoryFlags {
+    type GlibType = ffi::GstMemoryFlags;
+
+    fn to_glib(&self) -> ffi::GstMemoryFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMemoryFlags> for MemoryFlags {
+    fn from_glib(value: ffi::GstMemoryFlags) -> MemoryFlags {
+        skip_assert_initialized!();
+        MemoryFlags::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for MemoryFlags {
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::gst_memory_flags_get_type()) }
+    }
+}
+
+impl<'a> FromValueOptional<'a> for MemoryFlags {
+    unsafe fn from_value_optional(value: &Value) -> Option<Self> {
+        Some(FromValue::from_value(value))
+    }
+}
+
+impl<'a> FromValue<'a> for MemoryFlags {
+    unsafe fn from_value(value: &Value) -> Self {
+        from_glib(gobject_ffi::g_value_get_flags(value.to_glib_none().0))
+    }
+}
+
+impl SetValue for MemoryFlags {
+    unsafe fn set_value(value: &mut Value, this: &Self) {
+        gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, this.to_glib())
+    }
+}
+
+gst_define_mini_object_wrapper!(Memory, MemoryRef, ffi::GstMemory, [Debug,], || {
+    ffi::gst_memory_get_type()
+});
+
+pub struct MemoryMap<'a, T> {
+    memory: &'a MemoryRef,
+    map_info: ffi::GstMapInfo,
+    phantom: PhantomData<T>,
+}
+
+pub struct MappedMemory<T> {
+    memory: Option<Memory>,
+    map_info: ffi::GstMapInfo,
+    phantom: PhantomData<T>,
+}
+
+impl Memory {
+    pub fn with_size(size: usize) -> Option<Self> {
+        assert_initialized_main_thread!();
+
+        unsafe {
+            from_glib_full(ffi::gst_allocator_alloc(
+                ptr::null_mut(),
+                size,
+                ptr::null_mut(),
+            ))
+        }
+    }
+
+    unsafe extern "C" fn drop_box<T>(vec: glib_ffi::gpointer) {
+        let slice: Box<T> = Box::from_raw(vec as *mut T);
+        drop(slice);
+    }
+
+    pub fn from_mut_slice<T: AsMut<[u8]> + Send + 'static>(slice: T) -> Option<Self> {
+        assert_initialized_main_thread!();
+
+        unsafe {
+            let mut b = Box::new(slice);
+            let (size, data) = {
+                let slice = (*b).as_mut();
+                (slice.len(), slice.as_mut_ptr())
+            };
+            let user_data = Box::into_raw(b);
+            from_glib_full(ffi::gst_memory_new_wrapped(
+                0,
+                data as glib_ffi::gpointer,
+                size,
+                0,
+                size,
+                user_data as glib_ffi::gpointer,
+                Some(Self::drop_box::<T>),
+            ))
+        }
+    }
+
+    pub fn from_slice<T: AsRef<[u8]> + Send + 'static>(slice: T) -> Option<Self> {
+        assert_initialized_main_thread!();
+
+        unsafe {
+            let b = Box::new(slice);
+            let (size, data) = {
+                let slice = (*b).as_ref();
+                (slice.len(), slice.as_ptr())
+            };
+            let user_data = Box::into_raw(b);
+            from_glib_full(ffi::gst_memory_new_wrapped(
+                ffi::GST_MEMORY_FLAG_READONLY,
+                data as glib_ffi::gpointer,
+                size,
+                0,
+                size,
+                user_data as glib_ffi::gpointer,
+                Some(Self::drop_box::<T>),
+            ))
+        }
+    }
+
+    pub fn into_mapped_memory_readable(self) -> Result<MappedMemory<Readable>, Self> {
+        let mut map_info: ffi::GstMapInfo = unsafe { mem::zeroed() };
+        let res: bool = unsafe {
+            from_glib(ffi::gst_memory_map(
+                self.as_mut_ptr(),
+                &mut map_info,
+                ffi::GST_MAP_READ,
+            ))
+        };
+        if res {
+            Ok(MappedMemory {
+                memory: Some(self),
+                map_info,
+                phantom: PhantomData,
+            })
+        } else {
+            Err(self)
+        }
+    }
+
+    pub fn into_mapped_memory_writable(self) -> Result<MappedMemory<Writable>, Self> {
+        let mut map_info: ffi::GstMapInfo = unsafe { mem::zeroed() };
+        let res: bool = unsafe {
+            from_glib(ffi::gst_memory_map(
+                self.as_mut_ptr(),
+                &mut map_info,
+                ffi::GST_MAP_READWRITE,
+            ))
+        };
+        if res {
+            Ok(MappedMemory {
+                memory: Some(self),
+                map_info,
+                phantom: PhantomData,
+            })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl MemoryRef {
+    pub fn get_allocator(&self) -> Option<Allocator> {
+        unsafe { from_glib_none(self.0.allocator) }
+    }
+
+    pub fn get_parent(&self) -> Option<&MemoryRef> {
+        unsafe {
+            if self.0.parent.is_null() {
+                None
+            } else {
+                Some(MemoryRef::from_ptr(self.0.parent))
+            }
+        }
+    }
+
+    pub fn get_maxsize(&self) -> usize {
+        self.0.maxsize
+    }
+
+    pub fn get_align(&self) -> usize {
+        self.0.align
+    }
+
+    pub fn get_offset(&self) -> usize {
+        self.0.offset
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.0.size
+    }
+
+    pub fn get_flags(&self) -> MemoryFlags {
+        MemoryFlags::from_bits_truncate(self.0.mini_object.flags)
+    }
+
+    pub fn resize(&mut self, offset: isize, size: usize) {
+        assert!(
+            offset >= -(self.0.offset as isize)
+                && (offset + self.0.offset as isize) as usize + size <= self.0.maxsize
+        );
+
+        unsafe { ffi::gst_memory_resize(self.as_mut_ptr(), offset, size) }
+    }
+
+    pub fn is_type(&self, mem_type: &str) -> bool {
+        unsafe {
+            from_glib(ffi::gst_memory_is_type(
+                self.as_mut_ptr(),
+                mem_type.to_glib_none().0,
+            ))
+        }
+    }
+
+    /// Checks if `self` and `other` share the same parent memory and are
+    /// directly adjacent to each other.
+    ///
+    /// Returns the offset of `self` in the parent memory if that is the case.
+    pub fn is_span(&self, other: &MemoryRef) -> Option<usize> {
+        unsafe {
+            let mut offset = 0;
+            let res: bool = from_glib(ffi::gst_memory_is_span(
+                self.as_mut_ptr(),
+                other.as_mut_ptr(),
+                &mut offset,
+            ));
+
+            if res {
+                Some(offset)
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn share(&self, offset: isize, size: Option<usize>) -> Option<Memory> {
+        let size = size.map(|s| s as isize).unwrap_or(-1);
+
+        unsafe { from_glib_full(ffi::gst_memory_share(self.as_mut_ptr(), offset, size)) }
+    }
+
+    pub fn copy_part(&self, offset: isize, size: Option<usize>) -> Option<Memory> {
+        let size = size.map(|s| s as isize).unwrap_or(-1);
+
+        unsafe { from_glib_full(ffi::gst_memory_copy(self.as_mut_ptr(), offset, size)) }
+    }
+
+    pub fn map_readable(&self) -> Option<MemoryMap<Readable>> {
+        let mut map_info: ffi::GstMapInfo = unsafe { mem::zeroed() };
+        let res =
+            unsafe { ffi::gst_memory_map(self.as_mut_ptr(), &mut map_info, ffi::GST_MAP_READ) };
+        if res == glib_ffi::GTRUE {
+            Some(MemoryMap {
+                memory: self,
+                map_info,
+                phantom: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn map_writable(&mut self) -> Option<MemoryMap<Writable>> {
+        let mut map_info: ffi::GstMapInfo = unsafe { mem::zeroed() };
+        let res = unsafe {
+            ffi::gst_memory_map(self.as_mut_ptr(), &mut map_info, ffi::GST_MAP_READWRITE)
+        };
+        if res == glib_ffi::GTRUE {
+            Some(MemoryMap {
+                memory: self,
+                map_info,
+                phantom: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Debug for MemoryRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Memory")
+            .field("ptr", unsafe { &self.as_ptr() })
+            .field("allocator", &self.get_allocator())
+            .field("parent", &self.get_parent())
+            .field("maxsize", &self.get_maxsize())
+            .field("align", &self.get_align())
+            .field("offset", &self.get_offset())
+            .field("size", &self.get_size())
+            .field("flags", &self.get_flags())
+            .finish()
+    }
+}
+
+impl<'a, T> MemoryMap<'a, T> {
+    pub fn get_size(&self) -> usize {
+        self.map_info.size
+    }
+
+    pub fn get_memory(&self) -> &MemoryRef {
+        self.memory
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.map_info.data as *const u8, self.map_info.size) }
+    }
+}
+
+impl<'a> MemoryMap<'a, Writable> {
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.map_info.data as *mut u8, self.map_info.size) }
+    }
+}
+
+impl<'a, T> AsRef<[u8]> for MemoryMap<'a, T> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<'a> AsMut<[u8]> for MemoryMap<'a, Writable> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl<'a, T> ops::Deref for MemoryMap<'a, T> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<'a> ops::DerefMut for MemoryMap<'a, Writable> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl<'a, T> fmt::Debug for MemoryMap<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MemoryMap")
+            .field(&self.get_memory())
+            .finish()
+    }
+}
+
+impl<'a, T> PartialEq for MemoryMap<'a, T> {
+    fn eq(&self, other: &MemoryMap<'a, T>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<'a, T> Eq for MemoryMap<'a, T> {}
+
+impl<'a, T> Drop for MemoryMap<'a, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::gst_memory_unmap(self.memory.as_mut_ptr(), &mut self.map_info);
+        }
+    }
+}
+
+impl<T> MappedMemory<T> {
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.map_info.data as *const u8, self.map_info.size) }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.map_info.size
+    }
+
+    pub fn get_memory(&self) -> &MemoryRef {
+        self.memory.as_ref().unwrap().as_ref()
+    }
+
+    pub fn into_memory(mut self) -> Memory {
+        let memory = self.memory.take().unwrap();
+        unsafe {
+            ffi::gst_memory_unmap(memory.as_mut_ptr(), &mut self.map_info);
+        }
+
+        memory
+    }
+}
+
+impl MappedMemory<Writable> {
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.map_info.data as *mut u8, self.map_info.size) }
+    }
+}
+
+impl<T> AsRef<[u8]> for MappedMemory<T> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsMut<[u8]> for MappedMemory<Writable> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> ops::Deref for MappedMemory<T> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl ops::DerefMut for MappedMemory<Writable> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> Drop for MappedMemory<T> {
+    fn drop(&mut self) {
+        if let Some(ref memory) = self.memory {
+            unsafe {
+                ffi::gst_memory_unmap(memory.as_mut_ptr(), &mut self.map_info);
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for MappedMemory<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MappedMemory")
+            .field(&self.get_memory())
+            .finish()
+    }
+}
+
+impl<T> PartialEq for MappedMemory<T> {
+    fn eq(&self, other: &MappedMemory<T>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<T> Eq for MappedMemory<T> {}
+
+unsafe impl<T> Send for MappedMemory<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        ::init().unwrap();
+
+        let mut memory = Memory::from_mut_slice(vec![1, 2, 3, 4]).unwrap();
+        {
+            let data = memory.map_readable().unwrap();
+            assert_eq!(data.as_slice(), &[1, 2, 3, 4]);
+        }
+
+        {
+            let memory = memory.get_mut().unwrap();
+            let mut data = memory.map_writable().unwrap();
+            data.as_mut_slice()[0] = 0;
+        }
+
+        let data = memory.into_mapped_memory_readable().unwrap();
+        assert_eq!(data.as_slice(), &[0, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_share_and_span() {
+        ::init().unwrap();
+
+        let memory = Memory::from_slice(vec![1, 2, 3, 4]).unwrap();
+        assert!(memory.get_flags().contains(MemoryFlags::READONLY));
+
+        let first = memory.share(0, Some(2)).unwrap();
+        let second = memory.share(2, None).unwrap();
+        assert_eq!(first.get_size(), 2);
+        assert_eq!(second.get_size(), 2);
+        assert_eq!(second.map_readable().unwrap().as_slice(), &[3, 4]);
+
+        assert_eq!(first.is_span(&second), Some(0));
+        assert_eq!(second.is_span(&first), None);
+
+        let copy = second.copy_part(1, None).unwrap();
+        assert_eq!(copy.map_readable().unwrap().as_slice(), &[4]);
+    }
+
+    #[test]
+    fn test_resize() {
+        ::init().unwrap();
+
+        let mut memory = Memory::with_size(8).unwrap();
+        {
+            let memory = memory.get_mut().unwrap();
+            memory.resize(2, 4);
+            assert_eq!(memory.get_offset(), 2);
+            assert_eq!(memory.get_size(), 4);
+        }
+        assert!(memory.get_maxsize() >= 8);
+    }
+}
@@ -23,7 +23,9 @@ use std::ops::Deref;
 use std::ptr;
 
 use glib;
-use glib::translate::{from_glib, from_glib_full, from_glib_none, mut_override, ToGlib, ToGlibPtr};
+use glib::translate::{
+    from_glib, from_glib_full, from_glib_none, mut_override, FromGlib, ToGlib, ToGlibPtr,
+};
 use glib::value::ToSendValue;
 use glib::Cast;
 use glib::IsA;
@@ -53,6 +55,10 @@ impl MessageRef {
         }
     }
 
+    pub fn get_type(&self) -> MessageType {
+        unsafe { from_glib((*self.as_ptr()).type_) }
+    }
+
     pub fn view(&self) -> MessageView {
         let type_ = unsafe { (*self.as_ptr()).type_ };
 
@@ -106,6 +112,152 @@ impl MessageRef {
     }
 }
 
+/// The type of a `Message`.
+///
+/// Unlike `MessageView` this does not borrow the message and can be used for
+/// filtering messages, e.g. with `Bus::timed_pop_filtered()`.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
+pub enum MessageType {
+    Eos,
+    Error,
+    Warning,
+    Info,
+    Tag,
+    Buffering,
+    StateChanged,
+    StateDirty,
+    StepDone,
+    ClockProvide,
+    ClockLost,
+    NewClock,
+    StructureChange,
+    StreamStatus,
+    Application,
+    Element,
+    SegmentStart,
+    SegmentDone,
+    DurationChanged,
+    Latency,
+    AsyncStart,
+    AsyncDone,
+    RequestState,
+    StepStart,
+    Qos,
+    Progress,
+    Toc,
+    ResetTime,
+    StreamStart,
+    NeedContext,
+    HaveContext,
+    DeviceAdded,
+    DeviceRemoved,
+    PropertyNotify,
+    StreamCollection,
+    StreamsSelected,
+    #[doc(hidden)]
+    __Unknown(u32),
+}
+
+impl MessageType {
+    pub(crate) fn to_glib_mask(types: &[MessageType]) -> ffi::GstMessageType {
+        types.iter().fold(0, |mask, type_| mask | type_.to_glib())
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for MessageType {
+    type GlibType = ffi::GstMessageType;
+
+    fn to_glib(&self) -> ffi::GstMessageType {
+        match *self {
+            MessageType::Eos => ffi::GST_MESSAGE_EOS,
+            MessageType::Error => ffi::GST_MESSAGE_ERROR,
+            MessageType::Warning => ffi::GST_MESSAGE_WARNING,
+            MessageType::Info => ffi::GST_MESSAGE_INFO,
+            MessageType::Tag => ffi::GST_MESSAGE_TAG,
+            MessageType::Buffering => ffi::GST_MESSAGE_BUFFERING,
+            MessageType::StateChanged => ffi::GST_MESSAGE_STATE_CHANGED,
+            MessageType::StateDirty => ffi::GST_MESSAGE_STATE_DIRTY,
+            MessageType::StepDone => ffi::GST_MESSAGE_STEP_DONE,
+            MessageType::ClockProvide => ffi::GST_MESSAGE_CLOCK_PROVIDE,
+            MessageType::ClockLost => ffi::GST_MESSAGE_CLOCK_LOST,
+            MessageType::NewClock => ffi::GST_MESSAGE_NEW_CLOCK,
+            MessageType::StructureChange => ffi::GST_MESSAGE_STRUCTURE_CHANGE,
+            MessageType::StreamStatus => ffi::GST_MESSAGE_STREAM_STATUS,
+            MessageType::Application => ffi::GST_MESSAGE_APPLICATION,
+            MessageType::Element => ffi::GST_MESSAGE_ELEMENT,
+            MessageType::SegmentStart => ffi::GST_MESSAGE_SEGMENT_START,
+            MessageType::SegmentDone => ffi::GST_MESSAGE_SEGMENT_DONE,
+            MessageType::DurationChanged => ffi::GST_MESSAGE_DURATION_CHANGED,
+            MessageType::Latency => ffi::GST_MESSAGE_LATENCY,
+            MessageType::AsyncStart => ffi::GST_MESSAGE_ASYNC_START,
+            MessageType::AsyncDone => ffi::GST_MESSAGE_ASYNC_DONE,
+            MessageType::RequestState => ffi::GST_MESSAGE_REQUEST_STATE,
+            MessageType::StepStart => ffi::GST_MESSAGE_STEP_START,
+            MessageType::Qos => ffi::GST_MESSAGE_QOS,
+            MessageType::Progress => ffi::GST_MESSAGE_PROGRESS,
+            MessageType::Toc => ffi::GST_MESSAGE_TOC,
+            MessageType::ResetTime => ffi::GST_MESSAGE_RESET_TIME,
+            MessageType::StreamStart => ffi::GST_MESSAGE_STREAM_START,
+            MessageType::NeedContext => ffi::GST_MESSAGE_NEED_CONTEXT,
+            MessageType::HaveContext => ffi::GST_MESSAGE_HAVE_CONTEXT,
+            MessageType::DeviceAdded => ffi::GST_MESSAGE_DEVICE_ADDED,
+            MessageType::DeviceRemoved => ffi::GST_MESSAGE_DEVICE_REMOVED,
+            MessageType::PropertyNotify => ffi::GST_MESSAGE_PROPERTY_NOTIFY,
+            MessageType::StreamCollection => ffi::GST_MESSAGE_STREAM_COLLECTION,
+            MessageType::StreamsSelected => ffi::GST_MESSAGE_STREAMS_SELECTED,
+            MessageType::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstMessageType> for MessageType {
+    fn from_glib(value: ffi::GstMessageType) -> Self {
+        skip_assert_initialized!();
+        match value {
+            ffi::GST_MESSAGE_EOS => MessageType::Eos,
+            ffi::GST_MESSAGE_ERROR => MessageType::Error,
+            ffi::GST_MESSAGE_WARNING => MessageType::Warning,
+            ffi::GST_MESSAGE_INFO => MessageType::Info,
+            ffi::GST_MESSAGE_TAG => MessageType::Tag,
+            ffi::GST_MESSAGE_BUFFERING => MessageType::Buffering,
+            ffi::GST_MESSAGE_STATE_CHANGED => MessageType::StateChanged,
+            ffi::GST_MESSAGE_STATE_DIRTY => MessageType::StateDirty,
+            ffi::GST_MESSAGE_STEP_DONE => MessageType::StepDone,
+            ffi::GST_MESSAGE_CLOCK_PROVIDE => MessageType::ClockProvide,
+            ffi::GST_MESSAGE_CLOCK_LOST => MessageType::ClockLost,
+            ffi::GST_MESSAGE_NEW_CLOCK => MessageType::NewClock,
+            ffi::GST_MESSAGE_STRUCTURE_CHANGE => MessageType::StructureChange,
+            ffi::GST_MESSAGE_STREAM_STATUS => MessageType::StreamStatus,
+            ffi::GST_MESSAGE_APPLICATION => MessageType::Application,
+            ffi::GST_MESSAGE_ELEMENT => MessageType::Element,
+            ffi::GST_MESSAGE_SEGMENT_START => MessageType::SegmentStart,
+            ffi::GST_MESSAGE_SEGMENT_DONE => MessageType::SegmentDone,
+            ffi::GST_MESSAGE_DURATION_CHANGED => MessageType::DurationChanged,
+            ffi::GST_MESSAGE_LATENCY => MessageType::Latency,
+            ffi::GST_MESSAGE_ASYNC_START => MessageType::AsyncStart,
+            ffi::GST_MESSAGE_ASYNC_DONE => MessageType::AsyncDone,
+            ffi::GST_MESSAGE_REQUEST_STATE => MessageType::RequestState,
+            ffi::GST_MESSAGE_STEP_START => MessageType::StepStart,
+            ffi::GST_MESSAGE_QOS => MessageType::Qos,
+            ffi::GST_MESSAGE_PROGRESS => MessageType::Progress,
+            ffi::GST_MESSAGE_TOC => MessageType::Toc,
+            ffi::GST_MESSAGE_RESET_TIME => MessageType::ResetTime,
+            ffi::GST_MESSAGE_STREAM_START => MessageType::StreamStart,
+            ffi::GST_MESSAGE_NEED_CONTEXT => MessageType::NeedContext,
+            ffi::GST_MESSAGE_HAVE_CONTEXT => MessageType::HaveContext,
+            ffi::GST_MESSAGE_DEVICE_ADDED => MessageType::DeviceAdded,
+            ffi::GST_MESSAGE_DEVICE_REMOVED => MessageType::DeviceRemoved,
+            ffi::GST_MESSAGE_PROPERTY_NOTIFY => MessageType::PropertyNotify,
+            ffi::GST_MESSAGE_STREAM_COLLECTION => MessageType::StreamCollection,
+            ffi::GST_MESSAGE_STREAMS_SELECTED => MessageType::StreamsSelected,
+            value => MessageType::__Unknown(value),
+        }
+    }
+}
+
 impl Message {
     pub fn new_eos<'a>() -> EosBuilder<'a> {
         assert_initialized_main_thread!();
@@ -2352,6 +2504,7 @@ mod tests {
 
         // Message without arguments
         let eos_msg = Message::new_eos().seqnum(Seqnum(1)).build();
+        assert_eq!(eos_msg.get_type(), MessageType::Eos);
         match eos_msg.view() {
             MessageView::Eos(eos_msg) => {
                 assert_eq!(eos_msg.get_seqnum(), Seqnum(1));
@@ -2362,6 +2515,7 @@ mod tests {
 
         // Message with arguments
         let buffering_msg = Message::new_buffering(42).build();
+        assert_eq!(buffering_msg.get_type(), MessageType::Buffering);
         match buffering_msg.view() {
             MessageView::Buffering(buffering_msg) => {
                 assert_eq!(buffering_msg.get_percent(), 42);
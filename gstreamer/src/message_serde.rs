@@ -0,0 +1,358 @@
+// Copyright (C) 2019 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ffi;
+use glib;
+use glib::translate::{from_glib_full, ToGlib, ToGlibPtr};
+use glib_ffi;
+use libc;
+
+use serde::de;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use std::ffi::CStr;
+use std::ptr;
+
+use GstObjectExt;
+use Message;
+use MessageRef;
+use MessageType;
+use MessageView;
+use Seqnum;
+use State;
+use Structure;
+use TagList;
+
+#[derive(Serialize, Deserialize)]
+struct ErrorFields {
+    domain: String,
+    code: i32,
+    message: String,
+}
+
+impl<'a> From<&'a glib::Error> for ErrorFields {
+    fn from(error: &'a glib::Error) -> Self {
+        unsafe {
+            let error: *const glib_ffi::GError = error.to_glib_none().0;
+
+            ErrorFields {
+                domain: CStr::from_ptr(glib_ffi::g_quark_to_string((*error).domain))
+                    .to_string_lossy()
+                    .into_owned(),
+                code: (*error).code,
+                message: CStr::from_ptr((*error).message)
+                    .to_string_lossy()
+                    .into_owned(),
+            }
+        }
+    }
+}
+
+// Messages with commonly used fields that can't be represented by the
+// `Structure` serialization get their own variant, all others are
+// serialized with their `Structure`
+#[derive(Serialize, Deserialize)]
+enum MessageFields {
+    Error {
+        error: ErrorFields,
+        debug: Option<String>,
+    },
+    Warning {
+        error: ErrorFields,
+        debug: Option<String>,
+    },
+    Info {
+        error: ErrorFields,
+        debug: Option<String>,
+    },
+    StateChanged {
+        old: State,
+        current: State,
+        pending: State,
+    },
+    Tag(TagList),
+    Buffering {
+        percent: i32,
+    },
+    Structure(Option<Structure>),
+}
+
+// The source object can't be restored when deserializing and is only
+// serialized for informational purposes
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Message")]
+struct MessageSerde {
+    type_: MessageType,
+    seqnum: u32,
+    src: Option<String>,
+    fields: MessageFields,
+}
+
+impl Serialize for MessageRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields = match self.view() {
+            MessageView::Error(ref error) => MessageFields::Error {
+                error: ErrorFields::from(&error.get_error()),
+                debug: error.get_debug(),
+            },
+            MessageView::Warning(ref warning) => MessageFields::Warning {
+                error: ErrorFields::from(&warning.get_error()),
+                debug: warning.get_debug(),
+            },
+            MessageView::Info(ref info) => MessageFields::Info {
+                error: ErrorFields::from(&info.get_error()),
+                debug: info.get_debug(),
+            },
+            MessageView::StateChanged(ref state_changed) => MessageFields::StateChanged {
+                old: state_changed.get_old(),
+                current: state_changed.get_current(),
+                pending: state_changed.get_pending(),
+            },
+            MessageView::Tag(ref tag) => MessageFields::Tag(tag.get_tags()),
+            MessageView::Buffering(ref buffering) => MessageFields::Buffering {
+                percent: buffering.get_percent(),
+            },
+            _ => MessageFields::Structure(self.get_structure().map(|s| s.to_owned())),
+        };
+
+        MessageSerde {
+            type_: self.get_type(),
+            seqnum: self.get_seqnum().0,
+            src: self.get_src().map(|src| src.get_path_string()),
+            fields,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl MessageSerde {
+    fn into_message(self) -> Result<Message, &'static str> {
+        let seqnum = Seqnum(self.seqnum);
+
+        let type_ = match self.fields {
+            MessageFields::Error { .. } => Some(MessageType::Error),
+            MessageFields::Warning { .. } => Some(MessageType::Warning),
+            MessageFields::Info { .. } => Some(MessageType::Info),
+            MessageFields::StateChanged { .. } => Some(MessageType::StateChanged),
+            MessageFields::Tag(_) => Some(MessageType::Tag),
+            MessageFields::Buffering { percent } => {
+                if percent < 0 || percent > 100 {
+                    return Err("buffering percent out of range");
+                }
+                Some(MessageType::Buffering)
+            }
+            MessageFields::Structure(_) => None,
+        };
+        if let Some(type_) = type_ {
+            if type_ != self.type_ {
+                return Err("message type does not match the message fields");
+            }
+        }
+
+        unsafe {
+            let msg = match self.fields {
+                MessageFields::Error {
+                    ref error,
+                    ref debug,
+                } => new_error_message(ffi::gst_message_new_error, error, debug),
+                MessageFields::Warning {
+                    ref error,
+                    ref debug,
+                } => new_error_message(ffi::gst_message_new_warning, error, debug),
+                MessageFields::Info {
+                    ref error,
+                    ref debug,
+                } => new_error_message(ffi::gst_message_new_info, error, debug),
+                MessageFields::StateChanged {
+                    old,
+                    current,
+                    pending,
+                } => ffi::gst_message_new_state_changed(
+                    ptr::null_mut(),
+                    old.to_glib(),
+                    current.to_glib(),
+                    pending.to_glib(),
+                ),
+                MessageFields::Tag(tags) => {
+                    ffi::gst_message_new_tag(ptr::null_mut(), tags.into_ptr())
+                }
+                MessageFields::Buffering { percent } => {
+                    ffi::gst_message_new_buffering(ptr::null_mut(), percent)
+                }
+                MessageFields::Structure(structure) => ffi::gst_message_new_custom(
+                    self.type_.to_glib(),
+                    ptr::null_mut(),
+                    structure
+                        .map(|structure| structure.into_ptr())
+                        .unwrap_or_else(ptr::null_mut),
+                ),
+            };
+            if msg.is_null() {
+                return Err("failed to create message");
+            }
+            ffi::gst_message_set_seqnum(msg, seqnum.to_glib());
+
+            Ok(from_glib_full(msg))
+        }
+    }
+}
+
+unsafe fn new_error_message(
+    func: unsafe extern "C" fn(
+        *mut ffi::GstObject,
+        *mut glib_ffi::GError,
+        *const libc::c_char,
+    ) -> *mut ffi::GstMessage,
+    error: &ErrorFields,
+    debug: &Option<String>,
+) -> *mut ffi::GstMessage {
+    let gerror = glib_ffi::g_error_new_literal(
+        glib_ffi::g_quark_from_string(error.domain.to_glib_none().0),
+        error.code,
+        error.message.to_glib_none().0,
+    );
+
+    let msg = func(ptr::null_mut(), gerror, debug.to_glib_none().0);
+    glib_ffi::g_error_free(gerror);
+
+    msg
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        MessageSerde::deserialize(deserializer)
+            .and_then(|msg_de| msg_de.into_message().map_err(de::Error::custom))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate ron;
+    extern crate serde_json;
+
+    use Bin;
+    use CoreError;
+    use Message;
+    use MessageType;
+    use MessageView;
+    use Seqnum;
+    use State;
+    use Structure;
+
+    fn roundtrip(msg: &Message) -> Message {
+        let res = ron::ser::to_string(msg).unwrap();
+        ron::de::from_str(&res).unwrap()
+    }
+
+    #[test]
+    fn test_serialize() {
+        ::init().unwrap();
+
+        let msg = Message::new_state_changed(State::Null, State::Ready, State::Paused)
+            .seqnum(Seqnum(10))
+            .build();
+
+        let res = serde_json::to_string(&msg).unwrap();
+        assert_eq!(
+            concat!(
+                "{",
+                "\"type_\":\"StateChanged\",",
+                "\"seqnum\":10,",
+                "\"src\":null,",
+                "\"fields\":{\"StateChanged\":",
+                "{\"old\":\"Null\",\"current\":\"Ready\",\"pending\":\"Paused\"}}",
+                "}"
+            ),
+            res
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_error() {
+        ::init().unwrap();
+
+        let bin = Bin::new("bin");
+        let msg = Message::new_error(CoreError::Failed, "failed")
+            .src(Some(&bin))
+            .debug("some debug")
+            .seqnum(Seqnum(42))
+            .build();
+
+        let res = serde_json::to_string(&msg).unwrap();
+        assert!(res.contains("\"src\":\"/GstBin:bin\""));
+
+        let msg_de = roundtrip(&msg);
+        assert_eq!(msg_de.get_type(), MessageType::Error);
+        assert_eq!(msg_de.get_seqnum(), Seqnum(42));
+        assert!(msg_de.get_src().is_none());
+        match msg_de.view() {
+            MessageView::Error(ref error) => {
+                assert_eq!(
+                    error.get_error().kind::<CoreError>(),
+                    Some(CoreError::Failed)
+                );
+                assert_eq!(error.get_error().to_string(), "failed");
+                assert_eq!(error.get_debug(), Some("some debug".to_owned()));
+            }
+            _ => panic!("Expected an error message"),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_custom() {
+        ::init().unwrap();
+
+        let structure = Structure::builder("custom-message")
+            .field("int", &12)
+            .build();
+        let msg = Message::new_application(structure.clone())
+            .seqnum(Seqnum(3))
+            .build();
+
+        let msg_de = roundtrip(&msg);
+        assert_eq!(msg_de.get_type(), MessageType::Application);
+        assert_eq!(msg_de.get_seqnum(), Seqnum(3));
+        assert_eq!(msg_de.get_structure(), Some(&*structure));
+    }
+
+    #[test]
+    fn test_deserialize_invalid() {
+        ::init().unwrap();
+
+        let msg_ron = r#"
+            (
+                type_: Buffering,
+                seqnum: 1,
+                src: None,
+                fields: Buffering(
+                    percent: 200,
+                ),
+            )
+        "#;
+        assert!(ron::de::from_str::<Message>(msg_ron).is_err());
+
+        let msg_ron = r#"
+            (
+                type_: Eos,
+                seqnum: 1,
+                src: None,
+                fields: Buffering(
+                    percent: 50,
+                ),
+            )
+        "#;
+        assert!(ron::de::from_str::<Message>(msg_ron).is_err());
+    }
+}
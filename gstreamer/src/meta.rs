@@ -6,16 +6,23 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
 use std::fmt;
 use std::marker::PhantomData;
 use std::ops;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::Mutex;
 
 use miniobject::MiniObject;
 use BufferRef;
+use ClockTime;
 
 use ffi;
 use glib;
-use glib::translate::{from_glib, FromGlib};
+use glib::translate::{from_glib, FromGlib, ToGlib, ToGlibPtr};
 use glib_ffi;
 
 pub unsafe trait MetaAPI: Sized {
@@ -121,6 +128,15 @@ impl<'a, T: MetaAPI> MetaRef<'a, T> {
         }
     }
 
+    pub fn has_tag(&self, tag: &str) -> bool {
+        unsafe {
+            from_glib(ffi::gst_meta_api_type_has_tag(
+                self.get_api().to_glib(),
+                glib_ffi::g_quark_from_string(tag.to_glib_none().0),
+            ))
+        }
+    }
+
     pub fn as_ptr(&self) -> *const T::GstType {
         self.meta as *const _ as *const <T as MetaAPI>::GstType
     }
@@ -244,6 +260,368 @@ impl fmt::Debug for ParentBufferMeta {
     }
 }
 
+#[cfg(any(feature = "v1_14", feature = "dox"))]
+#[repr(C)]
+pub struct ReferenceTimestampMeta(ffi::GstReferenceTimestampMeta);
+
+#[cfg(any(feature = "v1_14", feature = "dox"))]
+impl ReferenceTimestampMeta {
+    pub fn add<'a>(
+        buffer: &'a mut BufferRef,
+        reference: &::Caps,
+        timestamp: ClockTime,
+        duration: ClockTime,
+    ) -> MetaRefMut<'a, Self, Standalone> {
+        unsafe {
+            let meta = ffi::gst_buffer_add_reference_timestamp_meta(
+                buffer.as_mut_ptr(),
+                reference.as_mut_ptr(),
+                timestamp.to_glib(),
+                duration.to_glib(),
+            );
+
+            Self::from_mut_ptr(buffer, meta)
+        }
+    }
+
+    pub fn get_reference(&self) -> &::CapsRef {
+        unsafe { ::CapsRef::from_ptr(self.0.reference) }
+    }
+
+    pub fn get_timestamp(&self) -> ClockTime {
+        from_glib(self.0.timestamp)
+    }
+
+    pub fn get_duration(&self) -> ClockTime {
+        from_glib(self.0.duration)
+    }
+}
+
+#[cfg(any(feature = "v1_14", feature = "dox"))]
+unsafe impl MetaAPI for ReferenceTimestampMeta {
+    type GstType = ffi::GstReferenceTimestampMeta;
+
+    fn get_meta_api() -> glib::Type {
+        unsafe { from_glib(ffi::gst_reference_timestamp_meta_api_get_type()) }
+    }
+}
+
+#[cfg(any(feature = "v1_14", feature = "dox"))]
+impl fmt::Debug for ReferenceTimestampMeta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReferenceTimestampMeta")
+            .field("reference", &self.get_reference())
+            .field("timestamp", &self.get_timestamp())
+            .field("duration", &self.get_duration())
+            .finish()
+    }
+}
+
+#[repr(C)]
+pub struct ProtectionMeta(ffi::GstProtectionMeta);
+
+impl ProtectionMeta {
+    pub fn add(buffer: &mut BufferRef, info: ::Structure) -> MetaRefMut<Self, Standalone> {
+        unsafe {
+            let meta = ffi::gst_buffer_add_protection_meta(buffer.as_mut_ptr(), info.into_ptr());
+
+            Self::from_mut_ptr(buffer, meta)
+        }
+    }
+
+    pub fn get_info(&self) -> &::StructureRef {
+        unsafe { ::StructureRef::from_glib_borrow(self.0.info) }
+    }
+
+    pub fn get_info_mut(&mut self) -> &mut ::StructureRef {
+        unsafe { ::StructureRef::from_glib_borrow_mut(self.0.info) }
+    }
+}
+
+unsafe impl MetaAPI for ProtectionMeta {
+    type GstType = ffi::GstProtectionMeta;
+
+    fn get_meta_api() -> glib::Type {
+        unsafe { from_glib(ffi::gst_protection_meta_api_get_type()) }
+    }
+}
+
+impl fmt::Debug for ProtectionMeta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProtectionMeta")
+            .field("info", &self.get_info())
+            .finish()
+    }
+}
+
+/// Transformation that is applied to a buffer with metas.
+#[derive(Debug)]
+pub enum MetaTransform<'a> {
+    /// The buffer or a region of it is copied. `size` is `None` if everything
+    /// after `offset` is copied.
+    Copy {
+        region: bool,
+        offset: usize,
+        size: Option<usize>,
+    },
+    /// Any other transformation, identified by its name and with
+    /// transformation-specific data.
+    Other(MetaTransformOther<'a>),
+}
+
+/// Transformation other than a copy, as passed to `MetaImpl::transform()`.
+///
+/// Can only be created by GStreamer when transforming a meta, so the data is
+/// guaranteed to match the transformation's name.
+#[derive(Debug)]
+pub struct MetaTransformOther<'a> {
+    name: &'a str,
+    data: glib_ffi::gconstpointer,
+}
+
+impl<'a> MetaTransformOther<'a> {
+    pub fn get_name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Transformation-specific data, e.g. a `GstVideoMetaTransform` for
+    /// `"gst-video-scale"`.
+    pub fn get_data(&self) -> glib_ffi::gconstpointer {
+        self.data
+    }
+}
+
+/// Trait for implementing metas with a Rust payload.
+///
+/// The meta API and implementation are registered on first use and are
+/// available as `CustomMeta<T>` via `BufferRef::add_meta()`,
+/// `BufferRef::get_meta()` and `BufferRef::iter_meta()`.
+pub trait MetaImpl: Sized + Send + Sync + 'static {
+    /// Name of the meta implementation. The meta API type is registered as
+    /// `NAME` with an `API` suffix.
+    const NAME: &'static str;
+
+    /// Tags of the meta API, for example `"memory"` or `"video"`.
+    const TAGS: &'static [&'static str] = &[];
+
+    type Params;
+
+    /// Creates the meta payload when the meta is added to `buffer`.
+    fn init(buffer: &mut BufferRef, params: Self::Params) -> Option<Self>;
+
+    /// Called right before the payload is dropped and the meta is removed
+    /// from `buffer`.
+    fn free(&mut self, _buffer: &mut BufferRef) {}
+
+    /// Returns the payload of the meta that should be added to `dest` for
+    /// `transform`, or `None` if the meta can't be transformed.
+    fn transform(
+        &self,
+        dest: &mut BufferRef,
+        src: &BufferRef,
+        transform: &MetaTransform,
+    ) -> Option<Self> {
+        match *transform {
+            MetaTransform::Copy {
+                region,
+                offset,
+                size,
+            } => self.transform_copy(dest, src, region, offset, size),
+            _ => None,
+        }
+    }
+
+    fn transform_copy(
+        &self,
+        _dest: &mut BufferRef,
+        _src: &BufferRef,
+        _region: bool,
+        _offset: usize,
+        _size: Option<usize>,
+    ) -> Option<Self> {
+        None
+    }
+}
+
+#[repr(C)]
+pub struct CustomMeta<T: MetaImpl> {
+    meta: ffi::GstMeta,
+    data: T,
+}
+
+struct CustomMetaInfo {
+    api: glib::Type,
+    info: *const ffi::GstMetaInfo,
+}
+
+unsafe impl Send for CustomMetaInfo {}
+
+lazy_static! {
+    static ref CUSTOM_META_INFOS: Mutex<HashMap<TypeId, CustomMetaInfo>> =
+        Mutex::new(HashMap::new());
+}
+
+impl<T: MetaImpl> CustomMeta<T> {
+    fn get_meta_info() -> (glib::Type, *const ffi::GstMetaInfo) {
+        assert_initialized_main_thread!();
+
+        let mut infos = CUSTOM_META_INFOS.lock().unwrap();
+        let info = infos.entry(TypeId::of::<T>()).or_insert_with(|| unsafe {
+            let api_name = CString::new(format!("{}API", T::NAME)).unwrap();
+            let tags = T::TAGS
+                .iter()
+                .map(|tag| CString::new(*tag).unwrap())
+                .collect::<Vec<_>>();
+            let mut tags_ptrs = tags.iter().map(|tag| tag.as_ptr()).collect::<Vec<_>>();
+            tags_ptrs.push(ptr::null());
+
+            let api: glib::Type = from_glib(ffi::gst_meta_api_type_register(
+                api_name.as_ptr(),
+                tags_ptrs.as_mut_ptr(),
+            ));
+            assert_ne!(api, glib::Type::Invalid);
+
+            let info = ffi::gst_meta_register(
+                api.to_glib(),
+                T::NAME.to_glib_none().0,
+                ::std::mem::size_of::<CustomMeta<T>>(),
+                Some(custom_meta_init::<T>),
+                Some(custom_meta_free::<T>),
+                Some(custom_meta_transform::<T>),
+            );
+            assert!(!info.is_null());
+
+            CustomMetaInfo { api, info }
+        });
+
+        (info.api, info.info)
+    }
+
+    pub(crate) unsafe fn add(buffer: &mut BufferRef, data: T) -> *mut ffi::GstMeta {
+        let (_, info) = Self::get_meta_info();
+        let mut data = Some(data);
+
+        ffi::gst_buffer_add_meta(
+            buffer.as_mut_ptr(),
+            info,
+            &mut data as *mut Option<T> as glib_ffi::gpointer,
+        )
+    }
+}
+
+unsafe impl<T: MetaImpl> MetaAPI for CustomMeta<T> {
+    type GstType = ffi::GstMeta;
+
+    fn get_meta_api() -> glib::Type {
+        Self::get_meta_info().0
+    }
+}
+
+impl<T: MetaImpl> ops::Deref for CustomMeta<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<T: MetaImpl> ops::DerefMut for CustomMeta<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+impl<T: MetaImpl + fmt::Debug> fmt::Debug for CustomMeta<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CustomMeta")
+            .field("name", &T::NAME)
+            .field("data", &self.data)
+            .finish()
+    }
+}
+
+unsafe extern "C" fn custom_meta_init<T: MetaImpl>(
+    meta: *mut ffi::GstMeta,
+    params: glib_ffi::gpointer,
+    _buffer: *mut ffi::GstBuffer,
+) -> glib_ffi::gboolean {
+    // Metas can only be added from Rust as the payload has to be passed
+    let data = match (params as *mut Option<T>)
+        .as_mut()
+        .and_then(|data| data.take())
+    {
+        Some(data) => data,
+        None => return glib_ffi::GFALSE,
+    };
+
+    ptr::write(&mut (*(meta as *mut CustomMeta<T>)).data, data);
+
+    glib_ffi::GTRUE
+}
+
+unsafe extern "C" fn custom_meta_free<T: MetaImpl>(
+    meta: *mut ffi::GstMeta,
+    buffer: *mut ffi::GstBuffer,
+) {
+    let meta = &mut *(meta as *mut CustomMeta<T>);
+
+    // Panics must not unwind into C and there is no way to report them from
+    // here, so they are ignored. The payload is dropped in any case.
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        // The buffer might be in the process of being freed, so don't go via from_mut_ptr()
+        meta.data.free(&mut *(buffer as *mut BufferRef));
+    }));
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+        ptr::drop_in_place(&mut meta.data);
+    }));
+}
+
+unsafe extern "C" fn custom_meta_transform<T: MetaImpl>(
+    dest: *mut ffi::GstBuffer,
+    meta: *mut ffi::GstMeta,
+    src: *mut ffi::GstBuffer,
+    type_: glib_ffi::GQuark,
+    data: glib_ffi::gpointer,
+) -> glib_ffi::gboolean {
+    let meta = &*(meta as *const CustomMeta<T>);
+    let name = match CStr::from_ptr(glib_ffi::g_quark_to_string(type_)).to_str() {
+        Ok(name) => name,
+        Err(_) => return glib_ffi::GFALSE,
+    };
+
+    let transform = if name == "gst-copy" {
+        let copy = &*(data as *const ffi::GstMetaTransformCopy);
+        MetaTransform::Copy {
+            region: from_glib(copy.region),
+            offset: copy.offset,
+            size: if copy.size == usize::max_value() {
+                None
+            } else {
+                Some(copy.size)
+            },
+        }
+    } else {
+        MetaTransform::Other(MetaTransformOther { name, data })
+    };
+
+    let dest = BufferRef::from_mut_ptr(dest);
+    // Panics must not unwind into C, so treat them like a failed transformation
+    let data = panic::catch_unwind(AssertUnwindSafe(|| {
+        meta.data
+            .transform(dest, BufferRef::from_ptr(src), &transform)
+    }));
+    match data {
+        Ok(Some(data)) => {
+            if CustomMeta::add(dest, data).is_null() {
+                glib_ffi::GFALSE
+            } else {
+                glib_ffi::GTRUE
+            }
+        }
+        Ok(None) | Err(_) => glib_ffi::GFALSE,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,4 +709,155 @@ mod tests {
 
         assert!(buffer.get_meta::<ParentBufferMeta>().is_none());
     }
+
+    #[cfg(feature = "v1_14")]
+    #[test]
+    fn test_reference_timestamp_meta() {
+        ::init().unwrap();
+
+        let mut buffer = ::Buffer::new();
+        let caps = ::Caps::new_simple("timestamp/x-ntp", &[]);
+        {
+            let meta = ReferenceTimestampMeta::add(
+                buffer.get_mut().unwrap(),
+                &caps,
+                ::SECOND,
+                ::CLOCK_TIME_NONE,
+            );
+            assert_eq!(meta.get_reference(), &*caps);
+            assert_eq!(meta.get_timestamp(), ::SECOND);
+            assert_eq!(meta.get_duration(), ::CLOCK_TIME_NONE);
+        }
+
+        let meta = buffer.get_meta::<ReferenceTimestampMeta>().unwrap();
+        assert_eq!(meta.get_reference(), &*caps);
+        assert_eq!(meta.get_timestamp(), ::SECOND);
+    }
+
+    #[test]
+    fn test_protection_meta() {
+        ::init().unwrap();
+
+        let mut buffer = ::Buffer::new();
+        {
+            let mut meta = ProtectionMeta::add(
+                buffer.get_mut().unwrap(),
+                ::Structure::new("application/x-cenc", &[("iv_size", &16u32)]),
+            );
+            meta.get_info_mut().set("encrypted", &true);
+        }
+
+        let meta = buffer.get_meta::<ProtectionMeta>().unwrap();
+        assert_eq!(meta.get_info().get_name(), "application/x-cenc");
+        assert_eq!(meta.get_info().get::<u32>("iv_size"), Some(16));
+        assert_eq!(meta.get_info().get::<bool>("encrypted"), Some(true));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct TestMeta {
+        value: u32,
+    }
+
+    impl MetaImpl for TestMeta {
+        const NAME: &'static str = "TestMeta";
+        const TAGS: &'static [&'static str] = &["test"];
+
+        type Params = u32;
+
+        fn init(_buffer: &mut BufferRef, value: u32) -> Option<Self> {
+            Some(TestMeta { value })
+        }
+
+        fn transform_copy(
+            &self,
+            _dest: &mut BufferRef,
+            _src: &BufferRef,
+            region: bool,
+            _offset: usize,
+            _size: Option<usize>,
+        ) -> Option<Self> {
+            if region {
+                None
+            } else {
+                Some(TestMeta {
+                    value: self.value + 1,
+                })
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_meta() {
+        ::init().unwrap();
+
+        let mut buffer = ::Buffer::with_size(16).unwrap();
+        {
+            let mut meta = buffer.get_mut().unwrap().add_meta::<TestMeta>(1).unwrap();
+            assert_eq!(meta.value, 1);
+            meta.value = 2;
+        }
+
+        {
+            let meta = buffer.get_meta::<CustomMeta<TestMeta>>().unwrap();
+            assert_eq!(meta.value, 2);
+            assert!(meta.has_tag("test"));
+            assert!(!meta.has_tag("memory"));
+        }
+        assert_eq!(buffer.iter_meta::<Meta>().count(), 1);
+
+        let copy = buffer.copy();
+        assert_eq!(copy.get_meta::<CustomMeta<TestMeta>>().unwrap().value, 3);
+
+        let region = buffer
+            .copy_region(::BufferCopyFlags::META, 0, Some(8))
+            .unwrap();
+        assert!(region.get_meta::<CustomMeta<TestMeta>>().is_none());
+
+        buffer
+            .get_mut()
+            .unwrap()
+            .get_meta_mut::<CustomMeta<TestMeta>>()
+            .unwrap()
+            .remove();
+        assert!(buffer.get_meta::<CustomMeta<TestMeta>>().is_none());
+    }
+
+    #[derive(Debug)]
+    struct PanicMeta;
+
+    impl MetaImpl for PanicMeta {
+        const NAME: &'static str = "PanicMeta";
+
+        type Params = ();
+
+        fn init(_buffer: &mut BufferRef, _params: ()) -> Option<Self> {
+            Some(PanicMeta)
+        }
+
+        fn free(&mut self, _buffer: &mut BufferRef) {
+            panic!("free panicked");
+        }
+
+        fn transform(
+            &self,
+            _dest: &mut BufferRef,
+            _src: &BufferRef,
+            _transform: &MetaTransform,
+        ) -> Option<Self> {
+            panic!("transform panicked");
+        }
+    }
+
+    #[test]
+    fn test_custom_meta_panic() {
+        ::init().unwrap();
+
+        let mut buffer = ::Buffer::with_size(16).unwrap();
+        buffer.get_mut().unwrap().add_meta::<PanicMeta>(()).unwrap();
+
+        let copy = buffer.copy();
+        assert!(copy.get_meta::<CustomMeta<PanicMeta>>().is_none());
+
+        drop(buffer);
+    }
 }
@@ -9,11 +9,14 @@
 use glib;
 use glib::object::{Downcast, ObjectExt};
 use glib::signal::SignalHandlerId;
-use glib::translate::{from_glib_none, ToGlibPtr};
+use glib::translate::*;
 use glib::IsA;
 
+use ffi;
 use gobject_ffi;
 
+use ClockTime;
+
 pub trait GstObjectExtManual {
     fn connect_deep_notify<
         'a,
@@ -24,6 +27,20 @@ pub trait GstObjectExtManual {
         name: P,
         f: F,
     ) -> SignalHandlerId;
+
+    fn add_control_binding<P: IsA<::ControlBinding>>(
+        &self,
+        binding: &P,
+    ) -> Result<(), glib::BoolError>;
+
+    fn remove_control_binding<P: IsA<::ControlBinding>>(
+        &self,
+        binding: &P,
+    ) -> Result<(), glib::BoolError>;
+
+    fn get_control_binding(&self, property_name: &str) -> Option<::ControlBinding>;
+
+    fn get_value(&self, property_name: &str, timestamp: ClockTime) -> Option<glib::Value>;
 }
 
 impl<O: IsA<::Object> + IsA<glib::Object> + glib::value::SetValue> GstObjectExtManual for O {
@@ -63,6 +80,55 @@ impl<O: IsA<::Object> + IsA<glib::Object> + glib::value::SetValue> GstObjectExtM
         })
         .unwrap()
     }
+
+    fn add_control_binding<P: IsA<::ControlBinding>>(
+        &self,
+        binding: &P,
+    ) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::BoolError::from_glib(
+                ffi::gst_object_add_control_binding(
+                    self.to_glib_none().0,
+                    binding.to_glib_none().0,
+                ),
+                "Failed to add control binding",
+            )
+        }
+    }
+
+    fn remove_control_binding<P: IsA<::ControlBinding>>(
+        &self,
+        binding: &P,
+    ) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::BoolError::from_glib(
+                ffi::gst_object_remove_control_binding(
+                    self.to_glib_none().0,
+                    binding.to_glib_none().0,
+                ),
+                "Failed to remove control binding",
+            )
+        }
+    }
+
+    fn get_control_binding(&self, property_name: &str) -> Option<::ControlBinding> {
+        unsafe {
+            from_glib_full(ffi::gst_object_get_control_binding(
+                self.to_glib_none().0,
+                property_name.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn get_value(&self, property_name: &str, timestamp: ClockTime) -> Option<glib::Value> {
+        unsafe {
+            from_glib_full(ffi::gst_object_get_value(
+                self.to_glib_none().0,
+                property_name.to_glib_none().0,
+                timestamp.to_glib(),
+            ))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -8,10 +8,16 @@
 
 use ffi;
 use Plugin;
+use PluginFlags;
 use Structure;
 use StructureRef;
 
+use glib;
 use glib::translate::*;
+use glib_ffi;
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
 
 impl Plugin {
     pub fn get_cache_data(&self) -> Option<&StructureRef> {
@@ -30,4 +36,139 @@ impl Plugin {
             ffi::gst_plugin_set_cache_data(self.to_glib_none().0, cache_data.into_ptr());
         }
     }
+
+    pub fn get_plugin_flags(&self) -> PluginFlags {
+        unsafe {
+            let stash = self.to_glib_none();
+            let obj = &*(stash.0 as *const ffi::GstObject);
+            let _guard = ::utils::MutexGuard::lock(&obj.lock);
+            from_glib(obj.flags)
+        }
+    }
+
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn register_static<F>(
+        name: &str,
+        description: &str,
+        version: &str,
+        license: &str,
+        source: &str,
+        package: &str,
+        origin: &str,
+        plugin_init: F,
+    ) -> Result<(), glib::BoolError>
+    where
+        F: FnOnce(&Plugin) -> Result<(), glib::BoolError>,
+    {
+        assert_initialized_main_thread!();
+
+        struct InitData<F> {
+            func: Option<F>,
+            error: Option<glib::BoolError>,
+            panic: Option<Box<Any + Send + 'static>>,
+        }
+
+        unsafe extern "C" fn plugin_init_trampoline<F>(
+            plugin: *mut ffi::GstPlugin,
+            user_data: glib_ffi::gpointer,
+        ) -> glib_ffi::gboolean
+        where
+            F: FnOnce(&Plugin) -> Result<(), glib::BoolError>,
+        {
+            let data = &mut *(user_data as *mut InitData<F>);
+            let func = match data.func.take() {
+                Some(func) => func,
+                None => return glib_ffi::GFALSE,
+            };
+
+            let plugin: Plugin = from_glib_borrow(plugin);
+            match panic::catch_unwind(AssertUnwindSafe(|| func(&plugin))) {
+                Ok(Ok(())) => glib_ffi::GTRUE,
+                Ok(Err(err)) => {
+                    data.error = Some(err);
+                    glib_ffi::GFALSE
+                }
+                Err(err) => {
+                    data.panic = Some(err);
+                    glib_ffi::GFALSE
+                }
+            }
+        }
+
+        let mut data = InitData {
+            func: Some(plugin_init),
+            error: None,
+            panic: None,
+        };
+
+        let res: bool = unsafe {
+            from_glib(ffi::gst_plugin_register_static_full(
+                1,
+                8,
+                name.to_glib_none().0,
+                description.to_glib_none().0,
+                Some(plugin_init_trampoline::<F>),
+                version.to_glib_none().0,
+                license.to_glib_none().0,
+                source.to_glib_none().0,
+                package.to_glib_none().0,
+                origin.to_glib_none().0,
+                &mut data as *mut InitData<F> as glib_ffi::gpointer,
+            ))
+        };
+
+        if let Some(err) = data.panic {
+            panic::resume_unwind(err);
+        }
+
+        if res {
+            Ok(())
+        } else {
+            Err(data
+                .error
+                .unwrap_or_else(|| glib::BoolError("Failed to register plugin")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_static() {
+        ::init().unwrap();
+
+        let mut called = false;
+        Plugin::register_static(
+            "rs-test-static",
+            "Static test plugin",
+            "1.0",
+            "MIT/X11",
+            "gstreamer-rs",
+            "gstreamer-rs",
+            "https://github.com/sdroege/gstreamer-rs",
+            |_plugin| {
+                called = true;
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert!(called);
+
+        let plugin = ::Registry::get().find_plugin("rs-test-static").unwrap();
+        assert_eq!(plugin.get_description(), "Static test plugin");
+
+        let res = Plugin::register_static(
+            "rs-test-static-fail",
+            "Static test plugin",
+            "1.0",
+            "MIT/X11",
+            "gstreamer-rs",
+            "gstreamer-rs",
+            "https://github.com/sdroege/gstreamer-rs",
+            |_plugin| Err(glib::BoolError("Failed to register elements")),
+        );
+        assert!(res.is_err());
+    }
 }
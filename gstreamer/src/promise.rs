@@ -7,11 +7,21 @@
 // except according to those terms.
 
 use ffi;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use futures_channel::oneshot;
 use glib::translate::*;
 use glib_ffi;
 use gobject_ffi;
+use std::error::Error;
+use std::fmt;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::future::Future;
 use std::mem;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::pin::Pin;
 use std::ptr;
+#[cfg(any(feature = "futures", feature = "dox"))]
+use std::task::{Context, Poll};
 use PromiseResult;
 use Structure;
 use StructureRef;
@@ -106,6 +116,82 @@ impl Promise {
     }
 }
 
+#[cfg(all(
+    any(feature = "v1_14", feature = "dox"),
+    any(feature = "futures", feature = "dox")
+))]
+impl Promise {
+    /// Creates a new promise together with a future that resolves once the
+    /// promise is replied to, interrupted or expired.
+    pub fn new_future() -> (Promise, PromiseFuture) {
+        let (sender, receiver) = oneshot::channel();
+        let promise = Promise::new_with_change_func(move |promise| {
+            let res = match promise.wait() {
+                PromiseResult::Replied => Ok(promise.get_reply().map(|s| s.to_owned())),
+                PromiseResult::Interrupted => Err(PromiseError::Interrupted),
+                PromiseResult::Expired => Err(PromiseError::Expired),
+                other => Err(PromiseError::Other(other)),
+            };
+
+            let _ = sender.send(res);
+        });
+
+        (promise, PromiseFuture(receiver))
+    }
+}
+
+#[cfg(any(feature = "v1_14", feature = "dox"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PromiseError {
+    Interrupted,
+    Expired,
+    Other(PromiseResult),
+}
+
+#[cfg(any(feature = "v1_14", feature = "dox"))]
+impl fmt::Display for PromiseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Promise error: {}", self.description())
+    }
+}
+
+#[cfg(any(feature = "v1_14", feature = "dox"))]
+impl Error for PromiseError {
+    fn description(&self) -> &str {
+        match *self {
+            PromiseError::Interrupted => "The promise was interrupted",
+            PromiseError::Expired => "The promise expired",
+            PromiseError::Other(_) => "The promise was dropped without a reply",
+        }
+    }
+}
+
+/// Future returned by `Promise::new_future()`, resolving to the reply of the
+/// promise.
+#[cfg(all(
+    any(feature = "v1_14", feature = "dox"),
+    any(feature = "futures", feature = "dox")
+))]
+#[derive(Debug)]
+pub struct PromiseFuture(oneshot::Receiver<Result<Option<Structure>, PromiseError>>);
+
+#[cfg(all(
+    any(feature = "v1_14", feature = "dox"),
+    any(feature = "futures", feature = "dox")
+))]
+impl Future for PromiseFuture {
+    type Output = Result<Option<Structure>, PromiseError>;
+
+    fn poll(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
+        match Pin::new(&mut self.0).poll(ctx) {
+            Poll::Ready(Ok(res)) => Poll::Ready(res),
+            // The promise was dropped without being replied to
+            Poll::Ready(Err(_)) => Poll::Ready(Err(PromiseError::Other(PromiseResult::Pending))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
 #[cfg(any(feature = "v1_14", feature = "dox"))]
 impl Default for Promise {
     fn default() -> Self {
@@ -115,3 +201,24 @@ impl Default for Promise {
 
 unsafe impl Send for Promise {}
 unsafe impl Sync for Promise {}
+
+#[cfg(all(test, feature = "futures"))]
+mod tests {
+    use super::*;
+    use futures_executor::block_on;
+
+    #[test]
+    fn test_promise_future() {
+        ::init().unwrap();
+
+        let (promise, future) = Promise::new_future();
+        promise.reply(::Structure::new_empty("foo/bar"));
+
+        let reply = block_on(future).unwrap().unwrap();
+        assert_eq!(reply.get_name(), "foo/bar");
+
+        let (promise, future) = Promise::new_future();
+        promise.interrupt();
+        assert_eq!(block_on(future).unwrap_err(), PromiseError::Interrupted);
+    }
+}
@@ -904,6 +904,27 @@ impl<T: AsPtr> Allocation<T> {
         }
     }
 
+    pub fn get_allocation_params(&self) -> Vec<(Option<::Allocator>, ::AllocationParams)> {
+        unsafe {
+            let n = ffi::gst_query_get_n_allocation_params(self.0.as_ptr());
+            let mut params = Vec::with_capacity(n as usize);
+            for i in 0..n {
+                let mut allocator = ptr::null_mut();
+                let mut p = mem::zeroed();
+
+                ffi::gst_query_parse_nth_allocation_param(
+                    self.0.as_ptr(),
+                    i,
+                    &mut allocator,
+                    &mut p,
+                );
+                params.push((from_glib_full(allocator), p.into()));
+            }
+
+            params
+        }
+    }
+
     pub fn get_allocation_metas(&self) -> Vec<(glib::Type, Option<&::StructureRef>)> {
         unsafe {
             let n = ffi::gst_query_get_n_allocation_metas(self.0.as_ptr());
@@ -989,6 +1010,48 @@ impl<T: AsMutPtr> Allocation<T> {
         }
     }
 
+    pub fn add_allocation_param(
+        &mut self,
+        allocator: Option<&::Allocator>,
+        params: Option<&::AllocationParams>,
+    ) {
+        unsafe {
+            ffi::gst_query_add_allocation_param(
+                self.0.as_mut_ptr(),
+                allocator.to_glib_none().0,
+                match params {
+                    Some(params) => params.as_ptr(),
+                    None => ptr::null(),
+                },
+            );
+        }
+    }
+
+    pub fn set_nth_allocation_param(
+        &mut self,
+        idx: u32,
+        allocator: Option<&::Allocator>,
+        params: Option<&::AllocationParams>,
+    ) {
+        unsafe {
+            ffi::gst_query_set_nth_allocation_param(
+                self.0.as_mut_ptr(),
+                idx,
+                allocator.to_glib_none().0,
+                match params {
+                    Some(params) => params.as_ptr(),
+                    None => ptr::null(),
+                },
+            );
+        }
+    }
+
+    pub fn remove_nth_allocation_param(&mut self, idx: u32) {
+        unsafe {
+            ffi::gst_query_remove_nth_allocation_param(self.0.as_mut_ptr(), idx);
+        }
+    }
+
     pub fn add_allocation_meta<U: ::MetaAPI>(&mut self, structure: Option<&::StructureRef>) {
         unsafe {
             ffi::gst_query_add_allocation_meta(
@@ -0,0 +1,273 @@
+// Copyright (C) 2019 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ffi;
+use glib::translate::from_glib_full;
+
+use serde::de;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use std::ffi::CStr;
+use std::ptr;
+
+use miniobject::MiniObject;
+use ClockTime;
+use GenericFormattedValue;
+use Query;
+use QueryRef;
+use QueryView;
+use Structure;
+
+// There is no Rust enum for the query types, so they are serialized by
+// their name
+static QUERY_TYPES: &[ffi::GstQueryType] = &[
+    ffi::GST_QUERY_UNKNOWN,
+    ffi::GST_QUERY_POSITION,
+    ffi::GST_QUERY_DURATION,
+    ffi::GST_QUERY_LATENCY,
+    ffi::GST_QUERY_JITTER,
+    ffi::GST_QUERY_RATE,
+    ffi::GST_QUERY_SEEKING,
+    ffi::GST_QUERY_SEGMENT,
+    ffi::GST_QUERY_CONVERT,
+    ffi::GST_QUERY_FORMATS,
+    ffi::GST_QUERY_BUFFERING,
+    ffi::GST_QUERY_CUSTOM,
+    ffi::GST_QUERY_URI,
+    ffi::GST_QUERY_ALLOCATION,
+    ffi::GST_QUERY_SCHEDULING,
+    ffi::GST_QUERY_ACCEPT_CAPS,
+    ffi::GST_QUERY_CAPS,
+    ffi::GST_QUERY_DRAIN,
+    ffi::GST_QUERY_CONTEXT,
+];
+
+fn query_type_name(type_: ffi::GstQueryType) -> String {
+    unsafe {
+        CStr::from_ptr(ffi::gst_query_type_get_name(type_))
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+fn query_type_from_name(name: &str) -> Option<ffi::GstQueryType> {
+    QUERY_TYPES
+        .iter()
+        .find(|type_| query_type_name(**type_) == name)
+        .cloned()
+}
+
+// Queries with commonly used fields that can't be represented by the
+// `Structure` serialization get their own variant, all others are
+// serialized with their `Structure`
+#[derive(Serialize, Deserialize)]
+enum QueryFields {
+    Position(GenericFormattedValue),
+    Duration(GenericFormattedValue),
+    Latency {
+        live: bool,
+        min: ClockTime,
+        max: ClockTime,
+    },
+    Seeking {
+        seekable: bool,
+        start: GenericFormattedValue,
+        end: GenericFormattedValue,
+    },
+    Structure(Option<Structure>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Query")]
+struct QuerySerde {
+    type_: String,
+    fields: QueryFields,
+}
+
+impl Serialize for QueryRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields = match self.view() {
+            QueryView::Position(ref position) => QueryFields::Position(position.get_result()),
+            QueryView::Duration(ref duration) => QueryFields::Duration(duration.get_result()),
+            QueryView::Latency(ref latency) => {
+                let (live, min, max) = latency.get_result();
+                QueryFields::Latency { live, min, max }
+            }
+            QueryView::Seeking(ref seeking) => {
+                let (seekable, start, end) = seeking.get_result();
+                QueryFields::Seeking {
+                    seekable,
+                    start,
+                    end,
+                }
+            }
+            _ => QueryFields::Structure(self.get_structure().map(|s| s.to_owned())),
+        };
+
+        QuerySerde {
+            type_: query_type_name(unsafe { (*self.as_ptr()).type_ }),
+            fields,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Serialize for Query {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+impl QuerySerde {
+    fn into_query(self) -> Result<Query, String> {
+        let query = match self.fields {
+            QueryFields::Position(position) => {
+                let mut query = Query::new_position(position.get_format());
+                query.set(position);
+                query.into()
+            }
+            QueryFields::Duration(duration) => {
+                let mut query = Query::new_duration(duration.get_format());
+                query.set(duration);
+                query.into()
+            }
+            QueryFields::Latency { live, min, max } => {
+                let mut query = Query::new_latency();
+                query.set(live, min, max);
+                query.into()
+            }
+            QueryFields::Seeking {
+                seekable,
+                start,
+                end,
+            } => {
+                if start.get_format() != end.get_format() {
+                    return Err("seeking start and end have different formats".into());
+                }
+
+                let mut query = Query::new_seeking(start.get_format());
+                query.set(seekable, start, end);
+                query.into()
+            }
+            QueryFields::Structure(structure) => {
+                let type_ = query_type_from_name(&self.type_)
+                    .ok_or_else(|| format!("unknown query type {}", self.type_))?;
+
+                unsafe {
+                    from_glib_full(ffi::gst_query_new_custom(
+                        type_,
+                        structure
+                            .map(|structure| structure.into_ptr())
+                            .unwrap_or_else(ptr::null_mut),
+                    ))
+                }
+            }
+        };
+
+        Ok(query)
+    }
+}
+
+impl<'de> Deserialize<'de> for Query {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        QuerySerde::deserialize(deserializer)
+            .and_then(|query_de| query_de.into_query().map_err(de::Error::custom))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate ron;
+    extern crate serde_json;
+
+    use ClockTime;
+    use Format;
+    use GenericFormattedValue;
+    use Query;
+    use QueryView;
+    use Structure;
+
+    fn roundtrip(query: &Query) -> Query {
+        let res = ron::ser::to_string(query).unwrap();
+        ron::de::from_str(&res).unwrap()
+    }
+
+    #[test]
+    fn test_serialize() {
+        ::init().unwrap();
+
+        let mut query = Query::new_position(Format::Time);
+        query.set(ClockTime::from_seconds(1));
+
+        let res = serde_json::to_string(&query).unwrap();
+        assert_eq!(
+            "{\"type_\":\"position\",\"fields\":{\"Position\":{\"Time\":1000000000}}}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_latency() {
+        ::init().unwrap();
+
+        let mut query = Query::new_latency();
+        query.set(true, ClockTime::from_mseconds(10), ClockTime::none());
+        let query: Query = query.into();
+
+        let query_de = roundtrip(&query);
+        match query_de.view() {
+            QueryView::Latency(ref latency) => assert_eq!(
+                latency.get_result(),
+                (true, ClockTime::from_mseconds(10), ClockTime::none())
+            ),
+            _ => panic!("Expected a latency query"),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_seeking() {
+        ::init().unwrap();
+
+        let mut query = Query::new_seeking(Format::Time);
+        query.set(
+            true,
+            ClockTime::from_seconds(0),
+            ClockTime::from_seconds(10),
+        );
+        let query: Query = query.into();
+
+        let query_de = roundtrip(&query);
+        match query_de.view() {
+            QueryView::Seeking(ref seeking) => assert_eq!(
+                seeking.get_result(),
+                (
+                    true,
+                    GenericFormattedValue::Time(ClockTime::from_seconds(0)),
+                    GenericFormattedValue::Time(ClockTime::from_seconds(10))
+                )
+            ),
+            _ => panic!("Expected a seeking query"),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_custom() {
+        ::init().unwrap();
+
+        let structure = Structure::builder("custom-query").field("int", &12).build();
+        let query: Query = Query::new_custom(structure.clone()).into();
+
+        let query_de = roundtrip(&query);
+        match query_de.view() {
+            QueryView::Custom(_) => (),
+            _ => panic!("Expected a custom query"),
+        }
+        assert_eq!(query_de.get_structure(), Some(&*structure));
+    }
+}
@@ -37,9 +37,7 @@ impl Segment {
     }
 
     pub fn downcast<T: FormattedValue>(self) -> Result<FormattedSegment<T>, Self> {
-        if T::get_default_format() == Format::Undefined
-            || T::get_default_format() == self.get_format()
-        {
+        if T::is_generic() || T::get_default_format() == self.get_format() {
             Ok(FormattedSegment(self.0, PhantomData))
         } else {
             Err(self)
@@ -47,9 +45,7 @@ impl Segment {
     }
 
     pub fn downcast_ref<T: FormattedValue>(&self) -> Option<&FormattedSegment<T>> {
-        if T::get_default_format() == Format::Undefined
-            || T::get_default_format() == self.get_format()
-        {
+        if T::is_generic() || T::get_default_format() == self.get_format() {
             Some(unsafe {
                 &*(self as *const FormattedSegment<GenericFormattedValue>
                     as *const FormattedSegment<T>)
@@ -60,9 +56,7 @@ impl Segment {
     }
 
     pub fn downcast_mut<T: FormattedValue>(&mut self) -> Option<&mut FormattedSegment<T>> {
-        if T::get_default_format() == Format::Undefined
-            || T::get_default_format() == self.get_format()
-        {
+        if T::is_generic() || T::get_default_format() == self.get_format() {
             Some(unsafe {
                 &mut *(self as *mut FormattedSegment<GenericFormattedValue>
                     as *mut FormattedSegment<T>)
@@ -104,7 +98,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
         let start = start.into();
         let stop = stop.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), start.get_format());
             assert_eq!(self.get_format(), stop.get_format());
         }
@@ -145,7 +139,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
         let start = start.into();
         let stop = stop.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), start.get_format());
             assert_eq!(self.get_format(), stop.get_format());
         }
@@ -184,7 +178,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn position_from_running_time<V: Into<T>>(&self, running_time: V) -> T {
         let running_time = running_time.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), running_time.get_format());
         }
 
@@ -203,7 +197,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn position_from_running_time_full<V: Into<T>>(&self, running_time: V) -> (i32, T) {
         let running_time = running_time.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), running_time.get_format());
         }
 
@@ -222,7 +216,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn position_from_stream_time<V: Into<T>>(&self, stream_time: V) -> T {
         let stream_time = stream_time.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), stream_time.get_format());
         }
 
@@ -241,7 +235,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn position_from_stream_time_full<V: Into<T>>(&self, stream_time: V) -> (i32, T) {
         let stream_time = stream_time.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), stream_time.get_format());
         }
 
@@ -260,7 +254,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn set_running_time<V: Into<T>>(&mut self, running_time: V) -> bool {
         let running_time = running_time.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), running_time.get_format());
         }
 
@@ -276,7 +270,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn to_running_time<V: Into<T>>(&self, position: V) -> T {
         let position = position.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), position.get_format());
         }
 
@@ -295,7 +289,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn to_running_time_full<V: Into<T>>(&self, position: V) -> (i32, T) {
         let position = position.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), position.get_format());
         }
 
@@ -314,7 +308,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn to_stream_time<V: Into<T>>(&self, position: V) -> T {
         let position = position.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), position.get_format());
         }
 
@@ -333,7 +327,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn to_stream_time_full<V: Into<T>>(&self, position: V) -> (i32, T) {
         let position = position.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), position.get_format());
         }
 
@@ -388,7 +382,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn set_base<V: Into<T>>(&mut self, base: V) {
         let base = base.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), base.get_format());
         }
 
@@ -402,7 +396,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn set_offset<V: Into<T>>(&mut self, offset: V) {
         let offset = offset.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), offset.get_format());
         }
 
@@ -416,7 +410,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn set_start<V: Into<T>>(&mut self, start: V) {
         let start = start.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), start.get_format());
         }
 
@@ -430,7 +424,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn set_stop<V: Into<T>>(&mut self, stop: V) {
         let stop = stop.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), stop.get_format());
         }
 
@@ -444,7 +438,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn set_time<V: Into<T>>(&mut self, time: V) {
         let time = time.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), time.get_format());
         }
 
@@ -458,7 +452,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn set_position<V: Into<T>>(&mut self, position: V) {
         let position = position.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), position.get_format());
         }
 
@@ -472,7 +466,7 @@ impl<T: FormattedValue> FormattedSegment<T> {
     pub fn set_duration<V: Into<T>>(&mut self, duration: V) {
         let duration = duration.into();
 
-        if T::get_default_format() == Format::Undefined {
+        if T::is_generic() {
             assert_eq!(self.get_format(), duration.get_format());
         }
 
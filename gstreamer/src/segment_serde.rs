@@ -266,6 +266,26 @@ mod tests {
         assert_eq!(fmt_seg.get_duration(), ClockTime::none());
     }
 
+    #[test]
+    fn test_deserialize_formatted_percent() {
+        use format::{Percent, Time};
+        use FormattedSegment;
+
+        ::init().unwrap();
+
+        let mut segment = FormattedSegment::<Percent>::new();
+        segment.set_start(Percent(Some(100_000)));
+        segment.set_stop(Percent(None));
+        let segment_se = ron::ser::to_string(&segment).unwrap();
+
+        let fmt_seg: FormattedSegment<Percent> = ron::de::from_str(segment_se.as_str()).unwrap();
+        assert_eq!(fmt_seg.get_format(), Format::Percent);
+        assert_eq!(fmt_seg.get_start(), Percent(Some(100_000)));
+        assert_eq!(fmt_seg.get_stop(), Percent(None));
+
+        assert!(ron::de::from_str::<FormattedSegment<Time>>(segment_se.as_str()).is_err());
+    }
+
     #[test]
     fn test_serde_roundtrip() {
         ::init().unwrap();
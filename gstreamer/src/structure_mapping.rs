@@ -0,0 +1,858 @@
+// Copyright (C) 2019 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Mapping between Rust types and `Structure`s.
+//!
+//! Unlike the `Serialize` implementation of `Structure`, which describes a
+//! `Structure` in an arbitrary serde format, the functions here use serde to
+//! convert any serializable Rust struct into a `Structure` and back:
+//!
+//! * structs become `Structure`s named after the struct,
+//! * sequences and tuples become `Array`s, or `List`s with
+//!   `#[serde(with = "gst::structure_mapping::list")]`,
+//! * `Fraction`s are stored as `Fraction`s with
+//!   `#[serde(with = "gst::structure_mapping::fraction")]`, otherwise they
+//!   become `Array`s of numerator and denominator,
+//! * unit enum variants are stored as strings in the kebab-case form used for
+//!   `GEnum` nicks, e.g. `void-pending` for `VoidPending`, and can be read
+//!   from such strings or from `GEnum` values by their nick,
+//! * `None` fields are not stored in the `Structure`.
+
+use glib;
+use glib::translate::ToGlibPtr;
+use glib::value::ToSendValue;
+use glib::StaticType;
+use glib_ffi;
+use gobject_ffi;
+
+use ffi;
+
+use serde::de;
+use serde::de::{Deserialize, DeserializeSeed, Deserializer, IntoDeserializer, Visitor};
+use serde::ser;
+use serde::ser::Serialize;
+
+use std::error::Error;
+use std::ffi::CStr;
+use std::fmt;
+
+use structure;
+use Array;
+use Fraction;
+use List;
+use Structure;
+use StructureRef;
+
+const LIST_NAME: &str = "GstValueList";
+const FRACTION_NAME: &str = "GstFraction";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructureMappingError(String);
+
+impl fmt::Display for StructureMappingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Structure mapping error: {}", self.0)
+    }
+}
+
+impl Error for StructureMappingError {
+    fn description(&self) -> &str {
+        "Structure mapping error"
+    }
+}
+
+impl ser::Error for StructureMappingError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        StructureMappingError(msg.to_string())
+    }
+}
+
+impl de::Error for StructureMappingError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        StructureMappingError(msg.to_string())
+    }
+}
+
+// Converts an enum variant name into the kebab-case form used for `GEnum`
+// nicks
+fn variant_to_nick(variant: &str) -> String {
+    let mut nick = String::with_capacity(variant.len() + 4);
+    for c in variant.chars() {
+        if c == '_' {
+            nick.push('-');
+        } else if c.is_uppercase() {
+            if !nick.is_empty() && !nick.ends_with('-') {
+                nick.push('-');
+            }
+            nick.extend(c.to_lowercase());
+        } else {
+            nick.push(c);
+        }
+    }
+
+    nick
+}
+
+// Finds the enum variant for a nick, ignoring case, `-` and `_`
+fn find_variant(nick: &str, variants: &'static [&'static str]) -> Option<&'static str> {
+    fn normalize(s: &str) -> String {
+        s.chars()
+            .filter(|c| *c != '-' && *c != '_')
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    let nick = normalize(nick);
+    variants
+        .iter()
+        .find(|variant| normalize(variant) == nick)
+        .cloned()
+}
+
+fn unsupported(what: &str) -> StructureMappingError {
+    StructureMappingError(format!("{} can't be stored in a Structure", what))
+}
+
+impl Structure {
+    pub fn from_serializable<T: Serialize + ?Sized>(
+        value: &T,
+    ) -> Result<Structure, StructureMappingError> {
+        assert_initialized_main_thread!();
+
+        value
+            .serialize(ValueSerializer)?
+            .and_then(|value| {
+                if value.type_() == Structure::static_type() {
+                    value.get::<Structure>()
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| StructureMappingError("expected a struct".into()))
+    }
+}
+
+impl StructureRef {
+    pub fn to_deserializable<'a, T: Deserialize<'a>>(&'a self) -> Result<T, StructureMappingError> {
+        T::deserialize(StructureDeserializer(self))
+    }
+}
+
+/// Stores a sequence as a `List` instead of an `Array`.
+///
+/// To be used with `#[serde(with = "gst::structure_mapping::list")]`.
+pub mod list {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(super::LIST_NAME, value)
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize(deserializer)
+    }
+}
+
+/// Stores a `Fraction` as a `Fraction` instead of an `Array`.
+///
+/// To be used with `#[serde(with = "gst::structure_mapping::fraction")]`.
+pub mod fraction {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use Fraction;
+
+    pub fn serialize<S: Serializer>(value: &Fraction, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(super::FRACTION_NAME, value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Fraction, D::Error> {
+        Fraction::deserialize(deserializer)
+    }
+}
+
+// Serializes into a single value, `None` is returned for values that are
+// not stored at all
+struct ValueSerializer;
+
+impl ValueSerializer {
+    fn serialize_required<T: Serialize + ?Sized>(
+        value: &T,
+    ) -> Result<glib::SendValue, StructureMappingError> {
+        value
+            .serialize(ValueSerializer)?
+            .ok_or_else(|| unsupported("None inside a sequence"))
+    }
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Option<glib::SendValue>;
+    type Error = StructureMappingError;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = ser::Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(i32::from(v).to_send_value()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(u32::from(v).to_send_value()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_string().to_send_value()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(v.to_send_value()))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported("Bytes"))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(variant_to_nick(variant).to_send_value()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        let value = value.serialize(self)?;
+        if name == FRACTION_NAME {
+            return value
+                .as_ref()
+                .and_then(|value| value.get::<Array>())
+                .and_then(|array| match *array.as_slice() {
+                    [ref numer, ref denom] => Some((numer.get::<i32>()?, denom.get::<i32>()?)),
+                    _ => None,
+                })
+                .map(|(numer, denom)| Some(Fraction::new(numer, denom).to_send_value()))
+                .ok_or_else(|| StructureMappingError("expected a Fraction".into()));
+        }
+
+        if name != LIST_NAME {
+            return Ok(value);
+        }
+
+        match value {
+            Some(ref value) if value.type_() == Array::static_type() => {
+                let array = value.get::<Array>().unwrap();
+                Ok(Some(
+                    List::from_owned(array.as_slice().to_vec()).to_send_value(),
+                ))
+            }
+            _ => Err(StructureMappingError(
+                "expected a sequence to store as List".into(),
+            )),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(unsupported(&format!(
+            "Enum variant {} with fields",
+            variant
+        )))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SeqSerializer::new(len.unwrap_or(0)))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(SeqSerializer::new(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(SeqSerializer::new(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(unsupported(&format!(
+            "Enum variant {} with fields",
+            variant
+        )))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(unsupported("Map"))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(StructSerializer(Structure::new_empty(name)))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(unsupported(&format!(
+            "Enum variant {} with fields",
+            variant
+        )))
+    }
+}
+
+struct SeqSerializer {
+    values: Vec<glib::SendValue>,
+}
+
+impl SeqSerializer {
+    fn new(len: usize) -> Self {
+        SeqSerializer {
+            values: Vec::with_capacity(len),
+        }
+    }
+
+    fn end(self) -> Result<Option<glib::SendValue>, StructureMappingError> {
+        Ok(Some(Array::from_owned(self.values).to_send_value()))
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Option<glib::SendValue>;
+    type Error = StructureMappingError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let value = ValueSerializer::serialize_required(value)?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SeqSerializer::end(self)
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Option<glib::SendValue>;
+    type Error = StructureMappingError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let value = ValueSerializer::serialize_required(value)?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SeqSerializer::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Option<glib::SendValue>;
+    type Error = StructureMappingError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let value = ValueSerializer::serialize_required(value)?;
+        self.values.push(value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SeqSerializer::end(self)
+    }
+}
+
+struct StructSerializer(Structure);
+
+impl ser::SerializeStruct for StructSerializer {
+    type Ok = Option<glib::SendValue>;
+    type Error = StructureMappingError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        if let Some(value) = value.serialize(ValueSerializer)? {
+            self.0.set_value(key, value);
+        }
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(self.0.to_send_value()))
+    }
+}
+
+struct StructureDeserializer<'a>(&'a StructureRef);
+
+impl<'de> Deserializer<'de> for StructureDeserializer<'de> {
+    type Error = StructureMappingError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(StructureAccess {
+            iter: self.0.iter(),
+            value: None,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
+        byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct StructureAccess<'a> {
+    iter: structure::Iter<'a>,
+    value: Option<&'a glib::SendValue>,
+}
+
+impl<'de> de::MapAccess<'de> for StructureAccess<'de> {
+    type Error = StructureMappingError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.iter.next() {
+            Some((name, value)) => {
+                self.value = Some(value);
+                seed.deserialize(name.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| StructureMappingError("value requested before key".into()))?;
+        seed.deserialize(ValueDeserializer(value))
+    }
+}
+
+struct ValueDeserializer<'a>(&'a glib::Value);
+
+impl<'a> ValueDeserializer<'a> {
+    fn is_enum(&self) -> bool {
+        self.0.type_().is_a(&glib::Type::BaseEnum)
+    }
+
+    fn get_enum_nick(&self) -> Option<String> {
+        unsafe {
+            let value = self.0.to_glib_none().0;
+            let class =
+                gobject_ffi::g_type_class_ref((*value).g_type) as *mut gobject_ffi::GEnumClass;
+            let enum_value =
+                gobject_ffi::g_enum_get_value(class, gobject_ffi::g_value_get_enum(value));
+            let nick = if enum_value.is_null() {
+                None
+            } else {
+                Some(
+                    CStr::from_ptr((*enum_value).value_nick)
+                        .to_string_lossy()
+                        .into_owned(),
+                )
+            };
+            gobject_ffi::g_type_class_unref(class as glib_ffi::gpointer);
+
+            nick
+        }
+    }
+
+    fn get_values(
+        &self,
+        size: u32,
+        get_value: unsafe extern "C" fn(
+            *const gobject_ffi::GValue,
+            u32,
+        ) -> *const gobject_ffi::GValue,
+    ) -> Vec<ValueDeserializer<'a>> {
+        unsafe {
+            let value = self.0.to_glib_none().0;
+            (0..size)
+                .map(|idx| ValueDeserializer(&*(get_value(value, idx) as *const glib::Value)))
+                .collect()
+        }
+    }
+
+    fn unsupported_type(&self) -> StructureMappingError {
+        StructureMappingError(format!("unsupported value type {}", self.0.type_()))
+    }
+}
+
+impl<'de> IntoDeserializer<'de, StructureMappingError> for ValueDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = StructureMappingError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value = self.0;
+        match value.type_() {
+            glib::Type::Bool => visitor.visit_bool(value.get::<bool>().unwrap()),
+            glib::Type::I8 => visitor.visit_i8(value.get::<i8>().unwrap()),
+            glib::Type::U8 => visitor.visit_u8(value.get::<u8>().unwrap()),
+            glib::Type::I32 => visitor.visit_i32(value.get::<i32>().unwrap()),
+            glib::Type::U32 => visitor.visit_u32(value.get::<u32>().unwrap()),
+            glib::Type::I64 => visitor.visit_i64(value.get::<i64>().unwrap()),
+            glib::Type::U64 => visitor.visit_u64(value.get::<u64>().unwrap()),
+            glib::Type::F32 => visitor.visit_f32(value.get::<f32>().unwrap()),
+            glib::Type::F64 => visitor.visit_f64(value.get::<f64>().unwrap()),
+            glib::Type::String => match value.get::<&str>() {
+                Some(s) => visitor.visit_borrowed_str(s),
+                None => visitor.visit_none(),
+            },
+            type_ if type_ == Structure::static_type() => unsafe {
+                let s = ffi::gst_value_get_structure(value.to_glib_none().0);
+                if s.is_null() {
+                    visitor.visit_none()
+                } else {
+                    StructureDeserializer(StructureRef::from_glib_borrow(s))
+                        .deserialize_any(visitor)
+                }
+            },
+            type_ if type_ == Array::static_type() => {
+                let size = unsafe { ffi::gst_value_array_get_size(value.to_glib_none().0) };
+                let values = self.get_values(size, ffi::gst_value_array_get_value);
+                visitor.visit_seq(de::value::SeqDeserializer::new(values.into_iter()))
+            }
+            type_ if type_ == List::static_type() => {
+                let size = unsafe { ffi::gst_value_list_get_size(value.to_glib_none().0) };
+                let values = self.get_values(size, ffi::gst_value_list_get_value);
+                visitor.visit_seq(de::value::SeqDeserializer::new(values.into_iter()))
+            }
+            type_ if type_ == Fraction::static_type() => {
+                let fraction = value.get::<Fraction>().unwrap();
+                visitor.visit_seq(de::value::SeqDeserializer::new(
+                    vec![*fraction.numer(), *fraction.denom()].into_iter(),
+                ))
+            }
+            _ if self.is_enum() => match self.get_enum_nick() {
+                Some(nick) => visitor.visit_string(nick),
+                None => Err(StructureMappingError("invalid enum value".into())),
+            },
+            _ => Err(self.unsupported_type()),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if self.is_enum() {
+            match self
+                .get_enum_nick()
+                .and_then(|nick| find_variant(&nick, variants))
+            {
+                Some(variant) => visitor.visit_enum(variant.into_deserializer()),
+                None => Err(StructureMappingError("invalid enum value".into())),
+            }
+        } else if self.0.type_() == glib::Type::String {
+            match self.0.get::<&str>() {
+                Some(s) => {
+                    visitor.visit_enum(find_variant(s, variants).unwrap_or(s).into_deserializer())
+                }
+                None => Err(StructureMappingError("expected an enum variant".into())),
+            }
+        } else {
+            Err(self.unsupported_type())
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
+        byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use State;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "kebab-case")]
+    enum Mode {
+        Live,
+        FileSource,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(rename = "inner")]
+    struct Inner {
+        a: i32,
+        b: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(rename = "test-config")]
+    struct Config {
+        name: String,
+        count: u32,
+        ratio: f64,
+        enabled: bool,
+        #[serde(with = "::structure_mapping::fraction")]
+        framerate: Fraction,
+        aspect_ratio: Fraction,
+        mode: Mode,
+        sizes: Vec<i32>,
+        #[serde(with = "::structure_mapping::list")]
+        formats: Vec<String>,
+        inner: Inner,
+        optional: Option<i64>,
+    }
+
+    fn config() -> Config {
+        Config {
+            name: "foo".into(),
+            count: 42,
+            ratio: 0.5,
+            enabled: true,
+            framerate: Fraction::new(30, 1),
+            aspect_ratio: Fraction::new(16, 9),
+            mode: Mode::FileSource,
+            sizes: vec![1, 2, 3],
+            formats: vec!["I420".into(), "NV12".into()],
+            inner: Inner {
+                a: -1,
+                b: "bar".into(),
+            },
+            optional: None,
+        }
+    }
+
+    #[test]
+    fn test_from_serializable() {
+        ::init().unwrap();
+
+        let s = Structure::from_serializable(&config()).unwrap();
+        assert_eq!(s.get_name(), "test-config");
+        assert_eq!(s.get::<&str>("name"), Some("foo"));
+        assert_eq!(s.get::<u32>("count"), Some(42));
+        assert_eq!(s.get::<f64>("ratio"), Some(0.5));
+        assert_eq!(s.get::<bool>("enabled"), Some(true));
+        assert_eq!(s.get::<Fraction>("framerate"), Some(Fraction::new(30, 1)));
+        assert!(s.has_field_with_type("aspect_ratio", Array::static_type()));
+        assert_eq!(s.get::<&str>("mode"), Some("file-source"));
+        assert!(s.has_field_with_type("sizes", Array::static_type()));
+        assert!(s.has_field_with_type("formats", List::static_type()));
+        assert!(!s.has_field("optional"));
+
+        let inner = s.get::<Structure>("inner").unwrap();
+        assert_eq!(inner.get_name(), "inner");
+        assert_eq!(inner.get::<i32>("a"), Some(-1));
+
+        assert!(Structure::from_serializable(&42).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        ::init().unwrap();
+
+        let mut config = config();
+        config.optional = Some(7);
+
+        let s = Structure::from_serializable(&config).unwrap();
+        let config_de: Config = s.to_deserializable().unwrap();
+        assert_eq!(config_de, config);
+    }
+
+    #[test]
+    fn test_to_deserializable_enum_nick() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum TargetState {
+            Null,
+            Ready,
+            Paused,
+            Playing,
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct StateChange {
+            target: TargetState,
+            count: u16,
+        }
+
+        ::init().unwrap();
+
+        let s = Structure::builder("state-change")
+            .field("target", &State::Paused)
+            .field("count", &3i32)
+            .build();
+
+        let state_change: StateChange = s.to_deserializable().unwrap();
+        assert_eq!(
+            state_change,
+            StateChange {
+                target: TargetState::Paused,
+                count: 3,
+            }
+        );
+
+        let s = Structure::builder("state-change")
+            .field("target", &"stopped")
+            .field("count", &3i32)
+            .build();
+        assert!(s.to_deserializable::<StateChange>().is_err());
+    }
+
+    #[test]
+    fn test_roundtrip_enum() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(rename = "state-change")]
+        struct StateChange {
+            target: State,
+        }
+
+        ::init().unwrap();
+
+        let state_change = StateChange {
+            target: State::Paused,
+        };
+        let s = Structure::from_serializable(&state_change).unwrap();
+        assert_eq!(s.get::<&str>("target"), Some("paused"));
+        let state_change_de: StateChange = s.to_deserializable().unwrap();
+        assert_eq!(state_change_de, state_change);
+
+        let s = Structure::builder("state-change")
+            .field("target", &State::Paused)
+            .build();
+        let state_change_de: StateChange = s.to_deserializable().unwrap();
+        assert_eq!(state_change_de, state_change);
+
+        let s = Structure::builder("state-change")
+            .field("target", &State::VoidPending)
+            .build();
+        let state_change_de: StateChange = s.to_deserializable().unwrap();
+        assert_eq!(
+            state_change_de,
+            StateChange {
+                target: State::VoidPending,
+            }
+        );
+    }
+
+    #[test]
+    fn test_variant_to_nick() {
+        assert_eq!(variant_to_nick("Paused"), "paused");
+        assert_eq!(variant_to_nick("VoidPending"), "void-pending");
+        assert_eq!(variant_to_nick("file-source"), "file-source");
+        assert_eq!(variant_to_nick("file_source"), "file-source");
+    }
+}
@@ -0,0 +1,592 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::any::Any;
+use std::mem;
+use std::ops;
+use std::ptr;
+
+use ffi;
+use glib_ffi;
+use gobject_ffi;
+
+use glib;
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::ObjectExt;
+
+use miniobject::MiniObject;
+use AllocationParams;
+use Allocator;
+use MapFlags;
+use Memory;
+use MemoryFlags;
+use MemoryRef;
+
+use super::error::panic_to_default;
+
+// Memory layout of all memories allocated by Rust allocators.
+//
+// `ptr` is the start of the mappable data of the root memory and `data` owns
+// the storage. Shared memories only keep their parent alive and have no data.
+#[repr(C)]
+struct RustMemory {
+    memory: ffi::GstMemory,
+    ptr: *mut u8,
+    data: Option<Box<Any + Send + Sync>>,
+}
+
+pub trait AllocatorImpl: ObjectImpl + Send + Sync + 'static {
+    /// Allocates a new memory of at least `size` bytes.
+    ///
+    /// Memories must be created with `new_memory()` or
+    /// `new_memory_from_mut_slice()` of `AllocatorImplExt`.
+    fn alloc(
+        &self,
+        allocator: &Allocator,
+        size: usize,
+        params: Option<&AllocationParams>,
+    ) -> Option<Memory>;
+
+    /// Called right before the memory and its data are freed.
+    fn free(&self, _allocator: &Allocator, _memory: &mut MemoryRef) {}
+
+    fn mem_map(
+        &self,
+        allocator: &Allocator,
+        memory: &MemoryRef,
+        maxsize: usize,
+        flags: MapFlags,
+    ) -> Option<ptr::NonNull<u8>> {
+        self.parent_mem_map(allocator, memory, maxsize, flags)
+    }
+
+    fn mem_unmap(&self, allocator: &Allocator, memory: &MemoryRef) {
+        self.parent_mem_unmap(allocator, memory)
+    }
+
+    fn mem_share(
+        &self,
+        allocator: &Allocator,
+        memory: &MemoryRef,
+        offset: isize,
+        size: Option<usize>,
+    ) -> Option<Memory> {
+        self.parent_mem_share(allocator, memory, offset, size)
+    }
+}
+
+pub trait AllocatorImplExt {
+    fn parent_mem_map(
+        &self,
+        allocator: &Allocator,
+        memory: &MemoryRef,
+        maxsize: usize,
+        flags: MapFlags,
+    ) -> Option<ptr::NonNull<u8>>;
+
+    fn parent_mem_unmap(&self, allocator: &Allocator, memory: &MemoryRef);
+
+    fn parent_mem_share(
+        &self,
+        allocator: &Allocator,
+        memory: &MemoryRef,
+        offset: isize,
+        size: Option<usize>,
+    ) -> Option<Memory>;
+
+    /// Creates a new memory for `allocator` that owns `data`.
+    ///
+    /// The memory can only be mapped if `mem_map()` is implemented.
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    fn new_memory<D: Any + Send + Sync>(
+        &self,
+        allocator: &Allocator,
+        flags: MemoryFlags,
+        maxsize: usize,
+        align: usize,
+        offset: usize,
+        size: usize,
+        data: D,
+    ) -> Memory;
+
+    /// Creates a new memory for `allocator` around the bytes of `data`, which
+    /// can be mapped without implementing `mem_map()`.
+    fn new_memory_from_mut_slice<D: AsMut<[u8]> + Send + Sync + 'static>(
+        &self,
+        allocator: &Allocator,
+        flags: MemoryFlags,
+        data: D,
+    ) -> Memory;
+
+    /// Returns the data passed when creating `memory`, or the data of its
+    /// root memory if it was shared.
+    fn get_memory_data<'a, D: Any>(&self, memory: &'a MemoryRef) -> Option<&'a D>;
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+unsafe fn new_rust_memory(
+    allocator: &Allocator,
+    parent: Option<&MemoryRef>,
+    flags: MemoryFlags,
+    maxsize: usize,
+    align: usize,
+    offset: usize,
+    size: usize,
+    ptr: *mut u8,
+    data: Option<Box<Any + Send + Sync>>,
+) -> Memory {
+    let mem = Box::new(RustMemory {
+        memory: mem::zeroed(),
+        ptr,
+        data,
+    });
+    let mem = Box::into_raw(mem) as *mut ffi::GstMemory;
+
+    ffi::gst_memory_init(
+        mem,
+        flags.to_glib(),
+        allocator.to_glib_none().0,
+        parent.map(|p| p.as_mut_ptr()).unwrap_or(ptr::null_mut()),
+        maxsize,
+        align,
+        offset,
+        size,
+    );
+
+    from_glib_full(mem)
+}
+
+unsafe fn get_root_memory(memory: &MemoryRef) -> &RustMemory {
+    let mut mem = memory.as_ptr();
+    while !(*mem).parent.is_null() {
+        mem = (*mem).parent;
+    }
+
+    &*(mem as *const RustMemory)
+}
+
+impl<T: AllocatorImpl + ObjectSubclass> AllocatorImplExt for T {
+    fn parent_mem_map(
+        &self,
+        _allocator: &Allocator,
+        memory: &MemoryRef,
+        _maxsize: usize,
+        _flags: MapFlags,
+    ) -> Option<ptr::NonNull<u8>> {
+        unsafe { ptr::NonNull::new(get_root_memory(memory).ptr) }
+    }
+
+    fn parent_mem_unmap(&self, _allocator: &Allocator, _memory: &MemoryRef) {}
+
+    fn parent_mem_share(
+        &self,
+        allocator: &Allocator,
+        memory: &MemoryRef,
+        offset: isize,
+        size: Option<usize>,
+    ) -> Option<Memory> {
+        let root = memory.get_parent().unwrap_or(memory);
+        let new_offset = memory.get_offset() as isize + offset;
+        assert!(new_offset >= 0);
+        let size = size.unwrap_or_else(|| (memory.get_size() as isize - offset) as usize);
+
+        unsafe {
+            Some(new_rust_memory(
+                allocator,
+                Some(root),
+                memory.get_flags() | MemoryFlags::READONLY,
+                memory.get_maxsize(),
+                memory.get_align(),
+                new_offset as usize,
+                size,
+                get_root_memory(memory).ptr,
+                None,
+            ))
+        }
+    }
+
+    fn new_memory<D: Any + Send + Sync>(
+        &self,
+        allocator: &Allocator,
+        flags: MemoryFlags,
+        maxsize: usize,
+        align: usize,
+        offset: usize,
+        size: usize,
+        data: D,
+    ) -> Memory {
+        assert!(allocator.get_type().is_a(&T::get_type()));
+        assert!(offset + size <= maxsize);
+
+        unsafe {
+            new_rust_memory(
+                allocator,
+                None,
+                flags,
+                maxsize,
+                align,
+                offset,
+                size,
+                ptr::null_mut(),
+                Some(Box::new(data) as Box<Any + Send + Sync>),
+            )
+        }
+    }
+
+    fn new_memory_from_mut_slice<D: AsMut<[u8]> + Send + Sync + 'static>(
+        &self,
+        allocator: &Allocator,
+        flags: MemoryFlags,
+        data: D,
+    ) -> Memory {
+        assert!(allocator.get_type().is_a(&T::get_type()));
+
+        let mut data = Box::new(data);
+        let (size, ptr) = {
+            let slice = (*data).as_mut();
+            (slice.len(), slice.as_mut_ptr())
+        };
+
+        unsafe {
+            new_rust_memory(
+                allocator,
+                None,
+                flags,
+                size,
+                0,
+                0,
+                size,
+                ptr,
+                Some(data as Box<Any + Send + Sync>),
+            )
+        }
+    }
+
+    fn get_memory_data<'a, D: Any>(&self, memory: &'a MemoryRef) -> Option<&'a D> {
+        let allocator = memory.get_allocator()?;
+        if !allocator.get_type().is_a(&T::get_type()) {
+            return None;
+        }
+
+        unsafe {
+            get_root_memory(memory)
+                .data
+                .as_ref()
+                .and_then(|data| data.downcast_ref::<D>())
+        }
+    }
+}
+
+/// Rust class struct for `GstAllocator` and all its subclasses.
+#[repr(C)]
+pub struct AllocatorClass(ffi::GstAllocatorClass);
+
+unsafe impl IsClassFor for AllocatorClass {
+    type Instance = Allocator;
+}
+
+unsafe impl Send for AllocatorClass {}
+unsafe impl Sync for AllocatorClass {}
+
+impl ops::Deref for AllocatorClass {
+    type Target = glib::ObjectClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for AllocatorClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl<T: ObjectSubclass + AllocatorImpl> IsSubclassable<T> for AllocatorClass {
+    fn override_vfuncs(&mut self) {
+        <glib::ObjectClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstAllocatorClass);
+            klass.alloc = Some(allocator_alloc::<T>);
+            klass.free = Some(allocator_free::<T>);
+
+            // The memory functions are per instance and have to be set up
+            // once the allocator is constructed
+            let klass = &mut *(self as *const Self as *mut gobject_ffi::GObjectClass);
+            klass.constructed = Some(allocator_constructed::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn allocator_constructed<T: ObjectSubclass>(ptr: *mut gobject_ffi::GObject)
+where
+    T: AllocatorImpl,
+{
+    let allocator = &mut *(ptr as *mut ffi::GstAllocator);
+    allocator.mem_type = gobject_ffi::g_type_name(T::get_type().to_glib());
+    allocator.mem_map = Some(allocator_mem_map::<T>);
+    allocator.mem_unmap = Some(allocator_mem_unmap::<T>);
+    allocator.mem_share = Some(allocator_mem_share::<T>);
+
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: glib::Object = from_glib_borrow(ptr);
+    let allocator: Allocator = from_glib_borrow(ptr as *mut ffi::GstAllocator);
+
+    panic_to_default(&allocator, (), || imp.constructed(&wrap));
+}
+
+unsafe extern "C" fn allocator_alloc<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAllocator,
+    size: usize,
+    params: *mut ffi::GstAllocationParams,
+) -> *mut ffi::GstMemory
+where
+    T: AllocatorImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Allocator = from_glib_borrow(ptr);
+
+    let params = if params.is_null() {
+        None
+    } else {
+        Some(AllocationParams::from(*params))
+    };
+
+    panic_to_default(&wrap, None, || imp.alloc(&wrap, size, params.as_ref()))
+        .map(|memory| memory.into_ptr())
+        .unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "C" fn allocator_free<T: ObjectSubclass>(
+    ptr: *mut ffi::GstAllocator,
+    memory: *mut ffi::GstMemory,
+) where
+    T: AllocatorImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Allocator = from_glib_borrow(ptr);
+
+    // The reference count is already zero here, so don't go via from_mut_ptr()
+    panic_to_default(&wrap, (), || {
+        imp.free(&wrap, &mut *(memory as *mut MemoryRef))
+    });
+
+    let memory = Box::from_raw(memory as *mut RustMemory);
+    drop(memory);
+}
+
+unsafe extern "C" fn allocator_mem_map<T: ObjectSubclass>(
+    memory: *mut ffi::GstMemory,
+    maxsize: usize,
+    flags: ffi::GstMapFlags,
+) -> glib_ffi::gpointer
+where
+    T: AllocatorImpl,
+{
+    let ptr = (*memory).allocator;
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Allocator = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, None, || {
+        imp.mem_map(
+            &wrap,
+            MemoryRef::from_ptr(memory),
+            maxsize,
+            from_glib(flags),
+        )
+    })
+    .map(|data| data.as_ptr() as glib_ffi::gpointer)
+    .unwrap_or(ptr::null_mut())
+}
+
+unsafe extern "C" fn allocator_mem_unmap<T: ObjectSubclass>(memory: *mut ffi::GstMemory)
+where
+    T: AllocatorImpl,
+{
+    let ptr = (*memory).allocator;
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Allocator = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, (), || {
+        imp.mem_unmap(&wrap, MemoryRef::from_ptr(memory))
+    })
+}
+
+unsafe extern "C" fn allocator_mem_share<T: ObjectSubclass>(
+    memory: *mut ffi::GstMemory,
+    offset: isize,
+    size: isize,
+) -> *mut ffi::GstMemory
+where
+    T: AllocatorImpl,
+{
+    let ptr = (*memory).allocator;
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Allocator = from_glib_borrow(ptr);
+
+    let size = if size < 0 { None } else { Some(size as usize) };
+
+    panic_to_default(&wrap, None, || {
+        imp.mem_share(&wrap, MemoryRef::from_ptr(memory), offset, size)
+    })
+    .map(|memory| memory.into_ptr())
+    .unwrap_or(ptr::null_mut())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib::subclass;
+    use prelude::*;
+    use std::sync::atomic;
+    use Buffer;
+
+    pub mod imp {
+        use super::*;
+
+        pub struct TestAllocator {
+            pub n_allocs: atomic::AtomicUsize,
+            pub n_frees: atomic::AtomicUsize,
+            pub n_maps: atomic::AtomicUsize,
+            pub n_unmaps: atomic::AtomicUsize,
+            pub n_shares: atomic::AtomicUsize,
+        }
+
+        impl ObjectSubclass for TestAllocator {
+            const NAME: &'static str = "TestAllocator";
+            type ParentType = Allocator;
+            type Instance = subclass::simple::InstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                TestAllocator {
+                    n_allocs: atomic::AtomicUsize::new(0),
+                    n_frees: atomic::AtomicUsize::new(0),
+                    n_maps: atomic::AtomicUsize::new(0),
+                    n_unmaps: atomic::AtomicUsize::new(0),
+                    n_shares: atomic::AtomicUsize::new(0),
+                }
+            }
+        }
+
+        impl ObjectImpl for TestAllocator {
+            glib_object_impl!();
+        }
+
+        impl AllocatorImpl for TestAllocator {
+            fn alloc(
+                &self,
+                allocator: &Allocator,
+                size: usize,
+                _params: Option<&AllocationParams>,
+            ) -> Option<Memory> {
+                self.n_allocs.fetch_add(1, atomic::Ordering::SeqCst);
+                Some(self.new_memory_from_mut_slice(
+                    allocator,
+                    MemoryFlags::empty(),
+                    vec![0u8; size],
+                ))
+            }
+
+            fn free(&self, _allocator: &Allocator, _memory: &mut MemoryRef) {
+                self.n_frees.fetch_add(1, atomic::Ordering::SeqCst);
+            }
+
+            fn mem_map(
+                &self,
+                allocator: &Allocator,
+                memory: &MemoryRef,
+                maxsize: usize,
+                flags: MapFlags,
+            ) -> Option<ptr::NonNull<u8>> {
+                self.n_maps.fetch_add(1, atomic::Ordering::SeqCst);
+                self.parent_mem_map(allocator, memory, maxsize, flags)
+            }
+
+            fn mem_unmap(&self, allocator: &Allocator, memory: &MemoryRef) {
+                self.n_unmaps.fetch_add(1, atomic::Ordering::SeqCst);
+                self.parent_mem_unmap(allocator, memory)
+            }
+
+            fn mem_share(
+                &self,
+                allocator: &Allocator,
+                memory: &MemoryRef,
+                offset: isize,
+                size: Option<usize>,
+            ) -> Option<Memory> {
+                self.n_shares.fetch_add(1, atomic::Ordering::SeqCst);
+                self.parent_mem_share(allocator, memory, offset, size)
+            }
+        }
+    }
+
+    #[test]
+    fn test_allocator_subclass() {
+        ::init().unwrap();
+
+        let allocator = glib::Object::new(imp::TestAllocator::get_type(), &[])
+            .unwrap()
+            .downcast::<Allocator>()
+            .unwrap();
+        let imp = imp::TestAllocator::from_instance(&allocator);
+
+        let memory = allocator.alloc(16, None).unwrap();
+        assert_eq!(memory.get_size(), 16);
+        assert_eq!(imp.n_allocs.load(atomic::Ordering::SeqCst), 1);
+
+        let mut buffer = Buffer::new();
+        buffer.get_mut().unwrap().append_memory(memory);
+
+        {
+            let buffer = buffer.get_mut().unwrap();
+            let mut map = buffer.map_writable().unwrap();
+            for (i, b) in map.as_mut_slice().iter_mut().enumerate() {
+                *b = i as u8;
+            }
+        }
+        assert_eq!(imp.n_maps.load(atomic::Ordering::SeqCst), 1);
+        assert_eq!(imp.n_unmaps.load(atomic::Ordering::SeqCst), 1);
+
+        let sub_buffer = buffer
+            .copy_region(::BufferCopyFlags::MEMORY, 4, Some(8))
+            .unwrap();
+        assert_eq!(imp.n_shares.load(atomic::Ordering::SeqCst), 1);
+
+        {
+            let memory = sub_buffer.peek_memory(0);
+            assert!(memory.get_parent().is_some());
+            assert_eq!(
+                imp.get_memory_data::<Vec<u8>>(memory)
+                    .map(|data| data.len()),
+                Some(16)
+            );
+
+            let map = sub_buffer.map_readable().unwrap();
+            assert_eq!(map.as_slice(), &[4, 5, 6, 7, 8, 9, 10, 11]);
+        }
+        assert_eq!(imp.n_maps.load(atomic::Ordering::SeqCst), 2);
+        assert_eq!(imp.n_unmaps.load(atomic::Ordering::SeqCst), 2);
+
+        drop(buffer);
+        assert_eq!(imp.n_frees.load(atomic::Ordering::SeqCst), 0);
+        drop(sub_buffer);
+        assert_eq!(imp.n_frees.load(atomic::Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,702 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use libc::c_char;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::ops;
+use std::ptr;
+use std::sync::Mutex;
+
+use ffi;
+use glib_ffi;
+
+use glib;
+use glib::subclass::prelude::*;
+use glib::translate::*;
+use glib::ObjectExt;
+
+use miniobject::MiniObject;
+use Buffer;
+use BufferPool;
+use BufferPoolAcquireParams;
+use BufferPoolConfigRef;
+use BufferRef;
+use FlowError;
+use FlowReturn;
+
+use super::error::panic_to_default;
+
+pub trait BufferPoolImpl: ObjectImpl + Send + Sync + 'static {
+    fn acquire_buffer(
+        &self,
+        pool: &BufferPool,
+        params: Option<&BufferPoolAcquireParams>,
+    ) -> Result<Buffer, FlowError> {
+        self.parent_acquire_buffer(pool, params)
+    }
+
+    fn alloc_buffer(
+        &self,
+        pool: &BufferPool,
+        params: Option<&BufferPoolAcquireParams>,
+    ) -> Result<Buffer, FlowError> {
+        self.parent_alloc_buffer(pool, params)
+    }
+
+    fn free_buffer(&self, pool: &BufferPool, buffer: Buffer) {
+        self.parent_free_buffer(pool, buffer)
+    }
+
+    fn release_buffer(&self, pool: &BufferPool, buffer: Buffer) {
+        self.parent_release_buffer(pool, buffer)
+    }
+
+    fn reset_buffer(&self, pool: &BufferPool, buffer: &mut BufferRef) {
+        self.parent_reset_buffer(pool, buffer)
+    }
+
+    fn start(&self, pool: &BufferPool) -> bool {
+        self.parent_start(pool)
+    }
+
+    fn stop(&self, pool: &BufferPool) -> bool {
+        self.parent_stop(pool)
+    }
+
+    /// Returns the options supported by the pool.
+    ///
+    /// The options are queried only once per type and must not change afterwards.
+    fn get_options(&self, pool: &BufferPool) -> &'static [&'static str] {
+        self.parent_get_options(pool)
+    }
+
+    fn set_config(&self, pool: &BufferPool, config: &mut BufferPoolConfigRef) -> bool {
+        self.parent_set_config(pool, config)
+    }
+
+    fn flush_start(&self, pool: &BufferPool) {
+        self.parent_flush_start(pool)
+    }
+
+    fn flush_stop(&self, pool: &BufferPool) {
+        self.parent_flush_stop(pool)
+    }
+}
+
+pub trait BufferPoolImplExt {
+    fn parent_acquire_buffer(
+        &self,
+        pool: &BufferPool,
+        params: Option<&BufferPoolAcquireParams>,
+    ) -> Result<Buffer, FlowError>;
+
+    fn parent_alloc_buffer(
+        &self,
+        pool: &BufferPool,
+        params: Option<&BufferPoolAcquireParams>,
+    ) -> Result<Buffer, FlowError>;
+
+    fn parent_free_buffer(&self, pool: &BufferPool, buffer: Buffer);
+
+    fn parent_release_buffer(&self, pool: &BufferPool, buffer: Buffer);
+
+    fn parent_reset_buffer(&self, pool: &BufferPool, buffer: &mut BufferRef);
+
+    fn parent_start(&self, pool: &BufferPool) -> bool;
+
+    fn parent_stop(&self, pool: &BufferPool) -> bool;
+
+    fn parent_get_options(&self, pool: &BufferPool) -> &'static [&'static str];
+
+    fn parent_set_config(&self, pool: &BufferPool, config: &mut BufferPoolConfigRef) -> bool;
+
+    fn parent_flush_start(&self, pool: &BufferPool);
+
+    fn parent_flush_stop(&self, pool: &BufferPool);
+}
+
+// Options of the parent classes and of the Rust subclasses, converted once per
+// type as the C API returns them without transferring ownership. The C strings
+// are kept alive together with the NULL-terminated array pointing to them
+struct OptionsStorage {
+    parent: HashMap<glib::Type, &'static [&'static str]>,
+    subclass: HashMap<glib::Type, (Vec<CString>, Vec<*const c_char>)>,
+}
+
+unsafe impl Send for OptionsStorage {}
+
+lazy_static! {
+    static ref OPTIONS: Mutex<OptionsStorage> = Mutex::new(OptionsStorage {
+        parent: HashMap::new(),
+        subclass: HashMap::new(),
+    });
+}
+
+impl<T: BufferPoolImpl + ObjectSubclass> BufferPoolImplExt for T {
+    fn parent_acquire_buffer(
+        &self,
+        pool: &BufferPool,
+        params: Option<&BufferPoolAcquireParams>,
+    ) -> Result<Buffer, FlowError> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            (*parent_class)
+                .acquire_buffer
+                .map(|f| {
+                    let mut buffer = ptr::null_mut();
+                    let ret: FlowReturn = from_glib(f(
+                        pool.to_glib_none().0,
+                        &mut buffer,
+                        params
+                            .map(|p| p.as_ptr() as *mut _)
+                            .unwrap_or(ptr::null_mut()),
+                    ));
+
+                    ret.into_result().map(|_| from_glib_full(buffer))
+                })
+                .unwrap_or(Err(FlowError::Error))
+        }
+    }
+
+    fn parent_alloc_buffer(
+        &self,
+        pool: &BufferPool,
+        params: Option<&BufferPoolAcquireParams>,
+    ) -> Result<Buffer, FlowError> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            (*parent_class)
+                .alloc_buffer
+                .map(|f| {
+                    let mut buffer = ptr::null_mut();
+                    let ret: FlowReturn = from_glib(f(
+                        pool.to_glib_none().0,
+                        &mut buffer,
+                        params
+                            .map(|p| p.as_ptr() as *mut _)
+                            .unwrap_or(ptr::null_mut()),
+                    ));
+
+                    ret.into_result().map(|_| from_glib_full(buffer))
+                })
+                .unwrap_or(Err(FlowError::Error))
+        }
+    }
+
+    fn parent_free_buffer(&self, pool: &BufferPool, buffer: Buffer) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            if let Some(f) = (*parent_class).free_buffer {
+                f(pool.to_glib_none().0, buffer.into_ptr())
+            }
+        }
+    }
+
+    fn parent_release_buffer(&self, pool: &BufferPool, buffer: Buffer) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            if let Some(f) = (*parent_class).release_buffer {
+                f(pool.to_glib_none().0, buffer.into_ptr())
+            }
+        }
+    }
+
+    fn parent_reset_buffer(&self, pool: &BufferPool, buffer: &mut BufferRef) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            if let Some(f) = (*parent_class).reset_buffer {
+                f(pool.to_glib_none().0, buffer.as_mut_ptr())
+            }
+        }
+    }
+
+    fn parent_start(&self, pool: &BufferPool) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            (*parent_class)
+                .start
+                .map(|f| from_glib(f(pool.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_stop(&self, pool: &BufferPool) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            (*parent_class)
+                .stop
+                .map(|f| from_glib(f(pool.to_glib_none().0)))
+                .unwrap_or(true)
+        }
+    }
+
+    fn parent_get_options(&self, pool: &BufferPool) -> &'static [&'static str] {
+        let mut storage = OPTIONS.lock().unwrap();
+        if let Some(options) = storage.parent.get(&T::get_type()) {
+            return options;
+        }
+
+        let mut options = Vec::new();
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            if let Some(f) = (*parent_class).get_options {
+                let mut ptr = f(pool.to_glib_none().0);
+                while !ptr.is_null() && !(*ptr).is_null() {
+                    // The strings are static in C and never freed
+                    options.push(CStr::from_ptr(*ptr).to_str().unwrap());
+                    ptr = ptr.offset(1);
+                }
+            }
+        }
+
+        let options: &'static [&'static str] = Box::leak(options.into_boxed_slice());
+        storage.parent.insert(T::get_type(), options);
+
+        options
+    }
+
+    fn parent_set_config(&self, pool: &BufferPool, config: &mut BufferPoolConfigRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            (*parent_class)
+                .set_config
+                .map(|f| from_glib(f(pool.to_glib_none().0, config.as_mut_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_flush_start(&self, pool: &BufferPool) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            if let Some(f) = (*parent_class).flush_start {
+                f(pool.to_glib_none().0)
+            }
+        }
+    }
+
+    fn parent_flush_stop(&self, pool: &BufferPool) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstBufferPoolClass;
+            if let Some(f) = (*parent_class).flush_stop {
+                f(pool.to_glib_none().0)
+            }
+        }
+    }
+}
+
+/// Rust class struct for `GstBufferPool` and all its subclasses.
+#[repr(C)]
+pub struct BufferPoolClass(ffi::GstBufferPoolClass);
+
+unsafe impl IsClassFor for BufferPoolClass {
+    type Instance = BufferPool;
+}
+
+unsafe impl Send for BufferPoolClass {}
+unsafe impl Sync for BufferPoolClass {}
+
+impl ops::Deref for BufferPoolClass {
+    type Target = glib::ObjectClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for BufferPoolClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl<T: ObjectSubclass + BufferPoolImpl> IsSubclassable<T> for BufferPoolClass {
+    fn override_vfuncs(&mut self) {
+        <glib::ObjectClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstBufferPoolClass);
+            klass.acquire_buffer = Some(buffer_pool_acquire_buffer::<T>);
+            klass.alloc_buffer = Some(buffer_pool_alloc_buffer::<T>);
+            klass.free_buffer = Some(buffer_pool_free_buffer::<T>);
+            klass.release_buffer = Some(buffer_pool_release_buffer::<T>);
+            klass.reset_buffer = Some(buffer_pool_reset_buffer::<T>);
+            klass.start = Some(buffer_pool_start::<T>);
+            klass.stop = Some(buffer_pool_stop::<T>);
+            klass.get_options = Some(buffer_pool_get_options::<T>);
+            klass.set_config = Some(buffer_pool_set_config::<T>);
+            klass.flush_start = Some(buffer_pool_flush_start::<T>);
+            klass.flush_stop = Some(buffer_pool_flush_stop::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn buffer_pool_acquire_buffer<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBufferPool,
+    buffer_ptr: *mut *mut ffi::GstBuffer,
+    params: *mut ffi::GstBufferPoolAcquireParams,
+) -> ffi::GstFlowReturn
+where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    let params = if params.is_null() {
+        None
+    } else {
+        Some(BufferPoolAcquireParams::from_glib_borrow(params))
+    };
+
+    panic_to_default(&wrap, FlowReturn::Error, || {
+        match imp.acquire_buffer(&wrap, params) {
+            Ok(buffer) => {
+                *buffer_ptr = buffer.into_ptr();
+                FlowReturn::Ok
+            }
+            Err(err) => FlowReturn::from_error(err),
+        }
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn buffer_pool_alloc_buffer<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBufferPool,
+    buffer_ptr: *mut *mut ffi::GstBuffer,
+    params: *mut ffi::GstBufferPoolAcquireParams,
+) -> ffi::GstFlowReturn
+where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    let params = if params.is_null() {
+        None
+    } else {
+        Some(BufferPoolAcquireParams::from_glib_borrow(params))
+    };
+
+    panic_to_default(&wrap, FlowReturn::Error, || {
+        match imp.alloc_buffer(&wrap, params) {
+            Ok(buffer) => {
+                *buffer_ptr = buffer.into_ptr();
+                FlowReturn::Ok
+            }
+            Err(err) => FlowReturn::from_error(err),
+        }
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn buffer_pool_free_buffer<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBufferPool,
+    buffer: *mut ffi::GstBuffer,
+) where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, (), || imp.free_buffer(&wrap, from_glib_full(buffer)))
+}
+
+unsafe extern "C" fn buffer_pool_release_buffer<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBufferPool,
+    buffer: *mut ffi::GstBuffer,
+) where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, (), || {
+        imp.release_buffer(&wrap, from_glib_full(buffer))
+    })
+}
+
+unsafe extern "C" fn buffer_pool_reset_buffer<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBufferPool,
+    buffer: *mut ffi::GstBuffer,
+) where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, (), || {
+        imp.reset_buffer(&wrap, BufferRef::from_mut_ptr(buffer))
+    })
+}
+
+unsafe extern "C" fn buffer_pool_start<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBufferPool,
+) -> glib_ffi::gboolean
+where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, false, || imp.start(&wrap)).to_glib()
+}
+
+unsafe extern "C" fn buffer_pool_stop<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBufferPool,
+) -> glib_ffi::gboolean
+where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, false, || imp.stop(&wrap)).to_glib()
+}
+
+unsafe extern "C" fn buffer_pool_get_options<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBufferPool,
+) -> *mut *const c_char
+where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    let type_ = wrap.get_type();
+    {
+        let storage = OPTIONS.lock().unwrap();
+        if let Some(options) = storage.subclass.get(&type_) {
+            return options.as_ptr() as *mut _;
+        }
+    }
+
+    // Must not be called with the lock held as it might chain up to the parent class
+    let options = panic_to_default(&wrap, &[][..], || imp.get_options(&wrap))
+        .iter()
+        .map(|option| CString::new(*option).unwrap())
+        .collect::<Vec<_>>();
+    let mut option_ptrs = options
+        .iter()
+        .map(|option| option.as_ptr())
+        .collect::<Vec<_>>();
+    option_ptrs.push(ptr::null());
+
+    let mut storage = OPTIONS.lock().unwrap();
+    storage
+        .subclass
+        .entry(type_)
+        .or_insert((options, option_ptrs))
+        .1
+        .as_ptr() as *mut _
+}
+
+unsafe extern "C" fn buffer_pool_set_config<T: ObjectSubclass>(
+    ptr: *mut ffi::GstBufferPool,
+    config: *mut ffi::GstStructure,
+) -> glib_ffi::gboolean
+where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, false, || {
+        imp.set_config(&wrap, BufferPoolConfigRef::from_glib_borrow_mut(config))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn buffer_pool_flush_start<T: ObjectSubclass>(ptr: *mut ffi::GstBufferPool)
+where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, (), || imp.flush_start(&wrap))
+}
+
+unsafe extern "C" fn buffer_pool_flush_stop<T: ObjectSubclass>(ptr: *mut ffi::GstBufferPool)
+where
+    T: BufferPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: BufferPool = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, (), || imp.flush_stop(&wrap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib;
+    use glib::subclass;
+    use prelude::*;
+    use std::sync::atomic;
+    use subclass::allocator::{AllocatorImpl, AllocatorImplExt};
+    use AllocationParams;
+    use Allocator;
+    use Memory;
+    use MemoryFlags;
+
+    pub mod imp {
+        use super::*;
+
+        pub struct TestAllocator;
+
+        impl ObjectSubclass for TestAllocator {
+            const NAME: &'static str = "TestAllocator";
+            type ParentType = Allocator;
+            type Instance = subclass::simple::InstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                TestAllocator
+            }
+        }
+
+        impl ObjectImpl for TestAllocator {
+            glib_object_impl!();
+        }
+
+        impl AllocatorImpl for TestAllocator {
+            fn alloc(
+                &self,
+                allocator: &Allocator,
+                size: usize,
+                _params: Option<&AllocationParams>,
+            ) -> Option<Memory> {
+                Some(self.new_memory_from_mut_slice(
+                    allocator,
+                    MemoryFlags::empty(),
+                    vec![0u8; size],
+                ))
+            }
+        }
+
+        pub struct TestPool {
+            pub(super) allocator: Allocator,
+            pub(super) n_resets: atomic::AtomicUsize,
+        }
+
+        impl ObjectSubclass for TestPool {
+            const NAME: &'static str = "TestPool";
+            type ParentType = BufferPool;
+            type Instance = subclass::simple::InstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                Self {
+                    allocator: glib::Object::new(TestAllocator::get_type(), &[])
+                        .unwrap()
+                        .downcast()
+                        .unwrap(),
+                    n_resets: atomic::AtomicUsize::new(0),
+                }
+            }
+        }
+
+        impl ObjectImpl for TestPool {
+            glib_object_impl!();
+        }
+
+        impl BufferPoolImpl for TestPool {
+            fn alloc_buffer(
+                &self,
+                _pool: &BufferPool,
+                _params: Option<&BufferPoolAcquireParams>,
+            ) -> Result<Buffer, FlowError> {
+                let memory = self.allocator.alloc(16, None).ok_or(FlowError::Error)?;
+                let mut buffer = Buffer::new();
+                buffer.get_mut().unwrap().append_memory(memory);
+                Ok(buffer)
+            }
+
+            fn reset_buffer(&self, pool: &BufferPool, buffer: &mut BufferRef) {
+                self.n_resets.fetch_add(1, atomic::Ordering::SeqCst);
+                self.parent_reset_buffer(pool, buffer)
+            }
+
+            fn get_options(&self, _pool: &BufferPool) -> &'static [&'static str] {
+                &["test-option"]
+            }
+        }
+    }
+
+    #[test]
+    fn test_buffer_pool_subclass() {
+        ::init().unwrap();
+
+        let pool = glib::Object::new(imp::TestPool::get_type(), &[])
+            .unwrap()
+            .downcast::<BufferPool>()
+            .unwrap();
+        assert_eq!(pool.get_options(), vec!["test-option"]);
+        assert!(pool.has_option("test-option"));
+
+        let mut config = pool.get_config();
+        config.set_params(None, 16, 0, 1);
+        pool.set_config(config).unwrap();
+        pool.set_active(true).unwrap();
+
+        let buffer = pool.acquire_buffer(None).unwrap();
+        assert_eq!(buffer.get_size(), 16);
+        {
+            let memory = buffer.peek_memory(0);
+            assert!(memory
+                .get_allocator()
+                .unwrap()
+                .get_type()
+                .is_a(&imp::TestAllocator::get_type()));
+            let map = memory.map_readable().unwrap();
+            assert_eq!(map.as_slice(), &[0u8; 16]);
+        }
+        drop(buffer);
+
+        let imp = imp::TestPool::from_instance(&pool);
+        assert_eq!(imp.n_resets.load(atomic::Ordering::SeqCst), 1);
+
+        pool.set_active(false).unwrap();
+    }
+}
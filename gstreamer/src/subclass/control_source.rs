@@ -0,0 +1,243 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::ops;
+use std::slice;
+
+use ffi;
+use glib_ffi;
+use gobject_ffi;
+
+use glib;
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use ClockTime;
+use ControlSource;
+
+use super::error::panic_to_default;
+
+pub trait ControlSourceImpl: ObjectImpl + Send + Sync + 'static {
+    /// Returns the control value at `timestamp`, or `None` if there is no
+    /// value for this position.
+    fn get_value(&self, control_source: &ControlSource, timestamp: ClockTime) -> Option<f64>;
+
+    /// Fills `values` with the control values starting at `timestamp`, one
+    /// every `interval`.
+    ///
+    /// By default this calls `get_value()` for every position and fails if
+    /// any of them has no value.
+    fn get_value_array(
+        &self,
+        control_source: &ControlSource,
+        timestamp: ClockTime,
+        interval: ClockTime,
+        values: &mut [f64],
+    ) -> Result<(), glib::BoolError> {
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = self
+                .get_value(control_source, timestamp + interval * (i as u64))
+                .ok_or_else(|| glib::BoolError("Failed to get control source value"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Rust class struct for `GstControlSource` and all its subclasses.
+#[repr(C)]
+pub struct ControlSourceClass(ffi::GstControlSourceClass);
+
+unsafe impl IsClassFor for ControlSourceClass {
+    type Instance = ControlSource;
+}
+
+unsafe impl Send for ControlSourceClass {}
+unsafe impl Sync for ControlSourceClass {}
+
+impl ops::Deref for ControlSourceClass {
+    type Target = glib::ObjectClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for ControlSourceClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl<T: ObjectSubclass + ControlSourceImpl> IsSubclassable<T> for ControlSourceClass {
+    fn override_vfuncs(&mut self) {
+        <glib::ObjectClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            // The value functions are per instance and have to be set up
+            // once the control source is constructed
+            let klass = &mut *(self as *const Self as *mut gobject_ffi::GObjectClass);
+            klass.constructed = Some(control_source_constructed::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn control_source_constructed<T: ObjectSubclass>(ptr: *mut gobject_ffi::GObject)
+where
+    T: ControlSourceImpl,
+{
+    let control_source = &mut *(ptr as *mut ffi::GstControlSource);
+    control_source.get_value = Some(control_source_get_value::<T>);
+    control_source.get_value_array = Some(control_source_get_value_array::<T>);
+
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: glib::Object = from_glib_borrow(ptr);
+    let control_source: ControlSource = from_glib_borrow(ptr as *mut ffi::GstControlSource);
+
+    panic_to_default(&control_source, (), || imp.constructed(&wrap));
+}
+
+unsafe extern "C" fn control_source_get_value<T: ObjectSubclass>(
+    ptr: *mut ffi::GstControlSource,
+    timestamp: ffi::GstClockTime,
+    value: *mut f64,
+) -> glib_ffi::gboolean
+where
+    T: ControlSourceImpl,
+{
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: ControlSource = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, false, || {
+        match imp.get_value(&wrap, from_glib(timestamp)) {
+            Some(v) => {
+                *value = v;
+                true
+            }
+            None => false,
+        }
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn control_source_get_value_array<T: ObjectSubclass>(
+    ptr: *mut ffi::GstControlSource,
+    timestamp: ffi::GstClockTime,
+    interval: ffi::GstClockTime,
+    n_values: u32,
+    values: *mut f64,
+) -> glib_ffi::gboolean
+where
+    T: ControlSourceImpl,
+{
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: ControlSource = from_glib_borrow(ptr);
+
+    let values: &mut [f64] = if n_values == 0 || values.is_null() {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(values, n_values as usize)
+    };
+
+    panic_to_default(&wrap, false, || {
+        imp.get_value_array(&wrap, from_glib(timestamp), from_glib(interval), values)
+            .is_ok()
+    })
+    .to_glib()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib::subclass;
+    use prelude::*;
+
+    pub mod imp {
+        use super::*;
+
+        // Ramps from 0.0 to 1.0 over the first second and panics after the
+        // tenth second
+        pub struct TestControlSource;
+
+        impl ObjectSubclass for TestControlSource {
+            const NAME: &'static str = "TestControlSource";
+            type ParentType = ControlSource;
+            type Instance = subclass::simple::InstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                TestControlSource
+            }
+        }
+
+        impl ObjectImpl for TestControlSource {
+            glib_object_impl!();
+        }
+
+        impl ControlSourceImpl for TestControlSource {
+            fn get_value(
+                &self,
+                _control_source: &ControlSource,
+                timestamp: ClockTime,
+            ) -> Option<f64> {
+                let timestamp = timestamp.nseconds()?;
+                assert!(timestamp <= 10 * ::SECOND_VAL);
+                Some((timestamp as f64 / ::SECOND_VAL as f64).min(1.0))
+            }
+        }
+    }
+
+    #[test]
+    fn test_control_source_subclass() {
+        ::init().unwrap();
+
+        let cs = glib::Object::new(imp::TestControlSource::get_type(), &[])
+            .unwrap()
+            .downcast::<ControlSource>()
+            .unwrap();
+
+        assert_eq!(
+            cs.control_source_get_value(ClockTime::from_mseconds(250)),
+            Some(0.25)
+        );
+        assert_eq!(cs.control_source_get_value(ClockTime::none()), None);
+
+        let mut values = [0.0; 4];
+        cs.control_source_get_value_array(
+            ClockTime::from_mseconds(500),
+            ClockTime::from_mseconds(250),
+            &mut values,
+        )
+        .unwrap();
+        assert_eq!(values, [0.5, 0.75, 1.0, 1.0]);
+
+        assert!(cs
+            .control_source_get_value_array(
+                ClockTime::none(),
+                ClockTime::from_mseconds(250),
+                &mut values,
+            )
+            .is_err());
+
+        assert_eq!(
+            cs.control_source_get_value(ClockTime::from_seconds(11)),
+            None
+        );
+        assert!(cs
+            .control_source_get_value_array(
+                ClockTime::from_seconds(9),
+                ClockTime::from_seconds(1),
+                &mut values,
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,729 @@
+// Copyright (C) 2017-2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use libc;
+
+use std::ops;
+use std::sync::atomic::AtomicBool;
+
+use ffi;
+use glib_ffi;
+use gobject_ffi;
+
+use glib;
+use glib::object::ObjectType;
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use prelude::*;
+
+use Caps;
+use CapsRef;
+use Clock;
+use Context;
+use Element;
+use Event;
+use Pad;
+use PadTemplate;
+use QueryRef;
+use StateChange;
+use StateChangeReturn;
+
+/// Instance struct for all element subclasses.
+///
+/// Next to the parent instance this keeps track of whether the implementation
+/// panicked before, in which case all further virtual method calls return a
+/// fallback value and post an error message instead of calling into the
+/// implementation again.
+#[repr(C)]
+pub struct ElementInstanceStruct<T: ObjectSubclass> {
+    parent: <T::ParentType as ObjectType>::GlibType,
+    panicked: AtomicBool,
+}
+
+unsafe impl<T: ObjectSubclass> InstanceStruct for ElementInstanceStruct<T> {
+    type Type = T;
+}
+
+impl<T: ObjectSubclass> ElementInstanceStruct<T> {
+    pub fn panicked(&self) -> &AtomicBool {
+        &self.panicked
+    }
+}
+
+pub trait ElementImpl: ObjectImpl + Send + Sync + 'static {
+    fn change_state(&self, element: &Element, transition: StateChange) -> StateChangeReturn {
+        self.parent_change_state(element, transition)
+    }
+
+    fn request_new_pad(
+        &self,
+        element: &Element,
+        templ: &PadTemplate,
+        name: Option<String>,
+        caps: Option<&CapsRef>,
+    ) -> Option<Pad> {
+        self.parent_request_new_pad(element, templ, name, caps)
+    }
+
+    fn release_pad(&self, element: &Element, pad: &Pad) {
+        self.parent_release_pad(element, pad)
+    }
+
+    fn send_event(&self, element: &Element, event: Event) -> bool {
+        self.parent_send_event(element, event)
+    }
+
+    fn query(&self, element: &Element, query: &mut QueryRef) -> bool {
+        self.parent_query(element, query)
+    }
+
+    fn set_context(&self, element: &Element, context: &Context) {
+        self.parent_set_context(element, context)
+    }
+
+    fn provide_clock(&self, element: &Element) -> Option<Clock> {
+        self.parent_provide_clock(element)
+    }
+}
+
+pub trait ElementImplExt {
+    fn parent_change_state(&self, element: &Element, transition: StateChange)
+        -> StateChangeReturn;
+
+    fn parent_request_new_pad(
+        &self,
+        element: &Element,
+        templ: &PadTemplate,
+        name: Option<String>,
+        caps: Option<&CapsRef>,
+    ) -> Option<Pad>;
+
+    fn parent_release_pad(&self, element: &Element, pad: &Pad);
+
+    fn parent_send_event(&self, element: &Element, event: Event) -> bool;
+
+    fn parent_query(&self, element: &Element, query: &mut QueryRef) -> bool;
+
+    fn parent_set_context(&self, element: &Element, context: &Context);
+
+    fn parent_provide_clock(&self, element: &Element) -> Option<Clock>;
+
+    fn catch_panic<F: FnOnce(&Self) -> R, R, P: IsA<Element>>(
+        &self,
+        element: &P,
+        fallback: R,
+        f: F,
+    ) -> R;
+}
+
+impl<T: ElementImpl + ObjectSubclass> ElementImplExt for T
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn parent_change_state(
+        &self,
+        element: &Element,
+        transition: StateChange,
+    ) -> StateChangeReturn {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstElementClass;
+
+            (*parent_class)
+                .change_state
+                .map(|f| from_glib(f(element.to_glib_none().0, transition.to_glib())))
+                .unwrap_or(StateChangeReturn::Success)
+        }
+    }
+
+    fn parent_request_new_pad(
+        &self,
+        element: &Element,
+        templ: &PadTemplate,
+        name: Option<String>,
+        caps: Option<&CapsRef>,
+    ) -> Option<Pad> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstElementClass;
+
+            (*parent_class)
+                .request_new_pad
+                .map(|f| {
+                    from_glib_none(f(
+                        element.to_glib_none().0,
+                        templ.to_glib_none().0,
+                        name.to_glib_none().0,
+                        caps.map(|caps| caps.as_ptr()).unwrap_or(::std::ptr::null()),
+                    ))
+                })
+                .unwrap_or(None)
+        }
+    }
+
+    fn parent_release_pad(&self, element: &Element, pad: &Pad) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstElementClass;
+
+            (*parent_class)
+                .release_pad
+                .map(|f| f(element.to_glib_none().0, pad.to_glib_none().0))
+                .unwrap_or(())
+        }
+    }
+
+    fn parent_send_event(&self, element: &Element, event: Event) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstElementClass;
+
+            (*parent_class)
+                .send_event
+                .map(|f| from_glib(f(element.to_glib_none().0, event.into_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_query(&self, element: &Element, query: &mut QueryRef) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstElementClass;
+
+            (*parent_class)
+                .query
+                .map(|f| from_glib(f(element.to_glib_none().0, query.as_mut_ptr())))
+                .unwrap_or(false)
+        }
+    }
+
+    fn parent_set_context(&self, element: &Element, context: &Context) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstElementClass;
+
+            (*parent_class)
+                .set_context
+                .map(|f| f(element.to_glib_none().0, context.as_mut_ptr()))
+                .unwrap_or(())
+        }
+    }
+
+    fn parent_provide_clock(&self, element: &Element) -> Option<Clock> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().get_parent_class() as *mut ffi::GstElementClass;
+
+            (*parent_class)
+                .provide_clock
+                .map(|f| from_glib_full(f(element.to_glib_none().0)))
+                .unwrap_or(None)
+        }
+    }
+
+    fn catch_panic<F: FnOnce(&Self) -> R, R, P: IsA<Element>>(
+        &self,
+        element: &P,
+        fallback: R,
+        f: F,
+    ) -> R {
+        unsafe {
+            assert!(element.get_type().is_a(&T::get_type()));
+            let ptr: *mut ffi::GstElement = element.to_glib_none().0;
+            let instance = &*(ptr as *mut T::Instance);
+            let imp = instance.get_impl();
+
+            gst_panic_to_error!(element, &instance.as_ref().panicked(), fallback, {
+                f(&imp)
+            })
+        }
+    }
+}
+
+impl<T: ObjectSubclass> AsRef<ElementInstanceStruct<T>> for ElementInstanceStruct<T> {
+    fn as_ref(&self) -> &ElementInstanceStruct<T> {
+        self
+    }
+}
+
+/// Rust class struct for `GstElement` and all its subclasses.
+#[repr(C)]
+pub struct ElementClass(ffi::GstElementClass);
+
+unsafe impl IsClassFor for ElementClass {
+    type Instance = Element;
+}
+
+unsafe impl Send for ElementClass {}
+unsafe impl Sync for ElementClass {}
+
+impl ops::Deref for ElementClass {
+    type Target = glib::ObjectClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for ElementClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+/// Class-init helpers for registering metadata and pad templates.
+///
+/// These are meant to be called from `ObjectSubclass::class_init`.
+pub unsafe trait ElementClassSubclassExt: Sized + 'static {
+    fn add_pad_template(&mut self, pad_template: PadTemplate) {
+        unsafe {
+            ffi::gst_element_class_add_pad_template(
+                self as *const Self as *mut ffi::GstElementClass,
+                pad_template.to_glib_none().0,
+            );
+        }
+    }
+
+    fn get_pad_template(&self, name: &str) -> Option<PadTemplate> {
+        unsafe {
+            from_glib_none(ffi::gst_element_class_get_pad_template(
+                self as *const Self as *mut ffi::GstElementClass,
+                name.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn set_metadata(
+        &mut self,
+        long_name: &str,
+        classification: &str,
+        description: &str,
+        author: &str,
+    ) {
+        unsafe {
+            ffi::gst_element_class_set_metadata(
+                self as *const Self as *mut ffi::GstElementClass,
+                long_name.to_glib_none().0,
+                classification.to_glib_none().0,
+                description.to_glib_none().0,
+                author.to_glib_none().0,
+            );
+        }
+    }
+
+    fn add_metadata(&mut self, key: &str, value: &str) {
+        unsafe {
+            ffi::gst_element_class_add_metadata(
+                self as *const Self as *mut ffi::GstElementClass,
+                key.to_glib_none().0,
+                value.to_glib_none().0,
+            );
+        }
+    }
+}
+
+unsafe impl ElementClassSubclassExt for ElementClass {}
+unsafe impl<T: ObjectSubclass + ElementImpl> ElementClassSubclassExt
+    for glib::subclass::simple::ClassStruct<T>
+{
+}
+
+unsafe impl<T: ObjectSubclass + ElementImpl> IsSubclassable<T> for ElementClass
+where
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    fn override_vfuncs(&mut self) {
+        <glib::ObjectClass as IsSubclassable<T>>::override_vfuncs(self);
+
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstElementClass);
+            klass.change_state = Some(element_change_state::<T>);
+            klass.request_new_pad = Some(element_request_new_pad::<T>);
+            klass.release_pad = Some(element_release_pad::<T>);
+            klass.send_event = Some(element_send_event::<T>);
+            klass.query = Some(element_query::<T>);
+            klass.set_context = Some(element_set_context::<T>);
+            klass.provide_clock = Some(element_provide_clock::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn element_change_state<T: ObjectSubclass>(
+    ptr: *mut ffi::GstElement,
+    transition: ffi::GstStateChange,
+) -> ffi::GstStateChangeReturn
+where
+    T: ElementImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Element = from_glib_borrow(ptr);
+
+    let transition = from_glib(transition);
+
+    // *Never* fail downwards state changes, otherwise elements could not
+    // be shut down anymore after a panic
+    let fallback = match transition {
+        StateChange::PlayingToPaused | StateChange::PausedToReady | StateChange::ReadyToNull => {
+            StateChangeReturn::Success
+        }
+        _ => StateChangeReturn::Failure,
+    };
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), fallback, {
+        imp.change_state(&wrap, transition)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn element_request_new_pad<T: ObjectSubclass>(
+    ptr: *mut ffi::GstElement,
+    templ: *mut ffi::GstPadTemplate,
+    name: *const libc::c_char,
+    caps: *const ffi::GstCaps,
+) -> *mut ffi::GstPad
+where
+    T: ElementImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Element = from_glib_borrow(ptr);
+
+    let caps: Option<Caps> = if caps.is_null() {
+        None
+    } else {
+        Some(from_glib_borrow(caps))
+    };
+
+    // XXX: This is effectively unsafe but the best we can do
+    // See https://bugzilla.gnome.org/show_bug.cgi?id=791193
+    let pad = gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), None, {
+        imp.request_new_pad(
+            &wrap,
+            &from_glib_borrow(templ),
+            from_glib_none(name),
+            caps.as_ref().map(|caps| caps.as_ref()),
+        )
+    });
+
+    // Ensure that the pad is owned by the element now, if a pad was returned
+    if let Some(ref pad) = pad {
+        assert_eq!(
+            pad.get_parent(),
+            Some(::Object::from_glib_borrow(ptr as *mut ffi::GstObject))
+        );
+    }
+
+    pad.to_glib_none().0
+}
+
+unsafe extern "C" fn element_release_pad<T: ObjectSubclass>(
+    ptr: *mut ffi::GstElement,
+    pad: *mut ffi::GstPad,
+) where
+    T: ElementImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Element = from_glib_borrow(ptr);
+
+    // If we get a floating reference passed simply return here. It can't be stored inside this
+    // element, and if we continued to use it we would take ownership of this floating reference.
+    if gobject_ffi::g_object_is_floating(pad as *mut gobject_ffi::GObject) != glib_ffi::GFALSE {
+        return;
+    }
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), (), {
+        imp.release_pad(&wrap, &from_glib_borrow(pad))
+    })
+}
+
+unsafe extern "C" fn element_send_event<T: ObjectSubclass>(
+    ptr: *mut ffi::GstElement,
+    event: *mut ffi::GstEvent,
+) -> glib_ffi::gboolean
+where
+    T: ElementImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Element = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.send_event(&wrap, from_glib_full(event))
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn element_query<T: ObjectSubclass>(
+    ptr: *mut ffi::GstElement,
+    query: *mut ffi::GstQuery,
+) -> glib_ffi::gboolean
+where
+    T: ElementImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Element = from_glib_borrow(ptr);
+    let query = QueryRef::from_mut_ptr(query);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), false, {
+        imp.query(&wrap, query)
+    })
+    .to_glib()
+}
+
+unsafe extern "C" fn element_set_context<T: ObjectSubclass>(
+    ptr: *mut ffi::GstElement,
+    context: *mut ffi::GstContext,
+) where
+    T: ElementImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Element = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), (), {
+        imp.set_context(&wrap, &from_glib_borrow(context))
+    })
+}
+
+unsafe extern "C" fn element_provide_clock<T: ObjectSubclass>(
+    ptr: *mut ffi::GstElement,
+) -> *mut ffi::GstClock
+where
+    T: ElementImpl,
+    T::Instance: AsRef<ElementInstanceStruct<T>>,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: Element = from_glib_borrow(ptr);
+
+    gst_panic_to_error!(&wrap, &instance.as_ref().panicked(), None, {
+        imp.provide_clock(&wrap)
+    })
+    .to_glib_full()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib;
+    use glib::subclass;
+    use std::sync::atomic;
+
+    pub mod imp {
+        use super::*;
+
+        pub struct TestElement {
+            pub(super) srcpad: ::Pad,
+            pub(super) sinkpad: ::Pad,
+            pub(super) n_buffers: atomic::AtomicU32,
+            pub(super) reached_playing: atomic::AtomicBool,
+        }
+
+        impl TestElement {
+            fn sink_chain(
+                &self,
+                _pad: &::Pad,
+                _element: &::Element,
+                buffer: ::Buffer,
+            ) -> ::FlowReturn {
+                self.n_buffers.fetch_add(1, atomic::Ordering::SeqCst);
+                self.srcpad.push(buffer)
+            }
+
+            fn sink_event(&self, _pad: &::Pad, _element: &::Element, event: ::Event) -> bool {
+                self.srcpad.push_event(event)
+            }
+
+            fn sink_query(
+                &self,
+                _pad: &::Pad,
+                _element: &::Element,
+                query: &mut ::QueryRef,
+            ) -> bool {
+                self.srcpad.peer_query(query)
+            }
+
+            fn src_event(&self, _pad: &::Pad, _element: &::Element, event: ::Event) -> bool {
+                self.sinkpad.push_event(event)
+            }
+
+            fn src_query(
+                &self,
+                _pad: &::Pad,
+                _element: &::Element,
+                query: &mut ::QueryRef,
+            ) -> bool {
+                self.sinkpad.peer_query(query)
+            }
+        }
+
+        impl ObjectSubclass for TestElement {
+            const NAME: &'static str = "TestElement";
+            type ParentType = ::Element;
+            type Instance = ::subclass::ElementInstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new_with_class(klass: &subclass::simple::ClassStruct<Self>) -> Self {
+                let templ = klass.get_pad_template("sink").unwrap();
+                let sinkpad = ::Pad::new_from_template(&templ, "sink");
+                let templ = klass.get_pad_template("src").unwrap();
+                let srcpad = ::Pad::new_from_template(&templ, "src");
+
+                sinkpad.set_chain_function(|pad, parent, buffer| {
+                    let element = parent.as_ref().unwrap().downcast_ref::<::Element>().unwrap();
+                    let imp = Self::from_instance(element);
+                    imp.sink_chain(pad, element, buffer)
+                });
+                sinkpad.set_event_function(|pad, parent, event| {
+                    let element = parent.as_ref().unwrap().downcast_ref::<::Element>().unwrap();
+                    let imp = Self::from_instance(element);
+                    imp.sink_event(pad, element, event)
+                });
+                sinkpad.set_query_function(|pad, parent, query| {
+                    let element = parent.as_ref().unwrap().downcast_ref::<::Element>().unwrap();
+                    let imp = Self::from_instance(element);
+                    imp.sink_query(pad, element, query)
+                });
+
+                srcpad.set_event_function(|pad, parent, event| {
+                    let element = parent.as_ref().unwrap().downcast_ref::<::Element>().unwrap();
+                    let imp = Self::from_instance(element);
+                    imp.src_event(pad, element, event)
+                });
+                srcpad.set_query_function(|pad, parent, query| {
+                    let element = parent.as_ref().unwrap().downcast_ref::<::Element>().unwrap();
+                    let imp = Self::from_instance(element);
+                    imp.src_query(pad, element, query)
+                });
+
+                Self {
+                    n_buffers: atomic::AtomicU32::new(0),
+                    reached_playing: atomic::AtomicBool::new(false),
+                    srcpad,
+                    sinkpad,
+                }
+            }
+
+            fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+                klass.set_metadata(
+                    "Test Element",
+                    "Generic",
+                    "Does nothing",
+                    "Sebastian Dröge <sebastian@centricular.com>",
+                );
+
+                let caps = ::Caps::new_any();
+                let src_pad_template =
+                    ::PadTemplate::new("src", ::PadDirection::Src, ::PadPresence::Always, &caps);
+                klass.add_pad_template(src_pad_template);
+
+                let sink_pad_template = ::PadTemplate::new(
+                    "sink",
+                    ::PadDirection::Sink,
+                    ::PadPresence::Always,
+                    &caps,
+                );
+                klass.add_pad_template(sink_pad_template);
+            }
+        }
+
+        impl ObjectImpl for TestElement {
+            glib_object_impl!();
+
+            fn constructed(&self, obj: &glib::Object) {
+                self.parent_constructed(obj);
+
+                let element = obj.downcast_ref::<::Element>().unwrap();
+                element.add_pad(&self.sinkpad).unwrap();
+                element.add_pad(&self.srcpad).unwrap();
+            }
+        }
+
+        impl ElementImpl for TestElement {
+            fn change_state(
+                &self,
+                element: &::Element,
+                transition: ::StateChange,
+            ) -> ::StateChangeReturn {
+                let res = self.parent_change_state(element, transition);
+
+                if transition == ::StateChange::PausedToPlaying {
+                    self.reached_playing.store(true, atomic::Ordering::SeqCst);
+                }
+
+                res
+            }
+        }
+    }
+
+    #[test]
+    fn test_element_subclass() {
+        ::init().unwrap();
+
+        let type_ = imp::TestElement::get_type();
+        assert!(::Element::register(None, "testelement", 0, type_));
+
+        let element = ::ElementFactory::make("testelement", "test").unwrap();
+        assert_eq!(element.get_name(), "test");
+        assert_eq!(
+            element.get_metadata(*::ELEMENT_METADATA_LONGNAME),
+            Some("Test Element")
+        );
+
+        let pipeline = ::Pipeline::new(None);
+        let src = ::ElementFactory::make("fakesrc", None).unwrap();
+        let sink = ::ElementFactory::make("fakesink", None).unwrap();
+
+        src.set_property("num-buffers", &100i32).unwrap();
+
+        pipeline.add_many(&[&src, &element, &sink]).unwrap();
+        ::Element::link_many(&[&src, &element, &sink]).unwrap();
+
+        assert_ne!(
+            pipeline.set_state(::State::Playing),
+            ::StateChangeReturn::Failure
+        );
+        let bus = pipeline.get_bus().unwrap();
+
+        while let Some(msg) = bus.timed_pop(::CLOCK_TIME_NONE) {
+            match msg.view() {
+                ::MessageView::Eos(..) => break,
+                ::MessageView::Error(err) => panic!("{:?}", err.get_error()),
+                _ => (),
+            }
+        }
+
+        assert_ne!(
+            pipeline.set_state(::State::Null),
+            ::StateChangeReturn::Failure
+        );
+
+        let imp = imp::TestElement::from_instance(&element);
+        assert_eq!(imp.n_buffers.load(atomic::Ordering::SeqCst), 100);
+        assert_eq!(imp.reached_playing.load(atomic::Ordering::SeqCst), true);
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use glib::IsA;
+
+use DebugCategory;
+use DebugColorFlags;
+use Object;
+
+lazy_static! {
+    static ref CAT: DebugCategory = DebugCategory::new(
+        "GST_RUST_SUBCLASS",
+        DebugColorFlags::empty(),
+        "Rust subclass implementations",
+    );
+}
+
+#[macro_export]
+macro_rules! gst_panic_to_error(
+    ($element:expr, $panicked:expr, $ret:expr, $code:block) => {{
+        use std::panic::{self, AssertUnwindSafe};
+        use std::sync::atomic::Ordering;
+        use $crate::ElementExtManual;
+
+        if $panicked.load(Ordering::Relaxed) {
+            $element.post_error_message(&gst_error_msg!($crate::LibraryError::Failed, ["Panicked"]));
+            $ret
+        } else {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| $code));
+
+            match result {
+                Ok(result) => result,
+                Err(err) => {
+                    $panicked.store(true, Ordering::Relaxed);
+                    if let Some(cause) = err.downcast_ref::<&str>() {
+                        $element.post_error_message(&gst_error_msg!($crate::LibraryError::Failed, ["Panicked: {}", cause]));
+                    } else if let Some(cause) = err.downcast_ref::<String>() {
+                        $element.post_error_message(&gst_error_msg!($crate::LibraryError::Failed, ["Panicked: {}", cause]));
+                    } else {
+                        $element.post_error_message(&gst_error_msg!($crate::LibraryError::Failed, ["Panicked"]));
+                    }
+                    $ret
+                }
+            }
+        }
+    }};
+);
+
+// Objects other than elements have no error reporting mechanism, so panics in
+// their Rust implementations are logged and `default` is returned to the C
+// caller instead of unwinding into it.
+pub(crate) fn panic_to_default<O, R, F>(obj: &O, default: R, func: F) -> R
+where
+    O: IsA<Object>,
+    F: FnOnce() -> R,
+{
+    match panic::catch_unwind(AssertUnwindSafe(func)) {
+        Ok(ret) => ret,
+        Err(err) => {
+            if let Some(cause) = err.downcast_ref::<&str>() {
+                gst_error!(CAT, obj: obj, "Panicked: {}", cause);
+            } else if let Some(cause) = err.downcast_ref::<String>() {
+                gst_error!(CAT, obj: obj, "Panicked: {}", cause);
+            } else {
+                gst_error!(CAT, obj: obj, "Panicked");
+            }
+            default
+        }
+    }
+}
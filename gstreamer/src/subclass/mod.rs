@@ -0,0 +1,36 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use]
+pub mod error;
+#[macro_use]
+pub mod plugin;
+
+pub mod allocator;
+pub mod buffer_pool;
+pub mod control_source;
+pub mod element;
+pub mod pad;
+pub mod task_pool;
+
+pub use self::allocator::AllocatorClass;
+pub use self::buffer_pool::BufferPoolClass;
+pub use self::control_source::ControlSourceClass;
+pub use self::element::{ElementClass, ElementInstanceStruct};
+pub use self::pad::PadClass;
+pub use self::task_pool::TaskPoolClass;
+
+pub mod prelude {
+    pub use super::allocator::{AllocatorImpl, AllocatorImplExt};
+    pub use super::buffer_pool::{BufferPoolImpl, BufferPoolImplExt};
+    pub use super::control_source::ControlSourceImpl;
+    pub use super::element::{ElementClassSubclassExt, ElementImpl, ElementImplExt};
+    pub use super::pad::{PadImpl, PadImplExt};
+    pub use super::task_pool::{TaskHandle, TaskPoolImpl};
+    pub use glib::subclass::prelude::*;
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Defines the `gst_plugin_desc` symbol that GStreamer looks up when loading a
+/// plugin from a shared library.
+///
+/// `$plugin_init` must be a function in the calling module with the signature
+/// `fn(&gst::Plugin) -> Result<(), glib::BoolError>`. All string arguments must
+/// be string literals.
+///
+/// In addition a `plugin_desc::plugin_register_static()` function is generated
+/// that registers the same plugin statically, e.g. for tests or when the crate
+/// is linked into an application directly. It returns an error if the plugin
+/// could not be registered or its init function failed.
+#[macro_export]
+macro_rules! gst_plugin_define(
+    ($name:expr, $description:expr, $plugin_init:ident,
+     $version:expr, $license:expr, $source:expr,
+     $package:expr, $origin:expr, $release_datetime:expr) => {
+        pub mod plugin_desc {
+            use $crate::glib::translate::{from_glib, from_glib_borrow};
+            use $crate::ffi;
+            use $crate::glib_ffi;
+
+            use std::os::raw::c_char;
+            use std::panic::{self, AssertUnwindSafe};
+
+            #[repr(C)]
+            pub struct GstPluginDesc(ffi::GstPluginDesc);
+            unsafe impl Sync for GstPluginDesc {}
+
+            #[no_mangle]
+            #[allow(non_upper_case_globals)]
+            pub static gst_plugin_desc: GstPluginDesc = GstPluginDesc(ffi::GstPluginDesc {
+                major_version: 1,
+                minor_version: 8,
+                name: concat!($name, "\0") as *const str as *const c_char,
+                description: concat!($description, "\0") as *const str as *const c_char,
+                plugin_init: Some(plugin_init_trampoline),
+                version: concat!($version, "\0") as *const str as *const c_char,
+                license: concat!($license, "\0") as *const str as *const c_char,
+                source: concat!($source, "\0") as *const str as *const c_char,
+                package: concat!($package, "\0") as *const str as *const c_char,
+                origin: concat!($origin, "\0") as *const str as *const c_char,
+                release_datetime: concat!($release_datetime, "\0") as *const str as *const c_char,
+                _gst_reserved: [0 as glib_ffi::gpointer; 4],
+            });
+
+            pub fn plugin_register_static() -> Result<(), $crate::glib::BoolError> {
+                let res: bool = unsafe {
+                    from_glib(ffi::gst_plugin_register_static(
+                        gst_plugin_desc.0.major_version,
+                        gst_plugin_desc.0.minor_version,
+                        gst_plugin_desc.0.name,
+                        gst_plugin_desc.0.description,
+                        Some(plugin_init_trampoline),
+                        gst_plugin_desc.0.version,
+                        gst_plugin_desc.0.license,
+                        gst_plugin_desc.0.source,
+                        gst_plugin_desc.0.package,
+                        gst_plugin_desc.0.origin,
+                    ))
+                };
+
+                if res {
+                    Ok(())
+                } else {
+                    Err($crate::glib::BoolError("Failed to register plugin"))
+                }
+            }
+
+            unsafe extern "C" fn plugin_init_trampoline(
+                plugin: *mut ffi::GstPlugin,
+            ) -> glib_ffi::gboolean {
+                let panic_result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    super::$plugin_init(&from_glib_borrow(plugin))
+                }));
+
+                let msg = match panic_result {
+                    Ok(Ok(())) => return glib_ffi::GTRUE,
+                    Ok(Err(err)) => format!("Failed to register plugin: {}", err.0),
+                    Err(err) => {
+                        if let Some(cause) = err.downcast_ref::<&str>() {
+                            format!("Failed to initialize plugin due to panic: {}", cause)
+                        } else if let Some(cause) = err.downcast_ref::<String>() {
+                            format!("Failed to initialize plugin due to panic: {}", cause)
+                        } else {
+                            String::from("Failed to initialize plugin due to panic")
+                        }
+                    }
+                };
+
+                if let Some(cat) = $crate::DebugCategory::get("GST_PLUGIN_LOADING") {
+                    $crate::DebugCategory::log(
+                        &cat,
+                        None as Option<&$crate::Object>,
+                        $crate::DebugLevel::Error,
+                        file!(),
+                        module_path!(),
+                        line!(),
+                        format_args!("{}", msg),
+                    );
+                }
+
+                glib_ffi::GFALSE
+            }
+        }
+    };
+);
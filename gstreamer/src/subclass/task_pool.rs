@@ -0,0 +1,281 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+use std::ops;
+use std::ptr;
+
+use ffi;
+use glib_ffi;
+
+use glib;
+use glib::subclass::prelude::*;
+use glib::translate::*;
+
+use TaskPool;
+
+use super::error::panic_to_default;
+
+/// Function pushed to a `TaskPool` that has to be called exactly once on the
+/// thread it should run on.
+pub struct TaskPoolFunction(unsafe extern "C" fn(glib_ffi::gpointer), glib_ffi::gpointer);
+
+unsafe impl Send for TaskPoolFunction {}
+
+impl TaskPoolFunction {
+    pub fn call(self) {
+        unsafe { (self.0)(self.1) }
+    }
+}
+
+impl fmt::Debug for TaskPoolFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TaskPoolFunction").field(&self.1).finish()
+    }
+}
+
+/// Handle to a function that was pushed to a `TaskPool`.
+pub trait TaskHandle: Send + 'static {
+    /// Waits until the function has returned.
+    fn join(self);
+}
+
+impl<T: Send + 'static> TaskHandle for ::std::thread::JoinHandle<T> {
+    fn join(self) {
+        let _ = ::std::thread::JoinHandle::join(self);
+    }
+}
+
+pub trait TaskPoolImpl: ObjectImpl + Send + Sync + 'static {
+    type Handle: TaskHandle;
+
+    /// Prepares the pool for pushing functions to it, e.g. spawns the threads.
+    fn prepare(&self, _task_pool: &TaskPool) -> Result<(), glib::Error> {
+        Ok(())
+    }
+
+    /// Waits for all functions to finish and releases the resources of the
+    /// pool.
+    fn cleanup(&self, _task_pool: &TaskPool) {}
+
+    /// Runs `func` on one of the threads of the pool.
+    ///
+    /// If a handle is returned, it is joined once the owner of the function
+    /// waits for it to finish.
+    fn push(
+        &self,
+        task_pool: &TaskPool,
+        func: TaskPoolFunction,
+    ) -> Result<Option<Self::Handle>, glib::Error>;
+}
+
+/// Rust class struct for `GstTaskPool` and all its subclasses.
+#[repr(C)]
+pub struct TaskPoolClass(ffi::GstTaskPoolClass);
+
+unsafe impl IsClassFor for TaskPoolClass {
+    type Instance = TaskPool;
+}
+
+unsafe impl Send for TaskPoolClass {}
+unsafe impl Sync for TaskPoolClass {}
+
+impl ops::Deref for TaskPoolClass {
+    type Target = glib::ObjectClass;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self as *const Self as *const Self::Target) }
+    }
+}
+
+impl ops::DerefMut for TaskPoolClass {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *(self as *mut Self as *mut Self::Target) }
+    }
+}
+
+unsafe impl<T: ObjectSubclass + TaskPoolImpl> IsSubclassable<T> for TaskPoolClass {
+    fn override_vfuncs(&mut self) {
+        <glib::ObjectClass as IsSubclassable<T>>::override_vfuncs(self);
+        unsafe {
+            let klass = &mut *(self as *const Self as *mut ffi::GstTaskPoolClass);
+            klass.prepare = Some(task_pool_prepare::<T>);
+            klass.cleanup = Some(task_pool_cleanup::<T>);
+            klass.push = Some(task_pool_push::<T>);
+            klass.join = Some(task_pool_join::<T>);
+        }
+    }
+}
+
+unsafe extern "C" fn task_pool_prepare<T: ObjectSubclass>(
+    ptr: *mut ffi::GstTaskPool,
+    error: *mut *mut glib_ffi::GError,
+) where
+    T: TaskPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: TaskPool = from_glib_borrow(ptr);
+
+    let res = panic_to_default(&wrap, None, || Some(imp.prepare(&wrap)))
+        .unwrap_or_else(|| Err(glib::Error::new(::CoreError::Failed, "Panicked")));
+    if let Err(err) = res {
+        if !error.is_null() {
+            let err: *const glib_ffi::GError = err.to_glib_full();
+            *error = err as *mut _;
+        }
+    }
+}
+
+unsafe extern "C" fn task_pool_cleanup<T: ObjectSubclass>(ptr: *mut ffi::GstTaskPool)
+where
+    T: TaskPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: TaskPool = from_glib_borrow(ptr);
+
+    panic_to_default(&wrap, (), || imp.cleanup(&wrap));
+}
+
+unsafe extern "C" fn task_pool_push<T: ObjectSubclass>(
+    ptr: *mut ffi::GstTaskPool,
+    func: ffi::GstTaskPoolFunction,
+    user_data: glib_ffi::gpointer,
+    error: *mut *mut glib_ffi::GError,
+) -> glib_ffi::gpointer
+where
+    T: TaskPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.get_impl();
+    let wrap: TaskPool = from_glib_borrow(ptr);
+
+    let func = TaskPoolFunction(func.expect("Tried to push NULL function"), user_data);
+
+    let res = panic_to_default(&wrap, None, || Some(imp.push(&wrap, func)))
+        .unwrap_or_else(|| Err(glib::Error::new(::CoreError::Failed, "Panicked")));
+    match res {
+        Ok(Some(handle)) => Box::into_raw(Box::new(handle)) as glib_ffi::gpointer,
+        Ok(None) => ptr::null_mut(),
+        Err(err) => {
+            if !error.is_null() {
+                let err: *const glib_ffi::GError = err.to_glib_full();
+                *error = err as *mut _;
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn task_pool_join<T: ObjectSubclass>(
+    ptr: *mut ffi::GstTaskPool,
+    id: glib_ffi::gpointer,
+) where
+    T: TaskPoolImpl,
+{
+    glib_floating_reference_guard!(ptr);
+    let wrap: TaskPool = from_glib_borrow(ptr);
+
+    if id.is_null() {
+        return;
+    }
+
+    let handle = *Box::from_raw(id as *mut T::Handle);
+    panic_to_default(&wrap, (), || handle.join());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib::subclass;
+    use prelude::*;
+    use std::sync::mpsc::channel;
+    use std::sync::Mutex;
+    use std::thread;
+    use Task;
+    use TaskState;
+
+    pub mod imp {
+        use super::*;
+
+        pub struct TestTaskPool;
+
+        impl ObjectSubclass for TestTaskPool {
+            const NAME: &'static str = "TestTaskPool";
+            type ParentType = TaskPool;
+            type Instance = subclass::simple::InstanceStruct<Self>;
+            type Class = subclass::simple::ClassStruct<Self>;
+
+            glib_object_subclass!();
+
+            fn new() -> Self {
+                TestTaskPool
+            }
+        }
+
+        impl ObjectImpl for TestTaskPool {
+            glib_object_impl!();
+        }
+
+        impl TaskPoolImpl for TestTaskPool {
+            type Handle = thread::JoinHandle<()>;
+
+            fn prepare(&self, _task_pool: &TaskPool) -> Result<(), glib::Error> {
+                panic!("Can't prepare");
+            }
+
+            fn push(
+                &self,
+                _task_pool: &TaskPool,
+                func: TaskPoolFunction,
+            ) -> Result<Option<Self::Handle>, glib::Error> {
+                thread::Builder::new()
+                    .name("test-task-pool".into())
+                    .spawn(move || func.call())
+                    .map(Some)
+                    .map_err(|err| glib::Error::new(::CoreError::Failed, &format!("{}", err)))
+            }
+        }
+    }
+
+    #[test]
+    fn test_task_pool_subclass() {
+        ::init().unwrap();
+
+        let pool = glib::Object::new(imp::TestTaskPool::get_type(), &[])
+            .unwrap()
+            .downcast::<TaskPool>()
+            .unwrap();
+
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+        let task = Task::new(move || {
+            let _ = sender
+                .lock()
+                .unwrap()
+                .send(thread::current().name().map(String::from));
+        });
+        task.set_pool(&pool);
+
+        task.start().unwrap();
+        assert_eq!(
+            receiver.recv().unwrap(),
+            Some(String::from("test-task-pool"))
+        );
+
+        task.stop().unwrap();
+        task.join().unwrap();
+        assert_eq!(task.get_state(), TaskState::Stopped);
+
+        assert!(pool.prepare().is_err());
+    }
+}
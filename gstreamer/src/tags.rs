@@ -10,12 +10,19 @@ use std::ffi::CStr;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 
 use ffi;
 use glib;
-use glib::translate::{from_glib, from_glib_full, from_glib_none, ToGlib, ToGlibPtr, ToGlibPtrMut};
-use glib::value::{FromValueOptional, SendValue, SetValue, ToSendValue, TypedValue};
+use glib::translate::{
+    from_glib, from_glib_full, from_glib_none, FromGlib, ToGlib, ToGlibPtr, ToGlibPtrMut,
+};
+use glib::value::{
+    FromValue, FromValueOptional, SendValue, SetValue, ToSendValue, TypedValue, Value,
+};
+use glib::{StaticType, Type};
 use glib_ffi;
+use gobject_ffi;
 
 use miniobject::*;
 
@@ -699,6 +706,191 @@ impl<'a> DoubleEndedIterator for TagListIterator<'a> {
 
 impl<'a> ExactSizeIterator for TagListIterator<'a> {}
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TagFlag {
+    Undefined,
+    Meta,
+    Encoded,
+    Decoded,
+    Count,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl ToGlib for TagFlag {
+    type GlibType = ffi::GstTagFlag;
+
+    fn to_glib(&self) -> ffi::GstTagFlag {
+        match *self {
+            TagFlag::Undefined => ffi::GST_TAG_FLAG_UNDEFINED,
+            TagFlag::Meta => ffi::GST_TAG_FLAG_META,
+            TagFlag::Encoded => ffi::GST_TAG_FLAG_ENCODED,
+            TagFlag::Decoded => ffi::GST_TAG_FLAG_DECODED,
+            TagFlag::Count => ffi::GST_TAG_FLAG_COUNT,
+            TagFlag::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GstTagFlag> for TagFlag {
+    fn from_glib(value: ffi::GstTagFlag) -> Self {
+        skip_assert_initialized!();
+        match value {
+            0 => TagFlag::Undefined,
+            1 => TagFlag::Meta,
+            2 => TagFlag::Encoded,
+            3 => TagFlag::Decoded,
+            4 => TagFlag::Count,
+            value => TagFlag::__Unknown(value),
+        }
+    }
+}
+
+impl StaticType for TagFlag {
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::gst_tag_flag_get_type()) }
+    }
+}
+
+impl<'a> FromValueOptional<'a> for TagFlag {
+    unsafe fn from_value_optional(value: &Value) -> Option<Self> {
+        Some(FromValue::from_value(value))
+    }
+}
+
+impl<'a> FromValue<'a> for TagFlag {
+    unsafe fn from_value(value: &Value) -> Self {
+        from_glib(gobject_ffi::g_value_get_enum(value.to_glib_none().0))
+    }
+}
+
+impl SetValue for TagFlag {
+    unsafe fn set_value(value: &mut Value, this: &Self) {
+        gobject_ffi::g_value_set_enum(value.to_glib_none_mut().0, this.to_glib())
+    }
+}
+
+pub trait CustomTag<'a>: Tag<'a> {
+    const FLAG: ::TagFlag;
+    const NICK: &'static str;
+    const DESCRIPTION: &'static str;
+
+    /// Merges the list of values in `src` into a single value, see
+    /// `merge_use_first()` and `merge_strings_with_comma()`.
+    fn merge_func(src: &Value) -> Value {
+        merge_use_first(src)
+    }
+}
+
+/// Registers the tag `T` so that it can be used with `TagListRef::add()` and
+/// `TagListRef::get()`.
+///
+/// Fails if a tag with the same name is already registered.
+pub fn register<T: for<'a> CustomTag<'a>>() -> Result<(), glib::BoolError>
+where
+    <T as Tag<'static>>::TagType: StaticType,
+{
+    assert_initialized_main_thread!();
+
+    if tag_exists(T::tag_name()) {
+        return Err(glib::BoolError("Tag already registered"));
+    }
+
+    unsafe extern "C" fn merge_func_trampoline<T: for<'a> CustomTag<'a>>(
+        dest: *mut gobject_ffi::GValue,
+        src: *const gobject_ffi::GValue,
+    ) {
+        let src = &*(src as *const Value);
+        // Panics must not unwind into C, fall back to the default merge function
+        let res = panic::catch_unwind(AssertUnwindSafe(|| T::merge_func(src)))
+            .unwrap_or_else(|_| merge_use_first(src));
+        *dest = res.into_raw();
+    }
+
+    unsafe {
+        ffi::gst_tag_register(
+            T::tag_name().to_glib_none().0,
+            T::FLAG.to_glib(),
+            <T as Tag<'static>>::TagType::static_type().to_glib(),
+            T::NICK.to_glib_none().0,
+            T::DESCRIPTION.to_glib_none().0,
+            Some(merge_func_trampoline::<T>),
+        )
+    }
+
+    Ok(())
+}
+
+pub fn merge_use_first(src: &Value) -> Value {
+    skip_assert_initialized!();
+    assert_eq!(src.type_(), ::List::static_type());
+
+    unsafe {
+        let mut res: Value = mem::zeroed();
+        ffi::gst_tag_merge_use_first(res.to_glib_none_mut().0, src.to_glib_none().0);
+        res
+    }
+}
+
+pub fn merge_strings_with_comma(src: &Value) -> Value {
+    skip_assert_initialized!();
+    assert_eq!(src.type_(), ::List::static_type());
+
+    unsafe {
+        let mut res: Value = mem::zeroed();
+        ffi::gst_tag_merge_strings_with_comma(res.to_glib_none_mut().0, src.to_glib_none().0);
+        res
+    }
+}
+
+pub fn tag_exists(name: &str) -> bool {
+    skip_assert_initialized!();
+    unsafe { from_glib(ffi::gst_tag_exists(name.to_glib_none().0)) }
+}
+
+pub fn tag_get_type(name: &str) -> glib::Type {
+    skip_assert_initialized!();
+    unsafe { from_glib(ffi::gst_tag_get_type(name.to_glib_none().0)) }
+}
+
+pub fn tag_get_nick(name: &str) -> Option<&'static str> {
+    skip_assert_initialized!();
+    unsafe {
+        let ptr = ffi::gst_tag_get_nick(name.to_glib_none().0);
+
+        if ptr.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(ptr).to_str().unwrap())
+        }
+    }
+}
+
+pub fn tag_get_description(name: &str) -> Option<&'static str> {
+    skip_assert_initialized!();
+    unsafe {
+        let ptr = ffi::gst_tag_get_description(name.to_glib_none().0);
+
+        if ptr.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(ptr).to_str().unwrap())
+        }
+    }
+}
+
+pub fn tag_get_flag(name: &str) -> ::TagFlag {
+    skip_assert_initialized!();
+    unsafe { from_glib(ffi::gst_tag_get_flag(name.to_glib_none().0)) }
+}
+
+pub fn tag_is_fixed(name: &str) -> bool {
+    skip_assert_initialized!();
+    unsafe { from_glib(ffi::gst_tag_is_fixed(name.to_glib_none().0)) }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -837,4 +1029,62 @@ mod tests {
         assert_eq!(first_duration.get(), Some(::SECOND * 120));
         assert!(tag_iter.next().is_none());
     }
+
+    enum CameraSerial {}
+
+    impl<'a> Tag<'a> for CameraSerial {
+        type TagType = &'a str;
+
+        fn tag_name<'b>() -> &'b str {
+            "test-camera-serial"
+        }
+    }
+
+    impl<'a> CustomTag<'a> for CameraSerial {
+        const FLAG: ::TagFlag = ::TagFlag::Meta;
+        const NICK: &'static str = "camera serial";
+        const DESCRIPTION: &'static str = "Serial number of the capturing camera";
+
+        fn merge_func(src: &Value) -> Value {
+            merge_strings_with_comma(src)
+        }
+    }
+
+    #[test]
+    fn test_custom_tag() {
+        ::init().unwrap();
+
+        assert!(!tag_exists(CameraSerial::tag_name()));
+        register::<CameraSerial>().unwrap();
+        assert!(tag_exists(CameraSerial::tag_name()));
+        assert!(register::<CameraSerial>().is_err());
+        assert_eq!(tag_get_type(CameraSerial::tag_name()), glib::Type::String);
+        assert_eq!(
+            tag_get_nick(CameraSerial::tag_name()),
+            Some("camera serial")
+        );
+        assert_eq!(
+            tag_get_description(CameraSerial::tag_name()),
+            Some("Serial number of the capturing camera")
+        );
+        assert_eq!(tag_get_flag(CameraSerial::tag_name()), ::TagFlag::Meta);
+        assert!(!tag_is_fixed(CameraSerial::tag_name()));
+
+        let mut tags = TagList::new();
+        {
+            let tags = tags.get_mut().unwrap();
+            tags.add::<CameraSerial>(&"1234", TagMergeMode::Append);
+            tags.add::<CameraSerial>(&"5678", TagMergeMode::Append);
+        }
+
+        assert_eq!(tags.get_size::<CameraSerial>(), 2);
+        assert_eq!(
+            tags.get_index::<CameraSerial>(1).unwrap().get(),
+            Some("5678")
+        );
+        assert_eq!(
+            tags.get::<CameraSerial>().unwrap().get(),
+            Some("1234, 5678")
+        );
+    }
 }
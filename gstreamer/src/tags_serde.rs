@@ -50,9 +50,12 @@ impl<'a> Serialize for TagValuesSer<'a> {
         let mut seq = serializer.serialize_seq(tag_iter.size_hint().1)?;
         for value in tag_iter.deref_mut() {
             match value.type_() {
+                glib::Type::Bool => ser_tag!(value, seq, bool),
                 glib::Type::F64 => ser_tag!(value, seq, f64),
                 glib::Type::String => ser_tag!(value, seq, String),
+                glib::Type::I32 => ser_tag!(value, seq, i32),
                 glib::Type::U32 => ser_tag!(value, seq, u32),
+                glib::Type::I64 => ser_tag!(value, seq, i64),
                 glib::Type::U64 => ser_tag!(value, seq, u64),
                 glib::Type::Other(type_id) => {
                     if *DATE_TIME_OTHER_TYPE_ID == type_id {
@@ -141,9 +144,12 @@ impl<'de, 'a> Visitor<'de> for TagValuesVisitor<'a> {
 
         loop {
             let tag_value = match tag_type {
+                glib::Type::Bool => de_tag_value!(self.0, seq, bool),
                 glib::Type::F64 => de_tag_value!(self.0, seq, f64),
                 glib::Type::String => de_tag_value!(self.0, seq, String),
+                glib::Type::I32 => de_tag_value!(self.0, seq, i32),
                 glib::Type::U32 => de_tag_value!(self.0, seq, u32),
+                glib::Type::I64 => de_tag_value!(self.0, seq, i64),
                 glib::Type::U64 => de_tag_value!(self.0, seq, u64),
                 glib::Type::Other(type_id) => {
                     if *DATE_TIME_OTHER_TYPE_ID == type_id {
@@ -496,4 +502,37 @@ mod tests {
             assert_eq!(data.as_slice(), vec![1, 2, 3, 4].as_slice());
         }
     }
+
+    enum SceneId {}
+
+    impl<'a> Tag<'a> for SceneId {
+        type TagType = i64;
+
+        fn tag_name<'b>() -> &'b str {
+            "test-scene-id"
+        }
+    }
+
+    impl<'a> CustomTag<'a> for SceneId {
+        const FLAG: ::TagFlag = ::TagFlag::Meta;
+        const NICK: &'static str = "scene id";
+        const DESCRIPTION: &'static str = "Identifier of the scene";
+    }
+
+    #[test]
+    fn test_serde_custom_tag() {
+        ::init().unwrap();
+
+        register::<SceneId>().unwrap();
+
+        let mut tags = TagList::new();
+        tags.get_mut()
+            .unwrap()
+            .add::<SceneId>(&-42, TagMergeMode::Append);
+
+        let tags_ser = ron::ser::to_string(&tags).unwrap();
+
+        let tags_de: TagList = ron::de::from_str(tags_ser.as_str()).unwrap();
+        assert_eq!(tags_de.get::<SceneId>().unwrap().get(), Some(-42));
+    }
 }
@@ -0,0 +1,332 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::{RefCell, UnsafeCell};
+use std::fmt;
+use std::mem;
+use std::mem::transmute;
+use std::sync::Arc;
+
+use ffi;
+use glib_ffi;
+use glib_ffi::gpointer;
+use gobject_ffi;
+
+use glib;
+use glib::translate::*;
+use glib::IsA;
+
+use Object;
+use TaskPool;
+use TaskState;
+
+glib_wrapper! {
+    pub struct Task(Object<ffi::GstTask, ffi::GstTaskClass>): Object;
+
+    match fn {
+        get_type => || ffi::gst_task_get_type(),
+    }
+}
+
+impl Task {
+    pub fn new<F: FnMut() + Send + 'static>(func: F) -> Task {
+        assert_initialized_main_thread!();
+        let (major, minor, _, _) = ::version();
+        unsafe {
+            let task = ffi::gst_task_new(
+                Some(trampoline_task),
+                into_raw_task(func),
+                Some(destroy_closure_task),
+            );
+            if (major, minor) > (1, 12) {
+                from_glib_full(task)
+            } else {
+                // Work-around for 1.14 switching from transfer-floating to transfer-full
+                from_glib_none(task)
+            }
+        }
+    }
+
+    pub fn cleanup_all() {
+        assert_initialized_main_thread!();
+        unsafe {
+            ffi::gst_task_cleanup_all();
+        }
+    }
+}
+
+unsafe impl Send for Task {}
+unsafe impl Sync for Task {}
+
+pub trait TaskExt {
+    fn get_pool(&self) -> Option<TaskPool>;
+
+    fn get_state(&self) -> TaskState;
+
+    fn join(&self) -> Result<(), glib::BoolError>;
+
+    fn pause(&self) -> Result<(), glib::BoolError>;
+
+    fn set_pool<P: IsA<TaskPool>>(&self, pool: &P);
+
+    fn set_state(&self, state: TaskState) -> Result<(), glib::BoolError>;
+
+    fn start(&self) -> Result<(), glib::BoolError>;
+
+    fn stop(&self) -> Result<(), glib::BoolError>;
+}
+
+impl<O: IsA<Task>> TaskExt for O {
+    fn get_pool(&self) -> Option<TaskPool> {
+        unsafe { from_glib_full(ffi::gst_task_get_pool(self.to_glib_none().0)) }
+    }
+
+    fn get_state(&self) -> TaskState {
+        unsafe { from_glib(ffi::gst_task_get_state(self.to_glib_none().0)) }
+    }
+
+    fn join(&self) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::BoolError::from_glib(
+                ffi::gst_task_join(self.to_glib_none().0),
+                "Failed to join task",
+            )
+        }
+    }
+
+    fn pause(&self) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::BoolError::from_glib(
+                ffi::gst_task_pause(self.to_glib_none().0),
+                "Failed to pause task",
+            )
+        }
+    }
+
+    fn set_pool<P: IsA<TaskPool>>(&self, pool: &P) {
+        unsafe {
+            ffi::gst_task_set_pool(self.to_glib_none().0, pool.to_glib_none().0);
+        }
+    }
+
+    fn set_state(&self, state: TaskState) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::BoolError::from_glib(
+                ffi::gst_task_set_state(self.to_glib_none().0, state.to_glib()),
+                "Failed to set task state",
+            )
+        }
+    }
+
+    fn start(&self) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::BoolError::from_glib(
+                ffi::gst_task_start(self.to_glib_none().0),
+                "Failed to start task",
+            )
+        }
+    }
+
+    fn stop(&self) -> Result<(), glib::BoolError> {
+        unsafe {
+            glib::BoolError::from_glib(
+                ffi::gst_task_stop(self.to_glib_none().0),
+                "Failed to stop task",
+            )
+        }
+    }
+}
+
+/// Recursive lock that is taken by a `Task` around every call of its
+/// function, see `TaskExtManual::set_lock()`.
+#[derive(Clone)]
+pub struct TaskLock(Arc<RecMutex>);
+
+struct RecMutex(Box<UnsafeCell<glib_ffi::GRecMutex>>);
+
+unsafe impl Send for RecMutex {}
+unsafe impl Sync for RecMutex {}
+
+impl Drop for RecMutex {
+    fn drop(&mut self) {
+        unsafe {
+            glib_ffi::g_rec_mutex_clear(self.0.get());
+        }
+    }
+}
+
+impl TaskLock {
+    pub fn new() -> Self {
+        unsafe {
+            let mutex = Box::new(UnsafeCell::new(mem::zeroed()));
+            glib_ffi::g_rec_mutex_init(mutex.get());
+            TaskLock(Arc::new(RecMutex(mutex)))
+        }
+    }
+
+    pub fn lock(&self) -> TaskLockGuard {
+        unsafe {
+            glib_ffi::g_rec_mutex_lock((self.0).0.get());
+        }
+        TaskLockGuard(self)
+    }
+}
+
+impl Default for TaskLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for TaskLock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TaskLock").field(&(self.0).0.get()).finish()
+    }
+}
+
+#[must_use]
+pub struct TaskLockGuard<'a>(&'a TaskLock);
+
+impl<'a> Drop for TaskLockGuard<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            glib_ffi::g_rec_mutex_unlock(((self.0).0).0.get());
+        }
+    }
+}
+
+pub trait TaskExtManual {
+    fn set_enter_callback<F: Fn(&Task) + Send + Sync + 'static>(&self, func: F);
+
+    fn set_leave_callback<F: Fn(&Task) + Send + Sync + 'static>(&self, func: F);
+
+    fn set_lock(&self, lock: &TaskLock);
+}
+
+impl<O: IsA<Task>> TaskExtManual for O {
+    fn set_enter_callback<F: Fn(&Task) + Send + Sync + 'static>(&self, func: F) {
+        unsafe {
+            ffi::gst_task_set_enter_callback(
+                self.to_glib_none().0,
+                Some(trampoline_task_thread),
+                into_raw_task_thread(func),
+                Some(destroy_closure_task_thread),
+            );
+        }
+    }
+
+    fn set_leave_callback<F: Fn(&Task) + Send + Sync + 'static>(&self, func: F) {
+        unsafe {
+            ffi::gst_task_set_leave_callback(
+                self.to_glib_none().0,
+                Some(trampoline_task_thread),
+                into_raw_task_thread(func),
+                Some(destroy_closure_task_thread),
+            );
+        }
+    }
+
+    fn set_lock(&self, lock: &TaskLock) {
+        unsafe {
+            let ptr: *mut ffi::GstTask = self.to_glib_none().0;
+            ffi::gst_task_set_lock(ptr, (lock.0).0.get());
+
+            // The task only borrows the lock, so keep it alive for as long as
+            // the task exists
+            let lock = Box::new(lock.clone());
+            gobject_ffi::g_object_set_qdata_full(
+                ptr as *mut gobject_ffi::GObject,
+                glib_ffi::g_quark_from_static_string(
+                    b"gstreamer-rs-task-lock\0".as_ptr() as *const _
+                ),
+                Box::into_raw(lock) as gpointer,
+                Some(destroy_task_lock),
+            );
+        }
+    }
+}
+
+unsafe extern "C" fn destroy_task_lock(ptr: gpointer) {
+    Box::<TaskLock>::from_raw(ptr as *mut _);
+}
+
+unsafe extern "C" fn trampoline_task(func: gpointer) {
+    #[cfg_attr(feature = "cargo-clippy", allow(transmute_ptr_to_ref))]
+    let func: &RefCell<Box<FnMut() + Send + 'static>> = transmute(func);
+    (&mut *func.borrow_mut())()
+}
+
+unsafe extern "C" fn destroy_closure_task(ptr: gpointer) {
+    Box::<RefCell<Box<FnMut() + Send + 'static>>>::from_raw(ptr as *mut _);
+}
+
+fn into_raw_task<F: FnMut() + Send + 'static>(func: F) -> gpointer {
+    #[cfg_attr(feature = "cargo-clippy", allow(type_complexity))]
+    let func: Box<RefCell<Box<FnMut() + Send + 'static>>> = Box::new(RefCell::new(Box::new(func)));
+    Box::into_raw(func) as gpointer
+}
+
+unsafe extern "C" fn trampoline_task_thread(
+    task: *mut ffi::GstTask,
+    _thread: *mut glib_ffi::GThread,
+    func: gpointer,
+) {
+    #[cfg_attr(feature = "cargo-clippy", allow(transmute_ptr_to_ref))]
+    let func: &&(Fn(&Task) + Send + Sync + 'static) = transmute(func);
+    func(&from_glib_borrow(task))
+}
+
+unsafe extern "C" fn destroy_closure_task_thread(ptr: gpointer) {
+    Box::<Box<Fn(&Task) + Send + Sync + 'static>>::from_raw(ptr as *mut _);
+}
+
+fn into_raw_task_thread<F: Fn(&Task) + Send + Sync + 'static>(func: F) -> gpointer {
+    let func: Box<Box<Fn(&Task) + Send + Sync + 'static>> = Box::new(Box::new(func));
+    Box::into_raw(func) as gpointer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prelude::*;
+    use std::sync::mpsc::channel;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_task() {
+        ::init().unwrap();
+
+        let (sender, receiver) = channel();
+        let sender = Mutex::new(sender);
+        let (enter_sender, enter_receiver) = channel();
+        let enter_sender = Mutex::new(enter_sender);
+
+        let lock = TaskLock::new();
+        let task = Task::new(move || {
+            let _ = sender.lock().unwrap().send(());
+        });
+        task.set_lock(&lock);
+        task.set_enter_callback(move |_task| {
+            let _ = enter_sender.lock().unwrap().send(());
+        });
+        assert_eq!(task.get_state(), ::TaskState::Stopped);
+
+        {
+            // The task function can't run while the lock is held
+            let _guard = lock.lock();
+            task.start().unwrap();
+            enter_receiver.recv().unwrap();
+            assert_eq!(task.get_state(), ::TaskState::Started);
+        }
+        receiver.recv().unwrap();
+
+        task.stop().unwrap();
+        task.join().unwrap();
+        assert_eq!(task.get_state(), ::TaskState::Stopped);
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::ptr;
+
+use ffi;
+use glib;
+use glib::translate::*;
+use glib::IsA;
+
+use Object;
+
+glib_wrapper! {
+    pub struct TaskPool(Object<ffi::GstTaskPool, ffi::GstTaskPoolClass>): Object;
+
+    match fn {
+        get_type => || ffi::gst_task_pool_get_type(),
+    }
+}
+
+impl TaskPool {
+    pub fn new() -> TaskPool {
+        assert_initialized_main_thread!();
+        let (major, minor, _, _) = ::version();
+        if (major, minor) > (1, 12) {
+            unsafe { from_glib_full(ffi::gst_task_pool_new()) }
+        } else {
+            // Work-around for 1.14 switching from transfer-floating to transfer-full
+            unsafe { from_glib_none(ffi::gst_task_pool_new()) }
+        }
+    }
+}
+
+impl Default for TaskPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl Send for TaskPool {}
+unsafe impl Sync for TaskPool {}
+
+pub trait TaskPoolExt {
+    fn cleanup(&self);
+
+    fn prepare(&self) -> Result<(), glib::Error>;
+}
+
+impl<O: IsA<TaskPool>> TaskPoolExt for O {
+    fn cleanup(&self) {
+        unsafe {
+            ffi::gst_task_pool_cleanup(self.to_glib_none().0);
+        }
+    }
+
+    fn prepare(&self) -> Result<(), glib::Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            ffi::gst_task_pool_prepare(self.to_glib_none().0, &mut error);
+            if error.is_null() {
+                Ok(())
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright (C) 2019 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use glib::{Cast, ObjectExt};
+use Bin;
+use BinExtManual;
+use Caps;
+use ClockTime;
+use Element;
+use ElementExt;
+use ElementExtManual;
+use GhostPad;
+use GhostPadExt;
+use GstObjectExt;
+use IteratorError;
+use PadDirection;
+use PadExt;
+use State;
+
+/// Snapshot of the elements, pads and links inside a `Bin`.
+///
+/// Elements and pads are identified by their path strings as returned by
+/// `GstObjectExt::get_path_string()`. Created by `BinExtManual::topology()`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
+pub struct Topology {
+    /// All elements, starting with the bin itself and followed by its
+    /// children sorted by name, recursing into child bins.
+    pub nodes: Vec<TopologyNode>,
+    /// All links between source pads and their peers.
+    pub links: Vec<TopologyLink>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
+pub struct TopologyNode {
+    pub path: String,
+    pub name: String,
+    pub type_name: String,
+    pub factory_name: Option<String>,
+    /// Path of the bin containing this element, if any.
+    pub parent: Option<String>,
+    pub is_bin: bool,
+    pub current_state: State,
+    pub pending_state: State,
+    pub pads: Vec<TopologyPad>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
+pub struct TopologyPad {
+    pub path: String,
+    pub name: String,
+    pub direction: PadDirection,
+    /// Negotiated caps of the pad.
+    pub caps: Option<Caps>,
+    /// Path of the peer pad, if linked.
+    pub peer: Option<String>,
+    /// Path of the target pad if this is a ghost pad.
+    pub target: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
+pub struct TopologyLink {
+    /// Path of the source pad.
+    pub src: String,
+    /// Path of the sink pad.
+    pub sink: String,
+    /// Negotiated caps of the link.
+    pub caps: Option<Caps>,
+}
+
+impl Topology {
+    pub(crate) fn new(bin: &Bin) -> Self {
+        let mut topology = Topology {
+            nodes: Vec::new(),
+            links: Vec::new(),
+        };
+
+        topology.add_element(bin.upcast_ref(), None);
+
+        topology
+    }
+
+    fn add_element(&mut self, element: &Element, parent: Option<String>) {
+        let path = element.get_path_string();
+        let (_, current_state, pending_state) = element.get_state(ClockTime::from_seconds(0));
+
+        let pads = element
+            .get_pads()
+            .iter()
+            .map(|pad| {
+                let peer = pad.get_peer();
+                let caps = pad.get_current_caps();
+
+                // Links are only recorded once, from the source pad side
+                if let Some(ref peer) = peer {
+                    if pad.get_direction() == PadDirection::Src {
+                        self.links.push(TopologyLink {
+                            src: pad.get_path_string(),
+                            sink: peer.get_path_string(),
+                            caps: caps.clone(),
+                        });
+                    }
+                }
+
+                TopologyPad {
+                    path: pad.get_path_string(),
+                    name: pad.get_name(),
+                    direction: pad.get_direction(),
+                    caps,
+                    peer: peer.map(|peer| peer.get_path_string()),
+                    target: pad
+                        .downcast_ref::<GhostPad>()
+                        .and_then(|pad| pad.get_target())
+                        .map(|target| target.get_path_string()),
+                }
+            })
+            .collect();
+
+        let bin = element.downcast_ref::<Bin>();
+
+        self.nodes.push(TopologyNode {
+            path: path.clone(),
+            name: element.get_name(),
+            type_name: element.get_type().name(),
+            factory_name: element.get_factory().map(|factory| factory.get_name()),
+            parent,
+            is_bin: bin.is_some(),
+            current_state,
+            pending_state,
+            pads,
+        });
+
+        if let Some(bin) = bin {
+            for child in get_sorted_children(bin) {
+                self.add_element(&child, Some(path.clone()));
+            }
+        }
+    }
+}
+
+fn get_sorted_children(bin: &Bin) -> Vec<Element> {
+    let mut children = Vec::new();
+
+    let mut iter = bin.iterate_elements();
+    loop {
+        match iter.next() {
+            Ok(Some(child)) => children.push(child),
+            Ok(None) => break,
+            Err(IteratorError::Resync) => {
+                children.clear();
+                iter.resync();
+            }
+            Err(IteratorError::Error) => break,
+        }
+    }
+
+    // Sorted so that the snapshot does not depend on the order in which the
+    // children were added to the bin
+    children.sort_by_key(|child| child.get_name());
+
+    children
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prelude::*;
+
+    #[test]
+    fn test_topology() {
+        ::init().unwrap();
+
+        let pipeline = ::Pipeline::new("pipeline");
+        let src = ::ElementFactory::make("fakesrc", "src").unwrap();
+        let bin = ::Bin::new("bin");
+        let identity = ::ElementFactory::make("identity", "identity").unwrap();
+        let sink = ::ElementFactory::make("fakesink", "sink").unwrap();
+
+        bin.add(&identity).unwrap();
+        let sinkpad = GhostPad::new("sink", &identity.get_static_pad("sink").unwrap()).unwrap();
+        let srcpad = GhostPad::new("src", &identity.get_static_pad("src").unwrap()).unwrap();
+        bin.add_pad(&sinkpad).unwrap();
+        bin.add_pad(&srcpad).unwrap();
+
+        pipeline.add_many(&[&src, bin.upcast_ref(), &sink]).unwrap();
+        Element::link_many(&[&src, bin.upcast_ref(), &sink]).unwrap();
+
+        let topology = pipeline.topology();
+
+        let paths = topology
+            .nodes
+            .iter()
+            .map(|node| node.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "/GstPipeline:pipeline",
+                "/GstPipeline:pipeline/GstBin:bin",
+                "/GstPipeline:pipeline/GstBin:bin/GstIdentity:identity",
+                "/GstPipeline:pipeline/GstFakeSink:sink",
+                "/GstPipeline:pipeline/GstFakeSrc:src",
+            ]
+        );
+
+        let bin_node = &topology.nodes[1];
+        assert!(bin_node.is_bin);
+        assert_eq!(
+            bin_node.parent.as_ref().map(String::as_str),
+            Some("/GstPipeline:pipeline")
+        );
+        assert_eq!(bin_node.current_state, State::Null);
+        assert_eq!(
+            bin_node.pads[0].target.as_ref().map(String::as_str),
+            Some("/GstPipeline:pipeline/GstBin:bin/GstIdentity:identity.GstPad:sink")
+        );
+
+        let links = topology
+            .links
+            .iter()
+            .map(|link| (link.src.as_str(), link.sink.as_str()))
+            .collect::<Vec<_>>();
+        assert!(links.contains(&(
+            "/GstPipeline:pipeline/GstFakeSrc:src.GstPad:src",
+            "/GstPipeline:pipeline/GstBin:bin.GstGhostPad:sink"
+        )));
+        assert!(links.contains(&(
+            "/GstPipeline:pipeline/GstBin:bin.GstGhostPad:src",
+            "/GstPipeline:pipeline/GstFakeSink:sink.GstPad:sink"
+        )));
+    }
+}
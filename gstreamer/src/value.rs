@@ -10,6 +10,8 @@ use num_rational::Rational32;
 use std::borrow::{Borrow, Cow};
 use std::cmp;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
 use std::ops;
 use std::slice;
 
@@ -269,6 +271,8 @@ impl IntRange<i64> {
     }
 }
 
+pub type Int64Range = IntRange<i64>;
+
 impl From<(i32, i32)> for IntRange<i32> {
     fn from((min, max): (i32, i32)) -> Self {
         skip_assert_initialized!();
@@ -297,6 +301,20 @@ impl From<(i64, i64, i64)> for IntRange<i64> {
     }
 }
 
+impl From<ops::RangeInclusive<i32>> for IntRange<i32> {
+    fn from(range: ops::RangeInclusive<i32>) -> Self {
+        skip_assert_initialized!();
+        Self::new(*range.start(), *range.end())
+    }
+}
+
+impl From<ops::RangeInclusive<i64>> for IntRange<i64> {
+    fn from(range: ops::RangeInclusive<i64>) -> Self {
+        skip_assert_initialized!();
+        Self::new(*range.start(), *range.end())
+    }
+}
+
 impl glib::types::StaticType for IntRange<i32> {
     fn static_type() -> glib::types::Type {
         unsafe { from_glib(ffi::gst_int_range_get_type()) }
@@ -389,6 +407,14 @@ impl From<(Fraction, Fraction)> for FractionRange {
     }
 }
 
+impl From<ops::RangeInclusive<Fraction>> for FractionRange {
+    fn from(range: ops::RangeInclusive<Fraction>) -> Self {
+        skip_assert_initialized!();
+
+        Self::new(*range.start(), *range.end())
+    }
+}
+
 impl glib::types::StaticType for FractionRange {
     fn static_type() -> glib::types::Type {
         unsafe { from_glib(ffi::gst_fraction_range_get_type()) }
@@ -427,6 +453,150 @@ impl SetValue for FractionRange {
     }
 }
 
+// Deserialization is implemented manually to check that min < max
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "ser_de", derive(Serialize))]
+pub struct DoubleRange {
+    min: f64,
+    max: f64,
+}
+
+impl DoubleRange {
+    pub fn new(min: f64, max: f64) -> Self {
+        assert_initialized_main_thread!();
+
+        assert!(min < max);
+
+        DoubleRange { min, max }
+    }
+
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+}
+
+impl From<(f64, f64)> for DoubleRange {
+    fn from((min, max): (f64, f64)) -> Self {
+        skip_assert_initialized!();
+
+        Self::new(min, max)
+    }
+}
+
+impl From<ops::RangeInclusive<f64>> for DoubleRange {
+    fn from(range: ops::RangeInclusive<f64>) -> Self {
+        skip_assert_initialized!();
+
+        Self::new(*range.start(), *range.end())
+    }
+}
+
+impl glib::types::StaticType for DoubleRange {
+    fn static_type() -> glib::types::Type {
+        unsafe { from_glib(ffi::gst_double_range_get_type()) }
+    }
+}
+
+impl<'a> FromValue<'a> for DoubleRange {
+    unsafe fn from_value(v: &'a Value) -> Self {
+        let min = ffi::gst_value_get_double_range_min(v.to_glib_none().0);
+        let max = ffi::gst_value_get_double_range_max(v.to_glib_none().0);
+
+        Self::new(min, max)
+    }
+}
+
+impl<'a> FromValueOptional<'a> for DoubleRange {
+    unsafe fn from_value_optional(v: &'a Value) -> Option<Self> {
+        Some(Self::from_value(v))
+    }
+}
+
+impl SetValue for DoubleRange {
+    unsafe fn set_value(v: &mut Value, r: &Self) {
+        ffi::gst_value_set_double_range(v.to_glib_none_mut().0, r.min(), r.max());
+    }
+}
+
+// Bits of `flags` that are not set in `mask` are ignored by GStreamer, so
+// they are also not considered when comparing flag sets
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
+pub struct FlagSet {
+    flags: u32,
+    mask: u32,
+}
+
+impl FlagSet {
+    pub fn new(flags: u32, mask: u32) -> Self {
+        assert_initialized_main_thread!();
+
+        FlagSet { flags, mask }
+    }
+
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+}
+
+impl PartialEq for FlagSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.mask == other.mask && self.flags & self.mask == other.flags & other.mask
+    }
+}
+
+impl Eq for FlagSet {}
+
+impl Hash for FlagSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.flags & self.mask).hash(state);
+        self.mask.hash(state);
+    }
+}
+
+impl From<(u32, u32)> for FlagSet {
+    fn from((flags, mask): (u32, u32)) -> Self {
+        skip_assert_initialized!();
+
+        Self::new(flags, mask)
+    }
+}
+
+impl glib::types::StaticType for FlagSet {
+    fn static_type() -> glib::types::Type {
+        unsafe { from_glib(ffi::gst_flagset_get_type()) }
+    }
+}
+
+impl<'a> FromValue<'a> for FlagSet {
+    unsafe fn from_value(v: &'a Value) -> Self {
+        let flags = ffi::gst_value_get_flagset_flags(v.to_glib_none().0);
+        let mask = ffi::gst_value_get_flagset_mask(v.to_glib_none().0);
+
+        Self::new(flags, mask)
+    }
+}
+
+impl<'a> FromValueOptional<'a> for FlagSet {
+    unsafe fn from_value_optional(v: &'a Value) -> Option<Self> {
+        Some(Self::from_value(v))
+    }
+}
+
+impl SetValue for FlagSet {
+    unsafe fn set_value(v: &mut Value, f: &Self) {
+        ffi::gst_value_set_flagset(v.to_glib_none_mut().0, f.flags(), f.mask());
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "ser_de", derive(Serialize, Deserialize))]
 pub struct Bitmask(pub u64);
@@ -687,158 +857,336 @@ pub trait GstValueExt: Sized {
     fn is_subset(&self, superset: &Self) -> bool;
     fn serialize(&self) -> Option<String>;
     fn deserialize<'a, T: Into<&'a str>>(s: T) -> Option<glib::Value>;
+    // The value is deserialized as `type_`, GStreamer can't infer the
+    // type from the string
+    fn deserialize_with_type<'a, T: Into<&'a str>>(s: T, type_: glib::Type) -> Option<glib::Value>;
 }
 
-impl GstValueExt for glib::Value {
-    fn can_compare(&self, other: &Self) -> bool {
-        unsafe {
-            from_glib(ffi::gst_value_can_compare(
-                self.to_glib_none().0,
-                other.to_glib_none().0,
-            ))
-        }
+// Converts the results of the value operations into the type they were called
+// on
+trait FromGlibValue {
+    unsafe fn from_glib_value(value: glib::Value) -> Self;
+}
+
+impl FromGlibValue for glib::Value {
+    unsafe fn from_glib_value(value: glib::Value) -> Self {
+        value
     }
+}
 
-    fn compare(&self, other: &Self) -> Option<cmp::Ordering> {
-        unsafe {
-            let val = ffi::gst_value_compare(self.to_glib_none().0, other.to_glib_none().0);
+impl FromGlibValue for glib::SendValue {
+    // The results only contain copies of or references to the input values,
+    // which are `Send` here
+    unsafe fn from_glib_value(value: glib::Value) -> Self {
+        mem::transmute(value)
+    }
+}
 
-            match val {
-                ffi::GST_VALUE_LESS_THAN => Some(cmp::Ordering::Less),
-                ffi::GST_VALUE_EQUAL => Some(cmp::Ordering::Equal),
-                ffi::GST_VALUE_GREATER_THAN => Some(cmp::Ordering::Greater),
-                _ => None,
+macro_rules! impl_gst_value_ext(
+    ($name:ty) => {
+        impl GstValueExt for $name {
+            fn can_compare(&self, other: &Self) -> bool {
+                unsafe {
+                    from_glib(ffi::gst_value_can_compare(
+                        self.to_glib_none().0,
+                        other.to_glib_none().0,
+                    ))
+                }
             }
-        }
-    }
 
-    fn eq(&self, other: &Self) -> bool {
-        self.compare(other) == Some(cmp::Ordering::Equal)
-    }
+            fn compare(&self, other: &Self) -> Option<cmp::Ordering> {
+                unsafe {
+                    let val = ffi::gst_value_compare(self.to_glib_none().0, other.to_glib_none().0);
+
+                    match val {
+                        ffi::GST_VALUE_LESS_THAN => Some(cmp::Ordering::Less),
+                        ffi::GST_VALUE_EQUAL => Some(cmp::Ordering::Equal),
+                        ffi::GST_VALUE_GREATER_THAN => Some(cmp::Ordering::Greater),
+                        _ => None,
+                    }
+                }
+            }
 
-    fn can_intersect(&self, other: &Self) -> bool {
-        unsafe {
-            from_glib(ffi::gst_value_can_intersect(
-                self.to_glib_none().0,
-                other.to_glib_none().0,
-            ))
-        }
-    }
+            fn eq(&self, other: &Self) -> bool {
+                self.compare(other) == Some(cmp::Ordering::Equal)
+            }
 
-    fn intersect(&self, other: &Self) -> Option<Self> {
-        unsafe {
-            let mut value = glib::Value::uninitialized();
-            let ret: bool = from_glib(ffi::gst_value_intersect(
-                value.to_glib_none_mut().0,
-                self.to_glib_none().0,
-                other.to_glib_none().0,
-            ));
-            if ret {
-                Some(value)
-            } else {
-                None
+            fn can_intersect(&self, other: &Self) -> bool {
+                unsafe {
+                    from_glib(ffi::gst_value_can_intersect(
+                        self.to_glib_none().0,
+                        other.to_glib_none().0,
+                    ))
+                }
             }
-        }
-    }
 
-    fn can_subtract(&self, other: &Self) -> bool {
-        unsafe {
-            from_glib(ffi::gst_value_can_subtract(
-                self.to_glib_none().0,
-                other.to_glib_none().0,
-            ))
-        }
-    }
+            fn intersect(&self, other: &Self) -> Option<Self> {
+                unsafe {
+                    let mut value = glib::Value::uninitialized();
+                    let ret: bool = from_glib(ffi::gst_value_intersect(
+                        value.to_glib_none_mut().0,
+                        self.to_glib_none().0,
+                        other.to_glib_none().0,
+                    ));
+                    if ret {
+                        Some(<$name>::from_glib_value(value))
+                    } else {
+                        None
+                    }
+                }
+            }
 
-    fn subtract(&self, other: &Self) -> Option<Self> {
-        unsafe {
-            let mut value = glib::Value::uninitialized();
-            let ret: bool = from_glib(ffi::gst_value_subtract(
-                value.to_glib_none_mut().0,
-                self.to_glib_none().0,
-                other.to_glib_none().0,
-            ));
-            if ret {
-                Some(value)
-            } else {
-                None
+            fn can_subtract(&self, other: &Self) -> bool {
+                unsafe {
+                    from_glib(ffi::gst_value_can_subtract(
+                        self.to_glib_none().0,
+                        other.to_glib_none().0,
+                    ))
+                }
             }
-        }
-    }
 
-    fn can_union(&self, other: &Self) -> bool {
-        unsafe {
-            from_glib(ffi::gst_value_can_union(
-                self.to_glib_none().0,
-                other.to_glib_none().0,
-            ))
-        }
-    }
+            fn subtract(&self, other: &Self) -> Option<Self> {
+                unsafe {
+                    let mut value = glib::Value::uninitialized();
+                    let ret: bool = from_glib(ffi::gst_value_subtract(
+                        value.to_glib_none_mut().0,
+                        self.to_glib_none().0,
+                        other.to_glib_none().0,
+                    ));
+                    if ret {
+                        Some(<$name>::from_glib_value(value))
+                    } else {
+                        None
+                    }
+                }
+            }
 
-    fn union(&self, other: &Self) -> Option<Self> {
-        unsafe {
-            let mut value = glib::Value::uninitialized();
-            let ret: bool = from_glib(ffi::gst_value_union(
-                value.to_glib_none_mut().0,
-                self.to_glib_none().0,
-                other.to_glib_none().0,
-            ));
-            if ret {
-                Some(value)
-            } else {
-                None
+            fn can_union(&self, other: &Self) -> bool {
+                unsafe {
+                    from_glib(ffi::gst_value_can_union(
+                        self.to_glib_none().0,
+                        other.to_glib_none().0,
+                    ))
+                }
+            }
+
+            fn union(&self, other: &Self) -> Option<Self> {
+                unsafe {
+                    let mut value = glib::Value::uninitialized();
+                    let ret: bool = from_glib(ffi::gst_value_union(
+                        value.to_glib_none_mut().0,
+                        self.to_glib_none().0,
+                        other.to_glib_none().0,
+                    ));
+                    if ret {
+                        Some(<$name>::from_glib_value(value))
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            fn fixate(&self) -> Option<Self> {
+                unsafe {
+                    let mut value = glib::Value::uninitialized();
+                    let ret: bool = from_glib(ffi::gst_value_fixate(
+                        value.to_glib_none_mut().0,
+                        self.to_glib_none().0,
+                    ));
+                    if ret {
+                        Some(<$name>::from_glib_value(value))
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            fn is_fixed(&self) -> bool {
+                unsafe { from_glib(ffi::gst_value_is_fixed(self.to_glib_none().0)) }
+            }
+
+            fn is_subset(&self, superset: &Self) -> bool {
+                unsafe {
+                    from_glib(ffi::gst_value_is_subset(
+                        self.to_glib_none().0,
+                        superset.to_glib_none().0,
+                    ))
+                }
+            }
+
+            fn serialize(&self) -> Option<String> {
+                unsafe { from_glib_full(ffi::gst_value_serialize(self.to_glib_none().0)) }
             }
-        }
-    }
 
-    fn fixate(&self) -> Option<Self> {
-        unsafe {
-            let mut value = glib::Value::uninitialized();
-            let ret: bool = from_glib(ffi::gst_value_fixate(
-                value.to_glib_none_mut().0,
-                self.to_glib_none().0,
-            ));
-            if ret {
-                Some(value)
-            } else {
-                None
+            fn deserialize<'a, T: Into<&'a str>>(s: T) -> Option<glib::Value> {
+                assert_initialized_main_thread!();
+
+                let s = s.into();
+
+                unsafe {
+                    let mut value = glib::Value::uninitialized();
+                    let ret: bool = from_glib(ffi::gst_value_deserialize(
+                        value.to_glib_none_mut().0,
+                        s.to_glib_none().0,
+                    ));
+                    if ret {
+                        Some(value)
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            fn deserialize_with_type<'a, T: Into<&'a str>>(
+                s: T,
+                type_: glib::Type,
+            ) -> Option<glib::Value> {
+                assert_initialized_main_thread!();
+
+                let s = s.into();
+
+                unsafe {
+                    let mut value = glib::Value::from_type(type_);
+                    let ret: bool = from_glib(ffi::gst_value_deserialize(
+                        value.to_glib_none_mut().0,
+                        s.to_glib_none().0,
+                    ));
+                    if ret {
+                        Some(value)
+                    } else {
+                        None
+                    }
+                }
             }
         }
+    };
+);
+
+impl_gst_value_ext!(glib::Value);
+impl_gst_value_ext!(glib::SendValue);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glib::{StaticType, ToValue};
+
+    #[test]
+    fn test_intersect_union_subtract() {
+        ::init().unwrap();
+
+        let a = IntRange::<i32>::new(0, 10).to_value();
+        let b = IntRange::<i32>::new(5, 20).to_value();
+
+        assert!(a.can_intersect(&b));
+        let res = a.intersect(&b).unwrap();
+        assert_eq!(
+            res.get::<IntRange<i32>>(),
+            Some(IntRange::<i32>::new(5, 10))
+        );
+
+        let res = a.union(&3.to_value()).unwrap();
+        assert_eq!(
+            res.get::<IntRange<i32>>(),
+            Some(IntRange::<i32>::new(0, 10))
+        );
+
+        let res = a.subtract(&b).unwrap();
+        assert_eq!(res.get::<IntRange<i32>>(), Some(IntRange::<i32>::new(0, 4)));
+
+        assert!(a.intersect(&30.to_value()).is_none());
+
+        let a = DoubleRange::new(0.0, 1.0).to_value();
+        let b = DoubleRange::new(0.5, 2.0).to_value();
+        let res = a.intersect(&b).unwrap();
+        assert_eq!(res.get::<DoubleRange>(), Some(DoubleRange::new(0.5, 1.0)));
+
+        let a = Int64Range::new(0, 1 << 40).to_value();
+        let res = a.intersect(&(1i64 << 35).to_value()).unwrap();
+        assert_eq!(res.get::<i64>(), Some(1 << 35));
     }
 
-    fn is_fixed(&self) -> bool {
-        unsafe { from_glib(ffi::gst_value_is_fixed(self.to_glib_none().0)) }
+    #[test]
+    fn test_compare_subset_fixate() {
+        ::init().unwrap();
+
+        assert_eq!(
+            1.to_value().compare(&2.to_value()),
+            Some(cmp::Ordering::Less)
+        );
+        assert_eq!(
+            Fraction::new(1, 2)
+                .to_value()
+                .compare(&Fraction::new(2, 4).to_value()),
+            Some(cmp::Ordering::Equal)
+        );
+        assert_eq!(
+            IntRange::<i32>::new(0, 10)
+                .to_value()
+                .compare(&IntRange::<i32>::new(0, 5).to_value()),
+            None
+        );
+
+        let small = IntRange::<i32>::new(2, 3).to_value();
+        let big = IntRange::<i32>::new(0, 10).to_value();
+        assert!(small.is_subset(&big));
+        assert!(!big.is_subset(&small));
+
+        assert!(!big.is_fixed());
+        assert_eq!(big.fixate().unwrap().get::<i32>(), Some(0));
+        let range = DoubleRange::new(0.5, 1.5).to_value();
+        assert_eq!(range.fixate().unwrap().get::<f64>(), Some(0.5));
     }
 
-    fn is_subset(&self, superset: &Self) -> bool {
-        unsafe {
-            from_glib(ffi::gst_value_is_subset(
-                self.to_glib_none().0,
-                superset.to_glib_none().0,
-            ))
-        }
+    #[test]
+    fn test_flag_set() {
+        ::init().unwrap();
+
+        let flag_set = FlagSet::new(0b101, 0b011);
+        assert_eq!(flag_set.flags(), 0b101);
+        assert_eq!(flag_set.mask(), 0b011);
+        assert_eq!(flag_set, FlagSet::new(0b001, 0b011));
+        assert_ne!(flag_set, FlagSet::new(0b001, 0b111));
+
+        let value = flag_set.to_value();
+        assert_eq!(value.get::<FlagSet>(), Some(flag_set));
+        let value_de = value.get::<FlagSet>().unwrap().to_value();
+        assert_eq!(value_de.compare(&value), Some(cmp::Ordering::Equal));
+
+        // Only the masked bits have to match
+        let other = FlagSet::new(0b111, 0b001).to_value();
+        assert!(value.can_intersect(&other));
+        assert!(value.intersect(&other).is_some());
+        let other = FlagSet::new(0b000, 0b001).to_value();
+        assert!(value.intersect(&other).is_none());
     }
 
-    fn serialize(&self) -> Option<String> {
-        unsafe { from_glib_full(ffi::gst_value_serialize(self.to_glib_none().0)) }
+    #[test]
+    fn test_serialize_deserialize() {
+        ::init().unwrap();
+
+        let value = IntRange::<i32>::new(0, 10).to_value();
+        let s = value.serialize().unwrap();
+        assert_eq!(s, "[ 0, 10 ]");
+
+        let value_de =
+            glib::Value::deserialize_with_type(s.as_str(), IntRange::<i32>::static_type()).unwrap();
+        assert_eq!(value_de.compare(&value), Some(cmp::Ordering::Equal));
+
+        assert!(glib::Value::deserialize_with_type("foo", i32::static_type()).is_none());
     }
 
-    fn deserialize<'a, T: Into<&'a str>>(s: T) -> Option<glib::Value> {
-        assert_initialized_main_thread!();
+    #[test]
+    fn test_send_value() {
+        ::init().unwrap();
 
-        let s = s.into();
-
-        unsafe {
-            let mut value = glib::Value::uninitialized();
-            let ret: bool = from_glib(ffi::gst_value_deserialize(
-                value.to_glib_none_mut().0,
-                s.to_glib_none().0,
-            ));
-            if ret {
-                Some(value)
-            } else {
-                None
-            }
-        }
+        let a = IntRange::<i32>::new(0, 10)
+            .to_value()
+            .try_into_send_value::<IntRange<i32>>()
+            .unwrap();
+        let b = 5.to_value().try_into_send_value::<i32>().unwrap();
+
+        let res: glib::SendValue = a.intersect(&b).unwrap();
+        assert_eq!(res.get::<i32>(), Some(5));
+        assert!(b.is_subset(&a));
     }
 }
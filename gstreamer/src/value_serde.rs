@@ -34,6 +34,8 @@ lazy_static! {
     pub(crate) static ref ARRAY_OTHER_TYPE_ID: usize = get_other_type_id::<Array>();
     pub(crate) static ref BITMASK_OTHER_TYPE_ID: usize = get_other_type_id::<Bitmask>();
     pub(crate) static ref DATE_TIME_OTHER_TYPE_ID: usize = get_other_type_id::<DateTime>();
+    pub(crate) static ref DOUBLE_RANGE_OTHER_TYPE_ID: usize = get_other_type_id::<DoubleRange>();
+    pub(crate) static ref FLAG_SET_OTHER_TYPE_ID: usize = get_other_type_id::<FlagSet>();
     pub(crate) static ref FRACTION_OTHER_TYPE_ID: usize = get_other_type_id::<Fraction>();
     pub(crate) static ref FRACTION_RANGE_OTHER_TYPE_ID: usize =
         get_other_type_id::<FractionRange>();
@@ -56,6 +58,27 @@ impl<'de> Deserialize<'de> for Fraction {
     }
 }
 
+#[derive(Deserialize)]
+#[serde(rename = "DoubleRange")]
+struct DoubleRangeDe {
+    min: f64,
+    max: f64,
+}
+
+impl<'de> Deserialize<'de> for DoubleRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let range = DoubleRangeDe::deserialize(deserializer)?;
+        if range.min < range.max {
+            Ok(DoubleRange::new(range.min, range.max))
+        } else {
+            Err(de::Error::custom(format!(
+                "DoubleRange min {} must be less than max {}",
+                range.min, range.max
+            )))
+        }
+    }
+}
+
 macro_rules! ser_value (
     ($value:expr, $t:ty, $ser_closure:expr) => (
         {
@@ -82,6 +105,10 @@ macro_rules! ser_value (
                     ser_value!($value, Bitmask, $ser_closure)
                 } else if *DATE_TIME_OTHER_TYPE_ID == type_id {
                     ser_value!($value, DateTime, $ser_closure)
+                } else if *DOUBLE_RANGE_OTHER_TYPE_ID == type_id {
+                    ser_value!($value, DoubleRange, $ser_closure)
+                } else if *FLAG_SET_OTHER_TYPE_ID == type_id {
+                    ser_value!($value, FlagSet, $ser_closure)
                 } else if *FRACTION_OTHER_TYPE_ID == type_id {
                     ser_value!($value, Fraction, $ser_closure)
                 } else if *FRACTION_RANGE_OTHER_TYPE_ID == type_id {
@@ -203,6 +230,8 @@ macro_rules! de_send_value(
             "Array" => de_send_value!($type_name, $seq, Array),
             "Bitmask" => de_send_value!($type_name, $seq, Bitmask),
             "DateTime" => de_send_value!($type_name, $seq, DateTime),
+            "DoubleRange" => de_send_value!($type_name, $seq, DoubleRange),
+            "FlagSet" => de_send_value!($type_name, $seq, FlagSet),
             "Fraction" => de_send_value!($type_name, $seq, Fraction),
             "FractionRange" => de_send_value!($type_name, $seq, FractionRange),
             "IntRange<i32>" => de_send_value!($type_name, $seq, IntRange<i32>),
@@ -267,6 +296,8 @@ mod tests {
 
     use Array;
     use Bitmask;
+    use DoubleRange;
+    use FlagSet;
     use Fraction;
     use FractionRange;
     use IntRange;
@@ -313,6 +344,24 @@ mod tests {
 
         let res = serde_json::to_string(&bitmask).unwrap();
         assert_eq!("1184".to_owned(), res);
+
+        // DoubleRange
+        let double_range = DoubleRange::new(0.5, 1.5);
+
+        let res = ron::ser::to_string_pretty(&double_range, pretty_config.clone());
+        assert_eq!(Ok("(    min: 0.5,    max: 1.5,)".to_owned()), res);
+
+        let res = serde_json::to_string(&double_range).unwrap();
+        assert_eq!("{\"min\":0.5,\"max\":1.5}".to_owned(), res);
+
+        // FlagSet
+        let flag_set = FlagSet::new(0b101, 0b011);
+
+        let res = ron::ser::to_string_pretty(&flag_set, pretty_config.clone());
+        assert_eq!(Ok("(    flags: 5,    mask: 3,)".to_owned()), res);
+
+        let res = serde_json::to_string(&flag_set).unwrap();
+        assert_eq!("{\"flags\":5,\"mask\":3}".to_owned(), res);
     }
 
     #[test]
@@ -430,6 +479,20 @@ mod tests {
         let bitmask_json = "1184";
         let bitmask: Bitmask = serde_json::from_str(bitmask_json).unwrap();
         assert_eq!(bitmask_ref, bitmask);
+
+        // DoubleRange
+        let double_range_json = "{\"min\":0.5,\"max\":1.5}";
+        let double_range: DoubleRange = serde_json::from_str(double_range_json).unwrap();
+        assert_eq!(double_range, DoubleRange::new(0.5, 1.5));
+
+        let double_range_json = "{\"min\":1.5,\"max\":0.5}";
+        assert!(serde_json::from_str::<DoubleRange>(double_range_json).is_err());
+
+        // FlagSet
+        let flag_set_json = "{\"flags\":5,\"mask\":3}";
+        let flag_set: FlagSet = serde_json::from_str(flag_set_json).unwrap();
+        assert_eq!(flag_set.flags(), 5);
+        assert_eq!(flag_set.mask(), 3);
     }
 
     #[cfg(feature = "ser_de")]
@@ -0,0 +1,42 @@
+// Copyright (C) 2018 Sebastian Dröge <sebastian@centricular.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Installs a process-global logger, so this is kept in its own test binary
+#![cfg(feature = "log")]
+
+#[macro_use]
+extern crate gstreamer as gst;
+extern crate log;
+
+use log::Log;
+
+#[test]
+fn forward_both_directions() {
+    gst::init().unwrap();
+
+    let cat = gst::DebugCategory::new(
+        "test-cat-log-bridge-both",
+        gst::DebugColorFlags::empty(),
+        "some debug category",
+    );
+    cat.set_threshold(gst::DebugLevel::Error);
+
+    let log_fn = gst::debug_forward_to_log();
+    gst::DebugCategoryLogger::new(cat).init().unwrap();
+
+    // Would recurse forever if the message was forwarded back again
+    gst_error!(cat, "meh");
+    log::logger().log(
+        &log::Record::builder()
+            .args(format_args!("meh"))
+            .level(log::Level::Error)
+            .build(),
+    );
+
+    gst::debug_remove_log_function(log_fn);
+}